@@ -354,6 +354,42 @@ impl fmt::Display for AllocErr {
     }
 }
 
+/// The error type for `try_reserve` methods.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[unstable(feature = "try_reserve", reason = "new API", issue="48043")]
+pub enum CollectionAllocErr {
+    /// Error due to the computed capacity exceeding the collection's maximum
+    /// (usually `isize::MAX` bytes).
+    CapacityOverflow,
+    /// Error due to the allocator (see the `AllocErr` type's docs).
+    AllocErr(AllocErr),
+}
+
+#[unstable(feature = "try_reserve", reason = "new API", issue="48043")]
+impl From<AllocErr> for CollectionAllocErr {
+    fn from(err: AllocErr) -> Self {
+        CollectionAllocErr::AllocErr(err)
+    }
+}
+
+impl CollectionAllocErr {
+    #[unstable(feature = "try_reserve", reason = "new API", issue="48043")]
+    pub fn description(&self) -> &str {
+        match *self {
+            CollectionAllocErr::CapacityOverflow => "capacity overflow",
+            CollectionAllocErr::AllocErr(ref e) => e.description(),
+        }
+    }
+}
+
+// (we need this for downstream impl of trait Error)
+#[unstable(feature = "try_reserve", reason = "new API", issue="48043")]
+impl fmt::Display for CollectionAllocErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description())
+    }
+}
+
 /// The `CannotReallocInPlace` error is used when `grow_in_place` or
 /// `shrink_in_place` were unable to reuse the given memory block for
 /// a requested layout.
@@ -17,7 +17,7 @@ use core::ops::Index;
 use core::{fmt, intrinsics, mem, ptr};
 
 use borrow::Borrow;
-use Bound::{Excluded, Included, Unbounded};
+use Bound::{self, Excluded, Included, Unbounded};
 use range::RangeArgument;
 
 use super::node::{self, Handle, NodeRef, marker};
@@ -499,6 +499,86 @@ impl<'a, K: 'a + Debug + Ord, V: 'a + Debug> Debug for OccupiedEntry<'a, K, V> {
     }
 }
 
+/// A cursor over a `BTreeMap`.
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth.
+///
+/// Cursors always point to an element in the tree, and index in a logically circular way.
+/// To accommodate this, there is a "ghost" non-element that yields `None` between the last and
+/// first elements of the tree.
+///
+/// A `Cursor` is created with the [`lower_bound`] and [`upper_bound`] methods on [`BTreeMap`].
+///
+/// [`BTreeMap`]: struct.BTreeMap.html
+/// [`lower_bound`]: struct.BTreeMap.html#method.lower_bound
+/// [`upper_bound`]: struct.BTreeMap.html#method.upper_bound
+#[unstable(feature = "btree_cursors", issue = "0")]
+pub struct Cursor<'a, K: 'a, V: 'a> {
+    current: Option<Handle<NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>, marker::KV>>,
+    root: &'a node::Root<K, V>,
+}
+
+#[unstable(feature = "btree_cursors", issue = "0")]
+impl<'a, K, V> Clone for Cursor<'a, K, V> {
+    fn clone(&self) -> Self {
+        Cursor {
+            current: self.current,
+            root: self.root,
+        }
+    }
+}
+
+#[unstable(feature = "btree_cursors", issue = "0")]
+impl<'a, K: Debug, V: Debug> Debug for Cursor<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Cursor")
+         .field(&self.key_value())
+         .finish()
+    }
+}
+
+/// A cursor over a `BTreeMap` with editing operations.
+///
+/// A `CursorMut` is like an iterator, except that it can freely seek back-and-forth, and can
+/// safely mutate the tree during iteration. This is because the lifetime of its yielded
+/// references is tied to its own lifetime, instead of just the underlying tree. This means
+/// cursors cannot yield multiple elements at once.
+///
+/// Cursors always point to an element in the tree, and index in a logically circular way.
+/// To accommodate this, there is a "ghost" non-element that yields `None` between the last and
+/// first elements of the tree.
+///
+/// A `CursorMut` is created with the [`lower_bound_mut`] and [`upper_bound_mut`] methods on
+/// [`BTreeMap`].
+///
+/// [`BTreeMap`]: struct.BTreeMap.html
+/// [`lower_bound_mut`]: struct.BTreeMap.html#method.lower_bound_mut
+/// [`upper_bound_mut`]: struct.BTreeMap.html#method.upper_bound_mut
+#[unstable(feature = "btree_cursors", issue = "0")]
+pub struct CursorMut<'a, K: 'a, V: 'a> {
+    current: Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV>>,
+    root: *mut node::Root<K, V>,
+    length: &'a mut usize,
+
+    // Be invariant in `K` and `V`
+    _marker: PhantomData<&'a mut (K, V)>,
+}
+
+#[unstable(feature = "btree_cursors", issue = "0")]
+unsafe impl<'a, K: Sync, V: Sync> Sync for CursorMut<'a, K, V> {}
+
+#[unstable(feature = "btree_cursors", issue = "0")]
+unsafe impl<'a, K: Send, V: Send> Send for CursorMut<'a, K, V> {}
+
+#[unstable(feature = "btree_cursors", issue = "0")]
+impl<'a, K: Debug, V: Debug> Debug for CursorMut<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CursorMut")
+         .field(&self.key_value())
+         .finish()
+    }
+}
+
 // An iterator for merging two sorted sequences into one
 struct MergeIter<K, V, I: Iterator<Item = (K, V)>> {
     left: Peekable<I>,
@@ -632,6 +712,321 @@ impl<K: Ord, V> BTreeMap<K, V> {
         }
     }
 
+    /// Returns the first key-value pair in the map.
+    /// The key in this pair is the minimum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(map_first_last)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// assert_eq!(map.first_key_value(), None);
+    /// map.insert(1, "b");
+    /// map.insert(2, "a");
+    /// assert_eq!(map.first_key_value(), Some((&1, &"b")));
+    /// ```
+    #[unstable(feature = "map_first_last", issue = "0")]
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        first_leaf_edge(self.root.as_ref()).right_kv().ok().map(Handle::into_kv)
+    }
+
+    /// Returns the first entry in the map for in-place manipulation.
+    /// The key of this entry is the minimum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(map_first_last)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// if let Some(mut entry) = map.first_entry() {
+    ///     if *entry.key() > 0 {
+    ///         entry.insert("first");
+    ///     }
+    /// }
+    /// assert_eq!(*map.get(&1).unwrap(), "first");
+    /// assert_eq!(*map.get(&2).unwrap(), "b");
+    /// ```
+    #[unstable(feature = "map_first_last", issue = "0")]
+    pub fn first_entry(&mut self) -> Option<OccupiedEntry<K, V>> {
+        let handle = first_leaf_edge(self.root.as_mut()).right_kv().ok()?;
+        Some(OccupiedEntry {
+            handle: handle.forget_node_type(),
+            length: &mut self.length,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Removes and returns the first element in the map.
+    /// The key of this element is the minimum key that was in the map.
+    ///
+    /// # Examples
+    ///
+    /// Draining elements in ascending order, while keeping a usable map each iteration.
+    ///
+    /// ```
+    /// #![feature(map_first_last)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// while let Some((key, _val)) = map.pop_first() {
+    ///     assert!(map.iter().all(|(k, _v)| *k > key));
+    /// }
+    /// assert!(map.is_empty());
+    /// ```
+    #[unstable(feature = "map_first_last", issue = "0")]
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        self.first_entry().map(|entry| entry.remove_entry())
+    }
+
+    /// Returns the last key-value pair in the map.
+    /// The key in this pair is the maximum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(map_first_last)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "b");
+    /// map.insert(2, "a");
+    /// assert_eq!(map.last_key_value(), Some((&2, &"a")));
+    /// ```
+    #[unstable(feature = "map_first_last", issue = "0")]
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        last_leaf_edge(self.root.as_ref()).left_kv().ok().map(Handle::into_kv)
+    }
+
+    /// Returns the last entry in the map for in-place manipulation.
+    /// The key of this entry is the maximum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(map_first_last)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// if let Some(mut entry) = map.last_entry() {
+    ///     if *entry.key() > 0 {
+    ///         entry.insert("last");
+    ///     }
+    /// }
+    /// assert_eq!(*map.get(&1).unwrap(), "a");
+    /// assert_eq!(*map.get(&2).unwrap(), "last");
+    /// ```
+    #[unstable(feature = "map_first_last", issue = "0")]
+    pub fn last_entry(&mut self) -> Option<OccupiedEntry<K, V>> {
+        let handle = last_leaf_edge(self.root.as_mut()).left_kv().ok()?;
+        Some(OccupiedEntry {
+            handle: handle.forget_node_type(),
+            length: &mut self.length,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Removes and returns the last element in the map.
+    /// The key of this element is the maximum key that was in the map.
+    ///
+    /// # Examples
+    ///
+    /// Draining elements in descending order, while keeping a usable map each iteration.
+    ///
+    /// ```
+    /// #![feature(map_first_last)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// while let Some((key, _val)) = map.pop_last() {
+    ///     assert!(map.iter().all(|(k, _v)| *k < key));
+    /// }
+    /// assert!(map.is_empty());
+    /// ```
+    #[unstable(feature = "map_first_last", issue = "0")]
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        self.last_entry().map(|entry| entry.remove_entry())
+    }
+
+    /// Returns a [`Cursor`] pointing at the first element that is above the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing `Bound::Unbounded` will return a cursor pointing at the first
+    /// element of the map.
+    ///
+    /// [`Cursor`]: struct.Cursor.html
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    /// use std::collections::BTreeMap;
+    /// use std::collections::Bound;
+    ///
+    /// let mut a = BTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    /// a.insert(3, "c");
+    /// a.insert(4, "d");
+    /// let cursor = a.lower_bound(Bound::Excluded(&2));
+    /// assert_eq!(cursor.key(), Some(&3));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn lower_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<K, V>
+        where K: Borrow<Q>,
+              Q: Ord
+    {
+        Cursor {
+            current: lower_bound_kv(self.root.as_ref(), bound),
+            root: &self.root,
+        }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the first element that is above the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing `Bound::Unbounded` will return a cursor pointing at the first
+    /// element of the map.
+    ///
+    /// [`CursorMut`]: struct.CursorMut.html
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    /// use std::collections::BTreeMap;
+    /// use std::collections::Bound;
+    ///
+    /// let mut a = BTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    /// a.insert(3, "c");
+    /// a.insert(4, "d");
+    /// let mut cursor = a.lower_bound_mut(Bound::Excluded(&2));
+    /// assert_eq!(cursor.remove_current(), Some((3, "c")));
+    /// assert_eq!(cursor.key(), Some(&4));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn lower_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<K, V>
+        where K: Borrow<Q>,
+              Q: Ord
+    {
+        let root: *mut node::Root<K, V> = &mut self.root;
+        CursorMut {
+            current: lower_bound_kv(unsafe { (*root).as_mut() }, bound),
+            root,
+            length: &mut self.length,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a [`Cursor`] pointing at the last element that is below the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing `Bound::Unbounded` will return a cursor pointing at the last
+    /// element of the map.
+    ///
+    /// [`Cursor`]: struct.Cursor.html
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    /// use std::collections::BTreeMap;
+    /// use std::collections::Bound;
+    ///
+    /// let mut a = BTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    /// a.insert(3, "c");
+    /// a.insert(4, "d");
+    /// let cursor = a.upper_bound(Bound::Excluded(&3));
+    /// assert_eq!(cursor.key(), Some(&2));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn upper_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<K, V>
+        where K: Borrow<Q>,
+              Q: Ord
+    {
+        Cursor {
+            current: upper_bound_kv(self.root.as_ref(), bound),
+            root: &self.root,
+        }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the last element that is below the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing `Bound::Unbounded` will return a cursor pointing at the last
+    /// element of the map.
+    ///
+    /// [`CursorMut`]: struct.CursorMut.html
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    /// use std::collections::BTreeMap;
+    /// use std::collections::Bound;
+    ///
+    /// let mut a = BTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    /// a.insert(3, "c");
+    /// a.insert(4, "d");
+    /// let mut cursor = a.upper_bound_mut(Bound::Unbounded);
+    /// assert_eq!(cursor.remove_current_and_move_back(), Some((4, "d")));
+    /// assert_eq!(cursor.key(), Some(&3));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn upper_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<K, V>
+        where K: Borrow<Q>,
+              Q: Ord
+    {
+        let root: *mut node::Root<K, V> = &mut self.root;
+        CursorMut {
+            current: upper_bound_kv(unsafe { (*root).as_mut() }, bound),
+            root,
+            length: &mut self.length,
+            _marker: PhantomData,
+        }
+    }
+
     /// Inserts a key-value pair into the map.
     ///
     /// If the map did not have this key present, `None` is returned.
@@ -1785,6 +2180,96 @@ fn last_leaf_edge<BorrowType, K, V>
     }
 }
 
+/// Finds the first key/value pair to the right of `edge`, climbing up the tree
+/// as needed. `edge` must be a leaf edge or an edge whose subtree has already
+/// been visited.
+fn right_kv_ascending<BorrowType, K, V>
+    (mut edge: Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::Edge>)
+     -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>> {
+    loop {
+        edge = match edge.right_kv() {
+            Ok(kv) => return Some(kv),
+            Err(last_edge) => match last_edge.into_node().ascend() {
+                Ok(parent_edge) => parent_edge.forget_node_type(),
+                Err(_) => return None,
+            },
+        }
+    }
+}
+
+/// The symmetric clone of `right_kv_ascending`.
+fn left_kv_ascending<BorrowType, K, V>
+    (mut edge: Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::Edge>)
+     -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>> {
+    loop {
+        edge = match edge.left_kv() {
+            Ok(kv) => return Some(kv),
+            Err(first_edge) => match first_edge.into_node().ascend() {
+                Ok(parent_edge) => parent_edge.forget_node_type(),
+                Err(_) => return None,
+            },
+        }
+    }
+}
+
+/// Finds the key/value pair that follows `kv` in the tree, if any.
+fn next_kv<BorrowType, K, V>
+    (kv: Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>)
+     -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>> {
+    let edge = match kv.force() {
+        Leaf(leaf_kv) => leaf_kv.right_edge(),
+        Internal(internal_kv) => first_leaf_edge(internal_kv.right_edge().descend()),
+    };
+    right_kv_ascending(edge.forget_node_type())
+}
+
+/// Finds the key/value pair that precedes `kv` in the tree, if any.
+fn prev_kv<BorrowType, K, V>
+    (kv: Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>)
+     -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>> {
+    let edge = match kv.force() {
+        Leaf(leaf_kv) => leaf_kv.left_edge(),
+        Internal(internal_kv) => last_leaf_edge(internal_kv.left_edge().descend()),
+    };
+    left_kv_ascending(edge.forget_node_type())
+}
+
+/// Finds the first key/value pair in the tree rooted at `root` that lies above `bound`.
+fn lower_bound_kv<BorrowType, K, V, Q: ?Sized>
+    (root: NodeRef<BorrowType, K, V, marker::LeafOrInternal>, bound: Bound<&Q>)
+     -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>>
+        where K: Borrow<Q>, Q: Ord {
+    match bound {
+        Unbounded => right_kv_ascending(first_leaf_edge(root).forget_node_type()),
+        Included(key) => match search::search_tree(root, key) {
+            Found(kv) => Some(kv),
+            GoDown(edge) => right_kv_ascending(edge.forget_node_type()),
+        },
+        Excluded(key) => match search::search_tree(root, key) {
+            Found(kv) => next_kv(kv),
+            GoDown(edge) => right_kv_ascending(edge.forget_node_type()),
+        },
+    }
+}
+
+/// Finds the last key/value pair in the tree rooted at `root` that lies below `bound`.
+fn upper_bound_kv<BorrowType, K, V, Q: ?Sized>
+    (root: NodeRef<BorrowType, K, V, marker::LeafOrInternal>, bound: Bound<&Q>)
+     -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>>
+        where K: Borrow<Q>, Q: Ord {
+    match bound {
+        Unbounded => left_kv_ascending(last_leaf_edge(root).forget_node_type()),
+        Included(key) => match search::search_tree(root, key) {
+            Found(kv) => Some(kv),
+            GoDown(edge) => left_kv_ascending(edge.forget_node_type()),
+        },
+        Excluded(key) => match search::search_tree(root, key) {
+            Found(kv) => prev_kv(kv),
+            GoDown(edge) => left_kv_ascending(edge.forget_node_type()),
+        },
+    }
+}
+
 fn range_search<BorrowType, K, V, Q: ?Sized, R: RangeArgument<Q>>(
     root1: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
     root2: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
@@ -2416,52 +2901,301 @@ impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
     }
 
     fn remove_kv(self) -> (K, V) {
-        *self.length -= 1;
+        remove_kv(self.handle, self.length)
+    }
+}
 
-        let (small_leaf, old_key, old_val) = match self.handle.force() {
-            Leaf(leaf) => {
-                let (hole, old_key, old_val) = leaf.remove();
-                (hole.into_node(), old_key, old_val)
-            }
-            Internal(mut internal) => {
-                let key_loc = internal.kv_mut().0 as *mut K;
-                let val_loc = internal.kv_mut().1 as *mut V;
+impl<'a, K, V> Cursor<'a, K, V> {
+    /// Moves the cursor to the next element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn move_next(&mut self) {
+        self.current = match self.current {
+            Some(kv) => next_kv(kv),
+            None => right_kv_ascending(first_leaf_edge(self.root.as_ref()).forget_node_type()),
+        };
+    }
 
-                let to_remove = first_leaf_edge(internal.right_edge().descend()).right_kv().ok();
-                let to_remove = unsafe { unwrap_unchecked(to_remove) };
+    /// Moves the cursor to the previous element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn move_prev(&mut self) {
+        self.current = match self.current {
+            Some(kv) => prev_kv(kv),
+            None => left_kv_ascending(last_leaf_edge(self.root.as_ref()).forget_node_type()),
+        };
+    }
 
-                let (hole, key, val) = to_remove.remove();
+    /// Returns a reference to the key of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn key(&self) -> Option<&'a K> {
+        self.current.map(|kv| kv.into_kv().0)
+    }
 
-                let old_key = unsafe { mem::replace(&mut *key_loc, key) };
-                let old_val = unsafe { mem::replace(&mut *val_loc, val) };
+    /// Returns a reference to the value of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn value(&self) -> Option<&'a V> {
+        self.current.map(|kv| kv.into_kv().1)
+    }
 
-                (hole.into_node(), old_key, old_val)
+    /// Returns a reference to the key and value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn key_value(&self) -> Option<(&'a K, &'a V)> {
+        self.current.map(Handle::into_kv)
+    }
+
+    /// Returns a reference to the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        let mut next = self.clone();
+        next.move_next();
+        next.key_value()
+    }
+
+    /// Returns a reference to the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        let mut prev = self.clone();
+        prev.move_prev();
+        prev.key_value()
+    }
+}
+
+impl<'a, K, V> CursorMut<'a, K, V> {
+    /// Moves the cursor to the next element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn move_next(&mut self) {
+        self.current = match self.current.take() {
+            Some(kv) => next_kv(kv),
+            None => {
+                let root = unsafe { (*self.root).as_mut() };
+                right_kv_ascending(first_leaf_edge(root).forget_node_type())
             }
         };
+    }
 
-        // Handle underflow
-        let mut cur_node = small_leaf.forget_type();
-        while cur_node.len() < node::CAPACITY / 2 {
-            match handle_underfull_node(cur_node) {
-                AtRoot => break,
-                EmptyParent(_) => unreachable!(),
-                Merged(parent) => {
-                    if parent.len() == 0 {
-                        // We must be at the root
-                        parent.into_root_mut().pop_level();
-                        break;
-                    } else {
-                        cur_node = parent.forget_type();
-                    }
-                }
-                Stole(_) => break,
+    /// Moves the cursor to the previous element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn move_prev(&mut self) {
+        self.current = match self.current.take() {
+            Some(kv) => prev_kv(kv),
+            None => {
+                let root = unsafe { (*self.root).as_mut() };
+                left_kv_ascending(last_leaf_edge(root).forget_node_type())
             }
+        };
+    }
+
+    /// Returns a reference to the key of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn key(&self) -> Option<&K> {
+        self.current.as_ref().map(|kv| kv.reborrow().into_kv().0)
+    }
+
+    /// Returns a reference to the value of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn value(&self) -> Option<&V> {
+        self.current.as_ref().map(|kv| kv.reborrow().into_kv().1)
+    }
+
+    /// Returns a reference to the key and value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn key_value(&self) -> Option<(&K, &V)> {
+        self.current.as_ref().map(|kv| kv.reborrow().into_kv())
+    }
+
+    /// Returns a mutable reference to the value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn value_mut(&mut self) -> Option<&mut V> {
+        self.current.as_mut().map(|kv| kv.kv_mut().1)
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of
+    /// the element that the cursor is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn key_value_mut(&mut self) -> Option<(&K, &mut V)> {
+        self.current.as_mut().map(|kv| {
+            let (k, v) = kv.kv_mut();
+            (&*k, v)
+        })
+    }
+
+    /// Returns a reference to the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_next(&self) -> Option<(&K, &V)> {
+        self.as_cursor().peek_next()
+    }
+
+    /// Returns a reference to the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_prev(&self) -> Option<(&K, &V)> {
+        self.as_cursor().peek_prev()
+    }
+
+    /// Returns a read-only cursor pointing to the current element.
+    ///
+    /// The lifetime of the returned `Cursor` is bound to that of the
+    /// `CursorMut`, which means it cannot outlive the `CursorMut` and that the
+    /// `CursorMut` is frozen for the lifetime of the `Cursor`.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn as_cursor(&self) -> Cursor<K, V> {
+        Cursor {
+            current: self.current.as_ref().map(|kv| kv.reborrow()),
+            root: unsafe { &*self.root },
         }
+    }
+}
 
-        (old_key, old_val)
+impl<'a, K: Ord, V> CursorMut<'a, K, V> {
+    /// Removes the current element from the `BTreeMap`.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the next element in the `BTreeMap`.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and `None` is returned. The cursor is not moved in this case.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        let current = self.current.take()?;
+        let (key, val) = remove_kv(current, self.length);
+        // Rebalancing may have moved any element around, so find our way back
+        // from the root.
+        let root = unsafe { (*self.root).as_mut() };
+        self.current = lower_bound_kv(root, Excluded(&key));
+        Some((key, val))
+    }
+
+    /// Removes the current element from the `BTreeMap`.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the previous element in the `BTreeMap`.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and `None` is returned. The cursor is not moved in this case.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn remove_current_and_move_back(&mut self) -> Option<(K, V)> {
+        let current = self.current.take()?;
+        let (key, val) = remove_kv(current, self.length);
+        // Rebalancing may have moved any element around, so find our way back
+        // from the root.
+        let root = unsafe { (*self.root).as_mut() };
+        self.current = upper_bound_kv(root, Excluded(&key));
+        Some((key, val))
     }
 }
 
+/// Removes the key/value pair at `handle` from its tree, rebalancing as needed,
+/// and decrements `length`. Any other handles into the tree are invalidated.
+fn remove_kv<'a, K, V>(handle: Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
+                                      marker::KV>,
+                       length: &mut usize)
+                       -> (K, V) {
+    *length -= 1;
+
+    let (small_leaf, old_key, old_val) = match handle.force() {
+        Leaf(leaf) => {
+            let (hole, old_key, old_val) = leaf.remove();
+            (hole.into_node(), old_key, old_val)
+        }
+        Internal(mut internal) => {
+            let key_loc = internal.kv_mut().0 as *mut K;
+            let val_loc = internal.kv_mut().1 as *mut V;
+
+            let to_remove = first_leaf_edge(internal.right_edge().descend()).right_kv().ok();
+            let to_remove = unsafe { unwrap_unchecked(to_remove) };
+
+            let (hole, key, val) = to_remove.remove();
+
+            let old_key = unsafe { mem::replace(&mut *key_loc, key) };
+            let old_val = unsafe { mem::replace(&mut *val_loc, val) };
+
+            (hole.into_node(), old_key, old_val)
+        }
+    };
+
+    // Handle underflow
+    let mut cur_node = small_leaf.forget_type();
+    while cur_node.len() < node::CAPACITY / 2 {
+        match handle_underfull_node(cur_node) {
+            AtRoot => break,
+            EmptyParent(_) => unreachable!(),
+            Merged(parent) => {
+                if parent.len() == 0 {
+                    // We must be at the root
+                    parent.into_root_mut().pop_level();
+                    break;
+                } else {
+                    cur_node = parent.forget_type();
+                }
+            }
+            Stole(_) => break,
+        }
+    }
+
+    (old_key, old_val)
+}
+
 enum UnderflowResult<'a, K, V> {
     AtRoot,
     EmptyParent(NodeRef<marker::Mut<'a>, K, V, marker::Internal>),
@@ -1462,6 +1462,22 @@ impl<BorrowType, K, V, HandleType>
     }
 }
 
+impl<BorrowType, K, V, NodeType, HandleType>
+        Handle<NodeRef<BorrowType, K, V, NodeType>, HandleType> {
+
+    /// Removes any static information about whether the underlying node is a
+    /// `Leaf` or an `Internal` node.
+    pub fn forget_node_type(self)
+            -> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, HandleType> {
+
+        Handle {
+            node: self.node.forget_type(),
+            idx: self.idx,
+            _marker: PhantomData
+        }
+    }
+}
+
 impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
     /// Move the suffix after `self` from one node to another one. `right` must be empty.
     /// The first edge of `right` remains unchanged.
@@ -685,3 +685,147 @@ fn test_split_off_large_random_sorted() {
     assert!(map.into_iter().eq(data.clone().into_iter().filter(|x| x.0 < key)));
     assert!(right.into_iter().eq(data.into_iter().filter(|x| x.0 >= key)));
 }
+
+#[test]
+fn test_first_last_entry() {
+    let mut a = BTreeMap::new();
+    assert_eq!(a.first_key_value(), None);
+    assert_eq!(a.last_key_value(), None);
+    assert!(a.first_entry().is_none());
+    assert!(a.last_entry().is_none());
+    assert_eq!(a.pop_first(), None);
+    assert_eq!(a.pop_last(), None);
+    a.insert(1, 42);
+    assert_eq!(a.first_key_value(), Some((&1, &42)));
+    assert_eq!(a.last_key_value(), Some((&1, &42)));
+    assert_eq!(a.first_entry().unwrap().key(), &1);
+    assert_eq!(a.last_entry().unwrap().key(), &1);
+    a.insert(2, 24);
+    assert_eq!(a.first_key_value(), Some((&1, &42)));
+    assert_eq!(a.last_key_value(), Some((&2, &24)));
+    a.insert(0, 6);
+    assert_eq!(a.first_key_value(), Some((&0, &6)));
+    assert_eq!(a.last_key_value(), Some((&2, &24)));
+    let (k1, v1) = a.first_entry().unwrap().remove_entry();
+    assert_eq!((k1, v1), (0, 6));
+    let (k2, v2) = a.last_entry().unwrap().remove_entry();
+    assert_eq!((k2, v2), (2, 24));
+    assert_eq!(a.first_key_value(), Some((&1, &42)));
+    assert_eq!(a.last_key_value(), Some((&1, &42)));
+}
+
+#[test]
+fn test_pop_first_last_large() {
+    let size = 10000;
+    let mut map: BTreeMap<_, _> = (0..size).map(|i| (i, i * 10)).collect();
+
+    for i in 0..size / 2 {
+        assert_eq!(map.pop_first(), Some((i, i * 10)));
+        assert_eq!(map.pop_last(), Some((size - i - 1, (size - i - 1) * 10)));
+        assert_eq!(map.len(), size - 2 * (i + 1));
+    }
+    assert_eq!(map.pop_first(), None);
+    assert_eq!(map.pop_last(), None);
+}
+
+#[test]
+fn test_cursor() {
+    let map: BTreeMap<_, _> = (0..5).map(|i| (i * 2, i * 20)).collect();
+
+    let mut cur = map.lower_bound(Unbounded);
+    assert_eq!(cur.key_value(), Some((&0, &0)));
+    assert_eq!(cur.peek_prev(), None);
+    assert_eq!(cur.peek_next(), Some((&2, &20)));
+    cur.move_prev();
+    assert_eq!(cur.key_value(), None);
+    assert_eq!(cur.peek_prev(), Some((&8, &80)));
+    assert_eq!(cur.peek_next(), Some((&0, &0)));
+    cur.move_prev();
+    assert_eq!(cur.key(), Some(&8));
+    cur.move_next();
+    assert_eq!(cur.key(), None);
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&0));
+
+    assert_eq!(map.lower_bound(Included(&4)).key(), Some(&4));
+    assert_eq!(map.lower_bound(Excluded(&4)).key(), Some(&6));
+    assert_eq!(map.lower_bound(Included(&5)).key(), Some(&6));
+    assert_eq!(map.lower_bound(Excluded(&8)).key(), None);
+    assert_eq!(map.upper_bound(Included(&4)).key(), Some(&4));
+    assert_eq!(map.upper_bound(Excluded(&4)).key(), Some(&2));
+    assert_eq!(map.upper_bound(Included(&5)).key(), Some(&4));
+    assert_eq!(map.upper_bound(Excluded(&0)).key(), None);
+    assert_eq!(map.upper_bound(Unbounded).key(), Some(&8));
+
+    let empty: BTreeMap<i32, i32> = BTreeMap::new();
+    let mut cur = empty.lower_bound(Unbounded);
+    assert_eq!(cur.key(), None);
+    cur.move_next();
+    assert_eq!(cur.key(), None);
+    cur.move_prev();
+    assert_eq!(cur.key(), None);
+}
+
+#[test]
+fn test_cursor_walk_large() {
+    let size = 1000;
+    let map: BTreeMap<_, _> = (0..size).map(|i| (i, i)).collect();
+
+    let mut cur = map.lower_bound(Unbounded);
+    for i in 0..size {
+        assert_eq!(cur.key(), Some(&i));
+        cur.move_next();
+    }
+    assert_eq!(cur.key(), None);
+    for i in (0..size).rev() {
+        cur.move_prev();
+        assert_eq!(cur.key(), Some(&i));
+    }
+}
+
+#[test]
+fn test_cursor_mut() {
+    let mut map: BTreeMap<_, _> = (0..5).map(|i| (i, i)).collect();
+    {
+        let mut cur = map.lower_bound_mut(Excluded(&1));
+        assert_eq!(cur.key_value(), Some((&2, &2)));
+        *cur.value_mut().unwrap() = 20;
+        assert_eq!(cur.peek_prev(), Some((&1, &1)));
+        assert_eq!(cur.peek_next(), Some((&3, &3)));
+        assert_eq!(cur.remove_current(), Some((2, 20)));
+        assert_eq!(cur.key(), Some(&3));
+        assert_eq!(cur.remove_current_and_move_back(), Some((3, 3)));
+        assert_eq!(cur.key(), Some(&1));
+        cur.move_prev();
+        cur.move_prev();
+        assert_eq!(cur.key(), None);
+        assert_eq!(cur.remove_current(), None);
+        cur.move_prev();
+        assert_eq!(cur.remove_current(), Some((4, 4)));
+        assert_eq!(cur.key(), None);
+    }
+    assert_eq!(map.into_iter().collect::<Vec<_>>(), vec![(0, 0), (1, 1)]);
+}
+
+#[test]
+fn test_cursor_mut_remove_large() {
+    let size = 1000;
+    let mut map: BTreeMap<_, _> = (0..size).map(|i| (i, i)).collect();
+    {
+        // Remove every odd key in a single pass.
+        let mut cur = map.lower_bound_mut(Unbounded);
+        loop {
+            let k = match cur.key() {
+                Some(&k) => k,
+                None => break,
+            };
+            if k % 2 == 1 {
+                assert_eq!(cur.remove_current(), Some((k, k)));
+            } else {
+                cur.move_next();
+            }
+        }
+    }
+    assert_eq!(map.len(), size / 2);
+    assert!(map.keys().cloned().eq((0..size).filter(|k| k % 2 == 0)));
+}
@@ -9,6 +9,8 @@
 // except according to those terms.
 
 use std::borrow::Cow;
+use std::collections::CollectionAllocErr::*;
+use std::usize;
 
 pub trait IntoCow<'a, B: ?Sized> where B: ToOwned {
     fn into_cow(self) -> Cow<'a, B>;
@@ -504,3 +506,16 @@ fn test_into_boxed_str() {
     let ys = xs.into_boxed_str();
     assert_eq!(&*ys, "hello my name is bob");
 }
+
+#[test]
+fn test_try_reserve() {
+    let mut empty = String::new();
+    empty.try_reserve(10).expect("small reservation should succeed");
+    assert!(empty.capacity() >= 10);
+
+    let mut one = String::from("a");
+    if let Err(CapacityOverflow) = one.try_reserve(usize::MAX) {
+    } else { panic!("len + usize::MAX should trigger an overflow!") }
+    if let Err(CapacityOverflow) = one.try_reserve_exact(usize::MAX) {
+    } else { panic!("len + usize::MAX should trigger an overflow!") }
+}
@@ -12,6 +12,8 @@ use std::borrow::Cow;
 use std::mem::size_of;
 use std::panic;
 use std::vec::{Drain, IntoIter};
+use std::collections::CollectionAllocErr::*;
+use std::usize;
 
 struct DropCounter<'a> {
     count: &'a mut u32,
@@ -965,3 +967,33 @@ fn drain_filter_complex() {
         assert_eq!(vec, vec![1, 3, 5, 7, 9, 11, 13, 15, 17, 19]);
     }
 }
+
+#[test]
+fn test_try_reserve() {
+    let mut empty: Vec<u8> = Vec::new();
+    empty.try_reserve(10).expect("small reservation should succeed");
+    assert!(empty.capacity() >= 10);
+
+    let mut one: Vec<u8> = vec![1];
+    if let Err(CapacityOverflow) = one.try_reserve(usize::MAX) {
+    } else { panic!("len + usize::MAX should trigger an overflow!") }
+
+    let mut ints: Vec<u32> = Vec::new();
+    if let Err(CapacityOverflow) = ints.try_reserve(usize::MAX / 2) {
+    } else { panic!("usize::MAX / 2 u32s should trigger an overflow!") }
+}
+
+#[test]
+fn test_try_reserve_exact() {
+    let mut empty: Vec<u8> = Vec::new();
+    empty.try_reserve_exact(10).expect("small reservation should succeed");
+    assert!(empty.capacity() >= 10);
+
+    let mut one: Vec<u8> = vec![1];
+    if let Err(CapacityOverflow) = one.try_reserve_exact(usize::MAX) {
+    } else { panic!("len + usize::MAX should trigger an overflow!") }
+
+    let mut ints: Vec<u32> = Vec::new();
+    if let Err(CapacityOverflow) = ints.try_reserve_exact(usize::MAX / 2) {
+    } else { panic!("usize::MAX / 2 u32s should trigger an overflow!") }
+}
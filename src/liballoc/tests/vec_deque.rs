@@ -11,6 +11,8 @@
 use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::collections::vec_deque::{Drain};
+use std::collections::CollectionAllocErr::*;
+use std::usize;
 
 use self::Taggy::*;
 use self::Taggypar::*;
@@ -1022,3 +1024,19 @@ fn test_placement_in() {
     }
     assert_eq!(buf, [5,4,3,1,2,6]);
 }
+
+#[test]
+fn test_try_reserve() {
+    let mut empty: VecDeque<u8> = VecDeque::new();
+    empty.try_reserve(10).expect("small reservation should succeed");
+    assert!(empty.capacity() >= 10);
+    empty.try_reserve_exact(20).expect("small reservation should succeed");
+    assert!(empty.capacity() >= 20);
+
+    let mut one: VecDeque<u8> = VecDeque::new();
+    one.push_back(1);
+    if let Err(CapacityOverflow) = one.try_reserve(usize::MAX) {
+    } else { panic!("len + usize::MAX should trigger an overflow!") }
+    if let Err(CapacityOverflow) = one.try_reserve_exact(usize::MAX) {
+    } else { panic!("len + usize::MAX should trigger an overflow!") }
+}
@@ -172,6 +172,7 @@ pub mod str;
 pub mod hash;
 pub mod fmt;
 pub mod time;
+pub mod primitive;
 
 // note: does not need to be public
 mod char_private;
@@ -0,0 +1,20 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Aliases of primitive types, for built-in macros that have to name them
+//! through `$crate` since the plain names can be shadowed at the call site.
+
+#![unstable(feature = "primitive_internals",
+            reason = "used by built-in macros only",
+            issue = "0")]
+#![doc(hidden)]
+
+/// `bool`, the type of the constants `static_assert!` expands to.
+pub type Bool = bool;
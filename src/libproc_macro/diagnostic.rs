@@ -28,6 +28,34 @@ pub enum Level {
     __Nonexhaustive,
 }
 
+/// Trait implemented by types that can be converted into a set of `Span`s.
+#[unstable(feature = "proc_macro", issue = "38356")]
+pub trait MultiSpan {
+    /// Converts `self` into a `Vec<Span>`.
+    fn into_spans(self) -> Vec<Span>;
+}
+
+#[unstable(feature = "proc_macro", issue = "38356")]
+impl MultiSpan for Span {
+    fn into_spans(self) -> Vec<Span> {
+        vec![self]
+    }
+}
+
+#[unstable(feature = "proc_macro", issue = "38356")]
+impl MultiSpan for Vec<Span> {
+    fn into_spans(self) -> Vec<Span> {
+        self
+    }
+}
+
+#[unstable(feature = "proc_macro", issue = "38356")]
+impl<'a> MultiSpan for &'a [Span] {
+    fn into_spans(self) -> Vec<Span> {
+        self.to_vec()
+    }
+}
+
 /// A structure representing a diagnostic message and associated children
 /// messages.
 #[unstable(feature = "proc_macro", issue = "38356")]
@@ -35,17 +63,29 @@ pub enum Level {
 pub struct Diagnostic {
     level: Level,
     message: String,
-    span: Option<Span>,
+    spans: Vec<Span>,
+    labels: Vec<(Span, String)>,
+    suggestions: Vec<Suggestion>,
     children: Vec<Diagnostic>
 }
 
+/// A suggested replacement of the source text of a span.
+#[derive(Clone, Debug)]
+struct Suggestion {
+    span: Span,
+    message: String,
+    replacement: String,
+}
+
 macro_rules! diagnostic_child_methods {
     ($spanned:ident, $regular:ident, $level:expr) => (
         /// Add a new child diagnostic message to `self` with the level
-        /// identified by this methods name with the given `span` and `message`.
+        /// identified by this methods name with the given `spans` and `message`.
         #[unstable(feature = "proc_macro", issue = "38356")]
-        pub fn $spanned<T: Into<String>>(mut self, span: Span, message: T) -> Diagnostic {
-            self.children.push(Diagnostic::spanned(span, $level, message));
+        pub fn $spanned<S, T>(mut self, spans: S, message: T) -> Diagnostic
+            where S: MultiSpan, T: Into<String>
+        {
+            self.children.push(Diagnostic::spanned(spans, $level, message));
             self
         }
 
@@ -66,23 +106,52 @@ impl Diagnostic {
         Diagnostic {
             level: level,
             message: message.into(),
-            span: None,
+            spans: vec![],
+            labels: vec![],
+            suggestions: vec![],
             children: vec![]
         }
     }
 
     /// Create a new diagnostic with the given `level` and `message` pointing to
-    /// the given `span`.
+    /// the given set of `spans`.
     #[unstable(feature = "proc_macro", issue = "38356")]
-    pub fn spanned<T: Into<String>>(span: Span, level: Level, message: T) -> Diagnostic {
+    pub fn spanned<S, T>(spans: S, level: Level, message: T) -> Diagnostic
+        where S: MultiSpan, T: Into<String>
+    {
         Diagnostic {
             level: level,
             message: message.into(),
-            span: Some(span),
+            spans: spans.into_spans(),
+            labels: vec![],
+            suggestions: vec![],
             children: vec![]
         }
     }
 
+    /// Add a label with the given `message` to `span`, pointing at another
+    /// part of the source code from within `self`.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn span_label<T: Into<String>>(mut self, span: Span, message: T) -> Diagnostic {
+        self.labels.push((span, message.into()));
+        self
+    }
+
+    /// Suggest replacing the source text of `span` with `replacement`, with
+    /// `message` describing the change. Like the compiler's own suggestions,
+    /// these are shown inline and can be applied automatically by tools.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn span_suggestion<T, U>(mut self, span: Span, message: T, replacement: U) -> Diagnostic
+        where T: Into<String>, U: Into<String>
+    {
+        self.suggestions.push(Suggestion {
+            span,
+            message: message.into(),
+            replacement: replacement.into(),
+        });
+        self
+    }
+
     diagnostic_child_methods!(span_error, error, Level::Error);
     diagnostic_child_methods!(span_warning, warning, Level::Warning);
     diagnostic_child_methods!(span_note, note, Level::Note);
@@ -94,6 +163,18 @@ impl Diagnostic {
         self.level
     }
 
+    /// Returns the message of `self`.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the spans `self` points to.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
     /// Emit the diagnostic.
     #[unstable(feature = "proc_macro", issue = "38356")]
     pub fn emit(self) {
@@ -102,16 +183,30 @@ impl Diagnostic {
             let level = __internal::level_to_internal_level(self.level);
             let mut diag = rustc::DiagnosticBuilder::new(handler, level, &*self.message);
 
-            if let Some(span) = self.span {
-                diag.set_span(span.0);
+            if !self.spans.is_empty() {
+                diag.set_span(__internal::to_internal_multispan(self.spans));
+            }
+
+            for (span, label) in self.labels {
+                diag.span_label(span.0, label);
             }
 
             for child in self.children {
-                let span = child.span.map(|s| s.0);
+                let span = if child.spans.is_empty() {
+                    None
+                } else {
+                    Some(__internal::to_internal_multispan(child.spans))
+                };
                 let level = __internal::level_to_internal_level(child.level);
                 diag.sub(level, &*child.message, span);
             }
 
+            for suggestion in self.suggestions {
+                diag.span_suggestion(suggestion.span.0,
+                                     &suggestion.message,
+                                     suggestion.replacement);
+            }
+
             diag.emit();
         });
     }
@@ -120,7 +215,12 @@ impl Diagnostic {
 #[unstable(feature = "proc_macro_internals", issue = "27812")]
 #[doc(hidden)]
 pub mod __internal {
-    use super::{Level, rustc};
+    use super::{Level, Span, rustc};
+    use syntax_pos::MultiSpan;
+
+    pub fn to_internal_multispan(spans: Vec<Span>) -> MultiSpan {
+        MultiSpan::from_spans(spans.into_iter().map(|s| s.0).collect())
+    }
 
     pub fn level_to_internal_level(level: Level) -> rustc::Level {
         match level {
@@ -47,9 +47,9 @@ extern crate rustc_errors;
 mod diagnostic;
 
 #[unstable(feature = "proc_macro", issue = "38356")]
-pub use diagnostic::{Diagnostic, Level};
+pub use diagnostic::{Diagnostic, Level, MultiSpan};
 
-use std::{ascii, fmt, iter};
+use std::{ascii, error, fmt, iter};
 use std::rc::Rc;
 use std::str::FromStr;
 
@@ -82,6 +82,27 @@ pub struct LexError {
     _inner: (),
 }
 
+/// Error returned from `TokenStream::expand_expr`.
+#[unstable(feature = "proc_macro", issue = "38356")]
+#[derive(Debug)]
+pub struct ExpandError {
+    _inner: (),
+}
+
+#[unstable(feature = "proc_macro", issue = "38356")]
+impl fmt::Display for ExpandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("cannot expand token stream as an expression")
+    }
+}
+
+#[unstable(feature = "proc_macro", issue = "38356")]
+impl error::Error for ExpandError {
+    fn description(&self) -> &str {
+        "cannot expand token stream as an expression"
+    }
+}
+
 #[stable(feature = "proc_macro_lib", since = "1.15.0")]
 impl FromStr for TokenStream {
     type Err = LexError;
@@ -165,6 +186,18 @@ impl TokenStream {
         TokenStream(tokenstream::TokenStream::empty())
     }
 
+    /// Parses `self` as an expression and eagerly expands the macro
+    /// invocations in it, like `concat!`, `env!` or `include_str!`, returning
+    /// the tokens of the expanded expression. An expression that expands to a
+    /// literal results in that single literal token.
+    ///
+    /// Any errors from parsing or expanding the expression are reported like
+    /// any other, and an `ExpandError` is returned.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn expand_expr(&self) -> Result<TokenStream, ExpandError> {
+        __internal::expand_expr(self.clone())
+    }
+
     /// Checks if this `TokenStream` is empty.
     #[unstable(feature = "proc_macro", issue = "38356")]
     pub fn is_empty(&self) -> bool {
@@ -256,6 +289,16 @@ impl Span {
         }
     }
 
+    /// The source text behind `self`, exactly as written, including any
+    /// whitespace and comments.
+    ///
+    /// Returns `None` if the source code isn't available, e.g. for spans
+    /// pointing into another crate.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn source_text(&self) -> Option<String> {
+        ::__internal::with_sess(|(sess, _)| sess.codemap().span_to_snippet(self.0).ok())
+    }
+
     /// Create a new span encompassing `self` and `other`.
     ///
     /// Returns `None` if `self` and `other` are from different files.
@@ -767,13 +810,14 @@ pub mod __internal {
     use syntax::ast;
     use syntax::ext::base::ExtCtxt;
     use syntax::ext::hygiene::Mark;
+    use syntax::fold::Folder;
     use syntax::ptr::P;
     use syntax::parse::{self, ParseSess};
     use syntax::parse::token::{self, Token};
     use syntax::tokenstream;
     use syntax_pos::{BytePos, Loc, DUMMY_SP};
 
-    use super::{TokenStream, LexError};
+    use super::{TokenStream, LexError, ExpandError};
 
     pub fn lookup_char_pos(pos: BytePos) -> Loc {
         with_sess(|(sess, _)| sess.codemap().lookup_char_pos(pos))
@@ -805,6 +849,40 @@ pub mod __internal {
         stream.0
     }
 
+    pub fn expand_expr(stream: TokenStream) -> Result<TokenStream, ExpandError> {
+        with_ext_ctxt(move |cx| {
+            let error_count_before = cx.parse_sess.span_diagnostic.err_count();
+            let mut parser = parse::stream_to_parser(cx.parse_sess, stream.0);
+            let expr = match parser.parse_expr() {
+                Ok(expr) => expr,
+                Err(mut err) => {
+                    err.emit();
+                    return Err(ExpandError { _inner: () });
+                }
+            };
+            if parser.token != token::Eof {
+                cx.span_err(parser.span, "expected the end of the expression");
+                return Err(ExpandError { _inner: () });
+            }
+
+            // Expand the expression as if it had been written at the call site
+            // of the procedural macro, like `include!` and friends expect.
+            let expr = expr.map(|mut expr| {
+                expr.span = expr.span.with_ctxt(
+                    expr.span.ctxt().apply_mark(cx.current_expansion.mark));
+                expr
+            });
+            let expr = cx.expander().fold_expr(expr);
+            if cx.parse_sess.span_diagnostic.err_count() > error_count_before {
+                return Err(ExpandError { _inner: () });
+            }
+
+            let span = expr.span;
+            let token = Token::interpolated(token::NtExpr(expr));
+            Ok(TokenStream(token.interpolated_to_tokenstream(cx.parse_sess, span)))
+        })
+    }
+
     pub trait Registry {
         fn register_custom_derive(&mut self,
                                   trait_name: &str,
@@ -824,24 +902,40 @@ pub mod __internal {
     thread_local! {
         static CURRENT_SESS: Cell<(*const ParseSess, Mark)> =
             Cell::new((0 as *const _, Mark::root()));
+
+        // The `ExtCtxt` handed to `set_sess`, with its type erased since a
+        // thread local cannot name its lifetime, and whether it is currently
+        // lent out by `with_ext_ctxt`.
+        static CURRENT_EXT_CTXT: Cell<(*mut (), bool)> =
+            Cell::new((0 as *mut _, false));
     }
 
-    pub fn set_sess<F, R>(cx: &ExtCtxt, f: F) -> R
+    pub fn set_sess<'a, F, R>(cx: &mut ExtCtxt<'a>, f: F) -> R
         where F: FnOnce() -> R
     {
-        struct Reset { prev: (*const ParseSess, Mark) }
+        struct Reset {
+            prev_sess: (*const ParseSess, Mark),
+            prev_ext_ctxt: (*mut (), bool),
+        }
 
         impl Drop for Reset {
             fn drop(&mut self) {
-                CURRENT_SESS.with(|p| p.set(self.prev));
+                CURRENT_SESS.with(|p| p.set(self.prev_sess));
+                CURRENT_EXT_CTXT.with(|p| p.set(self.prev_ext_ctxt));
             }
         }
 
-        CURRENT_SESS.with(|p| {
-            let _reset = Reset { prev: p.get() };
-            p.set((cx.parse_sess, cx.current_expansion.mark));
-            f()
-        })
+        let _reset = Reset {
+            prev_sess: CURRENT_SESS.with(|p| p.get()),
+            prev_ext_ctxt: CURRENT_EXT_CTXT.with(|p| p.get()),
+        };
+        let sess = cx.parse_sess;
+        let mark = cx.current_expansion.mark;
+        CURRENT_SESS.with(|p| p.set((sess, mark)));
+        // A macro expanded by `with_ext_ctxt` gets a reborrow of the context
+        // being lent out, which is free to be lent out again.
+        CURRENT_EXT_CTXT.with(|p| p.set((cx as *mut ExtCtxt<'a> as *mut (), false)));
+        f()
     }
 
     pub fn with_sess<F, R>(f: F) -> R
@@ -852,6 +946,33 @@ pub mod __internal {
                                  before set_parse_sess()!");
         f(unsafe { (&*p.0, p.1) })
     }
+
+    /// Runs `f` with the expansion context of the procedural macro currently
+    /// being expanded, which `set_sess` hands over for the duration of the
+    /// expansion.
+    ///
+    /// The context is mutably borrowed by whoever called `set_sess`, so it is
+    /// only lent out once at a time: calling this again from within `f`
+    /// panics.
+    fn with_ext_ctxt<F, R>(f: F) -> R
+        where F: for<'a> FnOnce(&mut ExtCtxt<'a>) -> R
+    {
+        struct Release;
+
+        impl Drop for Release {
+            fn drop(&mut self) {
+                CURRENT_EXT_CTXT.with(|p| p.set((p.get().0, false)));
+            }
+        }
+
+        let (cx, lent) = CURRENT_EXT_CTXT.with(|p| p.get());
+        assert!(!cx.is_null(), "proc_macro::__internal::with_ext_ctxt() called \
+                                before set_parse_sess()!");
+        assert!(!lent, "proc_macro::__internal::with_ext_ctxt() called re-entrantly");
+        CURRENT_EXT_CTXT.with(|p| p.set((cx, true)));
+        let _release = Release;
+        f(unsafe { &mut *(cx as *mut ExtCtxt) })
+    }
 }
 
 fn parse_to_lex_err(mut err: DiagnosticBuilder) -> LexError {
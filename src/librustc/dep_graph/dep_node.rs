@@ -244,6 +244,7 @@ macro_rules! define_dep_nodes {
                                 if cfg!(debug_assertions) &&
                                    !dep_node.kind.can_reconstruct_query_key() &&
                                    (tcx.sess.opts.debugging_opts.incremental_info ||
+                                    tcx.sess.opts.debugging_opts.incremental_explain.is_some() ||
                                     tcx.sess.opts.debugging_opts.query_dep_graph)
                                 {
                                     tcx.dep_graph.register_dep_node_debug_str(dep_node, || {
@@ -267,6 +268,7 @@ macro_rules! define_dep_nodes {
                                 if cfg!(debug_assertions) &&
                                    !dep_node.kind.can_reconstruct_query_key() &&
                                    (tcx.sess.opts.debugging_opts.incremental_info ||
+                                    tcx.sess.opts.debugging_opts.incremental_explain.is_some() ||
                                     tcx.sess.opts.debugging_opts.query_dep_graph)
                                 {
                                     tcx.dep_graph.register_dep_node_debug_str(dep_node, || {
@@ -504,6 +506,7 @@ define_dep_nodes!( <'tcx>
     [] ItemVarianceConstraints(DefId),
     [] ItemVariances(DefId),
     [] IsConstFn(DefId),
+    [] CrossCrateInlinable(DefId),
     [] IsForeignItem(DefId),
     [] TypeParamPredicates { item_id: DefId, param_id: DefId },
     [] SizedConstraint(DefId),
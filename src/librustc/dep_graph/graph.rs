@@ -63,6 +63,18 @@ impl DepNodeColor {
     }
 }
 
+/// The reason why a DepNode could not be marked as green and had to be
+/// recomputed. Only recorded if `-Z incremental-explain` is specified.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RecomputeReason {
+    /// The DepNode did not exist in the previous compilation session.
+    New,
+    /// The value of this dependency changed.
+    DependencyChanged(DepNode),
+    /// This dependency does not exist anymore or could not be recomputed.
+    DependencyRemoved(DepNode),
+}
+
 struct DepGraphData {
     /// The new encoding of the dependency graph, optimized for red/green
     /// tracking. The `current` field is the dependency graph of only the
@@ -89,6 +101,9 @@ struct DepGraphData {
 
     // Used for testing, only populated when -Zquery-dep-graph is specified.
     loaded_from_cache: RefCell<FxHashMap<DepNodeIndex, bool>>,
+
+    // Only populated when -Zincremental-explain is specified.
+    recompute_reasons: RefCell<FxHashMap<DepNode, RecomputeReason>>,
 }
 
 impl DepGraph {
@@ -108,6 +123,7 @@ impl DepGraph {
                 previous: prev_graph,
                 colors: RefCell::new(FxHashMap()),
                 loaded_from_cache: RefCell::new(FxHashMap()),
+                recompute_reasons: RefCell::new(FxHashMap()),
             })),
             fingerprints: Rc::new(RefCell::new(fingerprints)),
         }
@@ -422,6 +438,22 @@ impl DepGraph {
         self.data.as_ref().unwrap().work_products.borrow()
     }
 
+    /// Access the reasons why DepNodes from the previous compilation session
+    /// had to be recomputed. Only populated when `-Z incremental-explain` is
+    /// specified.
+    pub fn recompute_reasons(&self) -> Ref<FxHashMap<DepNode, RecomputeReason>> {
+        self.data.as_ref().unwrap().recompute_reasons.borrow()
+    }
+
+    fn record_recompute_reason(&self,
+                               tcx: TyCtxt,
+                               dep_node: &DepNode,
+                               reason: RecomputeReason) {
+        if tcx.sess.opts.debugging_opts.incremental_explain.is_some() {
+            self.data.as_ref().unwrap().recompute_reasons.borrow_mut().insert(*dep_node, reason);
+        }
+    }
+
     /// Access the map of work-products created during the cached run. Only
     /// used during saving of the dep-graph.
     pub fn previous_work_products(&self) -> Ref<FxHashMap<WorkProductId, WorkProduct>> {
@@ -531,6 +563,7 @@ impl DepGraph {
                 // so we cannot mark it as green.
                 debug!("try_mark_green({:?}) - END - DepNode does not exist in \
                         current compilation session anymore", dep_node);
+                self.record_recompute_reason(tcx, dep_node, RecomputeReason::New);
                 return None
             }
         };
@@ -557,6 +590,9 @@ impl DepGraph {
                     // with checking any of the other dependencies.
                     debug!("try_mark_green({:?}) - END - dependency {:?} was \
                             immediately red", dep_node, dep_dep_node);
+                    self.record_recompute_reason(tcx,
+                                                 dep_node,
+                                                 RecomputeReason::DependencyChanged(*dep_dep_node));
                     return None
                 }
                 None => {
@@ -581,6 +617,10 @@ impl DepGraph {
                                 if dep_node.extract_def_id(tcx).is_none() {
                                     // If the node does not exist anymore, we
                                     // just fail to mark green.
+                                    self.record_recompute_reason(
+                                        tcx,
+                                        dep_node,
+                                        RecomputeReason::DependencyRemoved(*dep_dep_node));
                                     return None
                                 } else {
                                     // If the node does exist, it should have
@@ -617,6 +657,10 @@ impl DepGraph {
                                         dependency {:?} was red after forcing",
                                        dep_node,
                                        dep_dep_node);
+                                self.record_recompute_reason(
+                                    tcx,
+                                    dep_node,
+                                    RecomputeReason::DependencyChanged(*dep_dep_node));
                                 return None
                             }
                             None => {
@@ -628,6 +672,10 @@ impl DepGraph {
                         // The DepNode could not be forced.
                         debug!("try_mark_green({:?}) - END - dependency {:?} \
                                 could not be forced", dep_node, dep_dep_node);
+                        self.record_recompute_reason(
+                            tcx,
+                            dep_node,
+                            RecomputeReason::DependencyRemoved(*dep_dep_node));
                         return None
                     }
                 }
@@ -799,6 +847,7 @@ pub enum WorkProductFileKind {
     Object,
     Bytecode,
     BytecodeCompressed,
+    DwarfObject,
 }
 
 pub(super) struct CurrentDepGraph {
@@ -420,7 +420,8 @@ for ::middle::const_val::ErrKind<'gcx> {
             MiscCatchAll |
             IndexOpFeatureGated |
             TypeckError |
-            CheckMatchError => {
+            CheckMatchError |
+            AlreadyReported => {
                 // nothing to do
             }
             UnimplementedConstVal(s) => {
@@ -430,6 +431,14 @@ for ::middle::const_val::ErrKind<'gcx> {
                 len.hash_stable(hcx, hasher);
                 index.hash_stable(hcx, hasher);
             }
+            StepLimitReached { step_limit, ref backtrace } => {
+                step_limit.hash_stable(hcx, hasher);
+                backtrace.hash_stable(hcx, hasher);
+            }
+            StackFrameLimitReached { stack_limit, ref backtrace } => {
+                stack_limit.hash_stable(hcx, hasher);
+                backtrace.hash_stable(hcx, hasher);
+            }
             Math(ref const_math_err) => {
                 const_math_err.hash_stable(hcx, hasher);
             }
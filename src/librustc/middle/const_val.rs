@@ -96,6 +96,9 @@ pub enum ErrKind<'tcx> {
     IndexedNonVec,
     IndexNotUsize,
     IndexOutOfBounds { len: u64, index: u64 },
+    /// `backtrace` lists the calls the evaluator was in, innermost first.
+    StepLimitReached { step_limit: u64, backtrace: Vec<(Span, DefId)> },
+    StackFrameLimitReached { stack_limit: usize, backtrace: Vec<(Span, DefId)> },
 
     MiscBinaryOp,
     MiscCatchAll,
@@ -108,6 +111,9 @@ pub enum ErrKind<'tcx> {
 
     TypeckError,
     CheckMatchError,
+    /// An error that has already been reported, e.g. by miri together with
+    /// its evaluation backtrace.
+    AlreadyReported,
 }
 
 impl<'tcx> From<ConstMathErr> for ErrKind<'tcx> {
@@ -159,6 +165,12 @@ impl<'a, 'gcx, 'tcx> ConstEvalErr<'tcx> {
                 simple!("index out of bounds: the len is {} but the index is {}",
                         len, index)
             }
+            StepLimitReached { step_limit, .. } => {
+                simple!("reached the configured maximum execution time of {} steps", step_limit)
+            }
+            StackFrameLimitReached { stack_limit, .. } => {
+                simple!("reached the configured maximum number of stack frames ({})", stack_limit)
+            }
 
             MiscBinaryOp => simple!("bad operands for binary"),
             MiscCatchAll => simple!("unsupported constant expr"),
@@ -170,6 +182,7 @@ impl<'a, 'gcx, 'tcx> ConstEvalErr<'tcx> {
 
             TypeckError => simple!("type-checking failed"),
             CheckMatchError => simple!("match-checking failed"),
+            AlreadyReported => simple!("constant evaluation failed"),
         }
     }
 
@@ -191,8 +204,21 @@ impl<'a, 'gcx, 'tcx> ConstEvalErr<'tcx> {
         diag
     }
 
+    /// Records that this error happened inside a call to `callee` made at
+    /// `call_span`. Only errors about the evaluation limits keep a backtrace.
+    pub fn inside_call(mut self, call_span: Span, callee: DefId) -> Self {
+        match self.kind {
+            ErrKind::StepLimitReached { ref mut backtrace, .. } |
+            ErrKind::StackFrameLimitReached { ref mut backtrace, .. } => {
+                backtrace.push((call_span, callee));
+            }
+            _ => {}
+        }
+        self
+    }
+
     pub fn note(&self,
-        _tcx: TyCtxt<'a, 'gcx, 'tcx>,
+        tcx: TyCtxt<'a, 'gcx, 'tcx>,
         primary_span: Span,
         primary_kind: &str,
         diag: &mut DiagnosticBuilder)
@@ -203,6 +229,21 @@ impl<'a, 'gcx, 'tcx> ConstEvalErr<'tcx> {
             }
         }
 
+        let backtrace = match self.kind {
+            ErrKind::StepLimitReached { ref backtrace, .. } => {
+                diag.help("use `-Z const-eval-step-limit=N` to allow more steps");
+                &backtrace[..]
+            }
+            ErrKind::StackFrameLimitReached { ref backtrace, .. } => {
+                diag.help("use `-Z const-eval-stack-limit=N` to allow deeper recursion");
+                &backtrace[..]
+            }
+            _ => &[][..],
+        };
+        for &(call_span, callee) in backtrace {
+            diag.span_note(call_span, &format!("inside call to `{}`", tcx.item_path_str(callee)));
+        }
+
         if !primary_span.contains(self.span) {
             diag.span_note(primary_span,
                         &format!("for {} here", primary_kind));
@@ -215,7 +256,9 @@ impl<'a, 'gcx, 'tcx> ConstEvalErr<'tcx> {
         primary_kind: &str)
     {
         match self.kind {
-            ErrKind::TypeckError | ErrKind::CheckMatchError => return,
+            ErrKind::TypeckError |
+            ErrKind::CheckMatchError |
+            ErrKind::AlreadyReported => return,
             _ => {}
         }
         self.struct_error(tcx, primary_span, primary_kind).emit();
@@ -48,7 +48,7 @@ enum_from_u32! {
 }
 
 impl LangItem {
-    fn name(self) -> &'static str {
+    pub fn name(self) -> &'static str {
         match self {
             $( $variant => $name, )*
         }
@@ -70,8 +70,12 @@ pub enum EvalErrorKind<'tcx> {
         memory_size: u64,
         memory_usage: u64,
     },
-    ExecutionTimeLimitReached,
-    StackFrameLimitReached,
+    ExecutionTimeLimitReached {
+        step_limit: u64,
+    },
+    StackFrameLimitReached {
+        stack_limit: usize,
+    },
     OutOfTls,
     TlsOutOfBounds,
     AbiViolation(String),
@@ -192,9 +196,9 @@ impl<'tcx> Error for EvalError<'tcx> {
                 "tried to interpret an invalid 32-bit value as a char",
             OutOfMemory{..} =>
                 "could not allocate more memory",
-            ExecutionTimeLimitReached =>
+            ExecutionTimeLimitReached{..} =>
                 "reached the configured maximum execution time",
-            StackFrameLimitReached =>
+            StackFrameLimitReached{..} =>
                 "reached the configured maximum number of stack frames",
             OutOfTls =>
                 "reached the maximum number of representable TLS keys",
@@ -303,6 +307,10 @@ impl<'tcx> fmt::Display for EvalError<'tcx> {
             OutOfMemory { allocation_size, memory_size, memory_usage } =>
                 write!(f, "tried to allocate {} more bytes, but only {} bytes are free of the {} byte memory",
                        allocation_size, memory_size - memory_usage, memory_size),
+            ExecutionTimeLimitReached { step_limit } =>
+                write!(f, "reached the configured maximum execution time of {} steps", step_limit),
+            StackFrameLimitReached { stack_limit } =>
+                write!(f, "reached the configured maximum number of stack frames ({})", stack_limit),
             AlignmentCheckFailed { required, has } =>
                write!(f, "tried to access memory with alignment {}, but alignment {} is required",
                       has, required),
@@ -62,6 +62,12 @@ pub enum Sanitizer {
     Thread,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum IncrementalExplain {
+    Text,
+    Json,
+}
+
 #[derive(Clone, Copy, PartialEq, Hash)]
 pub enum OptLevel {
     No, // -O0
@@ -92,6 +98,37 @@ pub enum Lto {
     Fat,
 }
 
+/// The setting of `-C profile-generate`: whether to instrument the crate for
+/// profile-guided optimization, and where the profiles should be written.
+#[derive(Clone, PartialEq, Hash)]
+pub enum SwitchWithOptPath {
+    Enabled(Option<PathBuf>),
+    Disabled,
+}
+
+impl SwitchWithOptPath {
+    pub fn enabled(&self) -> bool {
+        match *self {
+            SwitchWithOptPath::Enabled(_) => true,
+            SwitchWithOptPath::Disabled => false,
+        }
+    }
+}
+
+/// How to lay out the DWARF debug info on targets supporting split DWARF
+/// (`-Z split-debuginfo`).
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum SplitDebuginfo {
+    /// Keep all debug info in the object files and the linked output.
+    Off,
+    /// Move the debug info of each codegen unit into a `.dwo` file next to
+    /// its object, leaving skeleton compile units in the object files, and
+    /// package the `.dwo` files into a `.dwp` file next to linked outputs.
+    Packed,
+    /// Like `Packed`, but leave the `.dwo` files as they are.
+    Unpacked,
+}
+
 #[derive(Clone, Copy, PartialEq, Hash)]
 pub enum DebugInfoLevel {
     NoDebugInfo,
@@ -452,6 +489,8 @@ pub enum PrintRequest {
     TlsModels,
     TargetSpec,
     NativeStaticLibs,
+    CrateInfo,
+    CrateInfoJson,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -810,11 +849,18 @@ macro_rules! options {
             Some("one of `thin`, `fat`, or omitted");
         pub const parse_epoch: Option<&'static str> =
             Some("one of: `2015`, `2018`");
+        pub const parse_incremental_explain: Option<&'static str> =
+            Some("one of `text`, `json`, or omitted");
+        pub const parse_switch_with_opt_path: Option<&'static str> =
+            Some("an optional path to the profiling data output directory");
+        pub const parse_split_debuginfo: Option<&'static str> =
+            Some("one of: `off`, `packed`, or `unpacked`");
     }
 
     #[allow(dead_code)]
     mod $mod_set {
-        use super::{$struct_name, Passes, SomePasses, AllPasses, Sanitizer, Lto, Epoch};
+        use super::{$struct_name, Passes, SomePasses, AllPasses, Sanitizer, Lto, Epoch,
+                    IncrementalExplain, SwitchWithOptPath, SplitDebuginfo};
         use rustc_back::{LinkerFlavor, PanicStrategy, RelroLevel};
         use std::path::PathBuf;
 
@@ -1027,6 +1073,34 @@ macro_rules! options {
             }
             true
         }
+
+        fn parse_incremental_explain(slot: &mut Option<IncrementalExplain>,
+                                     v: Option<&str>) -> bool {
+            *slot = match v {
+                None | Some("text") => Some(IncrementalExplain::Text),
+                Some("json") => Some(IncrementalExplain::Json),
+                Some(_) => return false,
+            };
+            true
+        }
+
+        fn parse_switch_with_opt_path(slot: &mut SwitchWithOptPath, v: Option<&str>) -> bool {
+            *slot = match v {
+                None => SwitchWithOptPath::Enabled(None),
+                Some(path) => SwitchWithOptPath::Enabled(Some(PathBuf::from(path))),
+            };
+            true
+        }
+
+        fn parse_split_debuginfo(slot: &mut SplitDebuginfo, v: Option<&str>) -> bool {
+            *slot = match v {
+                Some("off") => SplitDebuginfo::Off,
+                Some("packed") => SplitDebuginfo::Packed,
+                Some("unpacked") => SplitDebuginfo::Unpacked,
+                _ => return false,
+            };
+            true
+        }
     }
 ) }
 
@@ -1102,6 +1176,15 @@ options! {CodegenOptions, CodegenSetter, basic_codegen_options,
         [TRACKED], "panic strategy to compile crate with"),
     incremental: Option<String> = (None, parse_opt_string, [UNTRACKED],
           "enable incremental compilation"),
+    profile_generate: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [TRACKED],
+        "instrument the generated code to collect execution profiles, written \
+         to the given directory (or the current one), for profile-guided optimization"),
+    profile_use: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
+        "use the given merged profile (`.profdata`) for profile-guided optimization"),
+    link_order_file: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
+        "place the functions listed in the given file (one symbol name per line) \
+         contiguously and in that order in the output"),
 }
 
 options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
@@ -1154,6 +1237,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "gather metadata statistics"),
     print_link_args: bool = (false, parse_bool, [UNTRACKED],
         "print the arguments passed to the linker"),
+    emit_link_map: bool = (false, parse_bool, [UNTRACKED],
+        "have the linker write a map file, and write a report of the size of each symbol \
+         and crate in the output (`<output>.sizes.json`)"),
     print_llvm_passes: bool = (false, parse_bool, [UNTRACKED],
         "prints the llvm optimization passes being run"),
     ast_json: bool = (false, parse_bool, [UNTRACKED],
@@ -1164,6 +1250,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "print the pre-expansion AST as JSON and halt"),
     ls: bool = (false, parse_bool, [UNTRACKED],
         "list the symbols defined by a library crate"),
+    explain_crate_search: bool = (false, parse_bool, [UNTRACKED],
+        "print every directory and candidate considered when looking for a crate, \
+         and why each candidate was rejected"),
     save_analysis: bool = (false, parse_bool, [UNTRACKED],
         "write syntax and type analysis (in JSON format) information, in \
          addition to normal output"),
@@ -1186,6 +1275,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "treat all errors that occur as bugs"),
     external_macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
           "show macro backtraces even for non-local macros"),
+    macro_backtrace_verbose: bool = (false, parse_bool, [UNTRACKED],
+          "explain how far each arm of a `macro_rules!` macro got when no arm matches, \
+           and show where each traced expansion happened"),
     teach: bool = (false, parse_bool, [TRACKED],
           "show extended diagnostic help"),
     continue_parse_after_error: bool = (false, parse_bool, [TRACKED],
@@ -1196,6 +1288,16 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "enable incremental compilation support for queries (experimental)"),
     incremental_info: bool = (false, parse_bool, [UNTRACKED],
         "print high-level information about incremental reuse (or the lack thereof)"),
+    incremental_max_size: Option<usize> = (None, parse_opt_uint, [UNTRACKED],
+        "evict the least recently used crates' caches from the incremental compilation \
+         directory until it is no larger than this many megabytes"),
+    incremental_max_age: Option<usize> = (None, parse_opt_uint, [UNTRACKED],
+        "evict caches from the incremental compilation directory that have not been used \
+         for this many days"),
+    incremental_explain: Option<IncrementalExplain> = (None, parse_incremental_explain,
+        [UNTRACKED],
+        "explain which changed inputs caused queries and codegen units to be recomputed \
+         (`text` or `json`)"),
     incremental_dump_hash: bool = (false, parse_bool, [UNTRACKED],
         "dump hash information in textual format to stdout"),
     incremental_verify_ich: bool = (false, parse_bool, [UNTRACKED],
@@ -1220,6 +1322,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "force overflow checks on or off"),
     trace_macros: bool = (false, parse_bool, [UNTRACKED],
           "for every macro invocation, print its name and arguments"),
+    split_debuginfo: SplitDebuginfo = (SplitDebuginfo::Off, parse_split_debuginfo, [TRACKED],
+        "write the debug info of each codegen unit to a separate `.dwo` file (split DWARF), \
+         and optionally package them into a `.dwp` file"),
     debug_macros: bool = (false, parse_bool, [TRACKED],
           "emit line numbers debug info inside macros"),
     enable_nonzeroing_move_hints: bool = (false, parse_bool, [TRACKED],
@@ -1259,6 +1364,12 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "encode MIR of all functions into the crate metadata"),
     miri: bool = (false, parse_bool, [TRACKED],
           "check the miri const evaluator against the old ctfe"),
+    const_eval_step_limit: Option<usize> = (None, parse_opt_uint, [TRACKED],
+          "the maximum number of steps (expressions, or MIR statements and terminators) \
+           const evaluation may take for a single constant (default: 1000000)"),
+    const_eval_stack_limit: Option<usize> = (None, parse_opt_uint, [TRACKED],
+          "the maximum number of nested calls const evaluation may make for a single \
+           constant (default: 100)"),
     osx_rpath_install_name: bool = (false, parse_bool, [TRACKED],
           "pass `-install_name @rpath/...` to the macOS linker"),
     sanitizer: Option<Sanitizer> = (None, parse_sanitizer, [TRACKED],
@@ -1293,6 +1404,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "enable ThinLTO when possible"),
     inline_in_all_cgus: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "control whether #[inline] functions are in all cgus"),
+    cross_crate_inline_threshold: Option<usize> = (None, parse_opt_uint, [TRACKED],
+        "make small leaf functions with at most this many MIR statements available for \
+         inlining in other crates, as if they were marked #[inline]"),
     tls_model: Option<String> = (None, parse_opt_string, [TRACKED],
          "choose the TLS model to use (rustc --print tls-models for details)"),
     saturating_float_casts: bool = (false, parse_bool, [TRACKED],
@@ -1548,7 +1662,8 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
                                print on stdout",
                      "[crate-name|file-names|sysroot|cfg|target-list|\
                        target-cpus|target-features|relocation-models|\
-                       code-models|tls-models|target-spec-json|native-static-libs]"),
+                       code-models|tls-models|target-spec-json|native-static-libs|\
+                       crate-info|crate-info-json]"),
         opt::flagmulti_s("g",  "",  "Equivalent to -C debuginfo=2"),
         opt::flagmulti_s("O", "", "Equivalent to -C opt-level=2"),
         opt::opt_s("o", "", "Write output to <filename>", "FILENAME"),
@@ -1773,6 +1888,11 @@ pub fn build_session_options_and_crate_config(matches: &getopts::Matches)
         early_error(error_format, "Value for query threads must be a positive nonzero integer");
     }
 
+    if debugging_opts.const_eval_step_limit == Some(0) ||
+       debugging_opts.const_eval_stack_limit == Some(0) {
+        early_error(error_format, "Value for const eval limits must be a positive nonzero integer");
+    }
+
     if codegen_units == Some(0) {
         early_error(error_format, "Value for codegen units must be a positive nonzero integer");
     }
@@ -1798,6 +1918,15 @@ pub fn build_session_options_and_crate_config(matches: &getopts::Matches)
         early_error(error_format, "can't perform LTO when compiling incrementally");
     }
 
+    if cg.lto != Lto::No && debugging_opts.split_debuginfo != SplitDebuginfo::Off {
+        early_error(error_format, "can't perform LTO with `-Z split-debuginfo`");
+    }
+
+    if cg.profile_generate.enabled() && cg.profile_use.is_some() {
+        early_error(error_format, "options `-C profile-generate` and `-C profile-use` \
+                                   are exclusive");
+    }
+
     let mut prints = Vec::<PrintRequest>::new();
     if cg.target_cpu.as_ref().map_or(false, |s| s == "help") {
         prints.push(PrintRequest::TargetCPUs);
@@ -1929,6 +2058,18 @@ pub fn build_session_options_and_crate_config(matches: &getopts::Matches)
                                           enable the target-spec-json print option"));
                 }
             },
+            "crate-info" | "crate-info-json" => {
+                if !nightly_options::is_unstable_enabled(matches) {
+                    early_error(error_format,
+                                &format!("the `-Z unstable-options` flag must also be passed to \
+                                          enable the {} print option", s));
+                }
+                if s == "crate-info" {
+                    PrintRequest::CrateInfo
+                } else {
+                    PrintRequest::CrateInfoJson
+                }
+            },
             req => {
                 early_error(error_format, &format!("unknown print request `{}`", req))
             }
@@ -2113,7 +2254,8 @@ mod dep_tracking {
     use std::path::PathBuf;
     use std::collections::hash_map::DefaultHasher;
     use super::{Passes, CrateType, OptLevel, DebugInfoLevel, Lto,
-                OutputTypes, Externs, ErrorOutputType, Sanitizer, Epoch};
+                OutputTypes, Externs, ErrorOutputType, Sanitizer, Epoch, SwitchWithOptPath,
+                SplitDebuginfo};
     use syntax::feature_gate::UnstableFeatures;
     use rustc_back::{PanicStrategy, RelroLevel};
 
@@ -2176,6 +2318,8 @@ mod dep_tracking {
     impl_dep_tracking_hash_via_hash!(Sanitizer);
     impl_dep_tracking_hash_via_hash!(Option<Sanitizer>);
     impl_dep_tracking_hash_via_hash!(Epoch);
+    impl_dep_tracking_hash_via_hash!(SwitchWithOptPath);
+    impl_dep_tracking_hash_via_hash!(SplitDebuginfo);
 
     impl_dep_tracking_hash_for_sortable_vec_of!(String);
     impl_dep_tracking_hash_for_sortable_vec_of!(PathBuf);
@@ -2247,7 +2391,7 @@ mod tests {
     use std::collections::{BTreeMap, BTreeSet};
     use std::iter::FromIterator;
     use std::path::PathBuf;
-    use super::{OutputType, OutputTypes, Externs};
+    use super::{OutputType, OutputTypes, Externs, SwitchWithOptPath, SplitDebuginfo};
     use rustc_back::{PanicStrategy, RelroLevel};
     use syntax::symbol::Symbol;
 
@@ -2805,6 +2949,18 @@ mod tests {
         opts = reference.clone();
         opts.cg.panic = Some(PanicStrategy::Abort);
         assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.cg.profile_generate = SwitchWithOptPath::Enabled(None);
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.cg.profile_use = Some(PathBuf::from("abc.profdata"));
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.cg.link_order_file = Some(PathBuf::from("startup.order"));
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
     }
 
     #[test]
@@ -2930,5 +3086,21 @@ mod tests {
         opts = reference.clone();
         opts.debugging_opts.relro_level = Some(RelroLevel::Full);
         assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.const_eval_step_limit = Some(10);
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.const_eval_stack_limit = Some(10);
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.cross_crate_inline_threshold = Some(10);
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.split_debuginfo = SplitDebuginfo::Packed;
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
     }
 }
@@ -0,0 +1,67 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use util::nodemap::FxHashMap;
+
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// The contents of a `-C link-order-file`: the (mangled) names of the
+/// functions that should be laid out contiguously, in that order, in the
+/// linked output.
+///
+/// The file lists one symbol per line. Empty lines and lines starting with
+/// `#` are ignored, as are repeated symbols.
+#[derive(Debug)]
+pub struct LinkOrder {
+    symbols: Vec<String>,
+    positions: FxHashMap<String, usize>,
+}
+
+impl LinkOrder {
+    pub fn load(path: &Path) -> io::Result<LinkOrder> {
+        let mut link_order = LinkOrder {
+            symbols: Vec::new(),
+            positions: FxHashMap(),
+        };
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            let symbol = line.trim();
+            if symbol.is_empty() || symbol.starts_with('#') {
+                continue
+            }
+            if !link_order.positions.contains_key(symbol) {
+                link_order.positions.insert(symbol.to_string(), link_order.symbols.len());
+                link_order.symbols.push(symbol.to_string());
+            }
+        }
+        Ok(link_order)
+    }
+
+    /// The listed symbols, in order.
+    pub fn symbols(&self) -> &[String] {
+        &self.symbols
+    }
+
+    /// The position of `symbol` in the file, if it is listed.
+    pub fn position(&self, symbol: &str) -> Option<usize> {
+        self.positions.get(symbol).cloned()
+    }
+}
+
+// The order only depends on the listed symbols, so this is all incremental
+// compilation has to compare.
+impl Hash for LinkOrder {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.symbols.hash(hasher);
+    }
+}
@@ -10,6 +10,7 @@
 
 pub use self::code_stats::{CodeStats, DataTypeKind, FieldInfo};
 pub use self::code_stats::{SizeKind, TypeSizeInfo, VariantInfo};
+pub use self::link_order::LinkOrder;
 
 use hir::def_id::CrateNum;
 use ich::Fingerprint;
@@ -43,8 +44,10 @@ use jobserver::Client;
 
 use std::cell::{self, Cell, RefCell};
 use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
 use std::env;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
@@ -54,6 +57,7 @@ use std::time::Duration;
 mod code_stats;
 pub mod config;
 pub mod filesearch;
+mod link_order;
 pub mod search_paths;
 
 /// Represents the data associated with a compilation
@@ -123,6 +127,9 @@ pub struct Session {
     /// Data about code being compiled, gathered during compilation.
     pub code_stats: RefCell<CodeStats>,
 
+    /// The symbols listed in the `-C link-order-file`, if any.
+    pub link_order: Option<LinkOrder>,
+
     next_node_id: Cell<ast::NodeId>,
 
     /// If -zfuel=crate=n is specified, Some(crate).
@@ -549,6 +556,12 @@ impl Session {
             return config::Lto::No
         }
 
+        // Split DWARF relies on each object file containing the compile unit
+        // of its own codegen unit only, which cross-module importing breaks.
+        if self.split_dwarf() {
+            return config::Lto::No
+        }
+
         // Now we're in "defaults" territory. By default we enable ThinLTO for
         // optimized compiles (anything greater than O0).
         match self.opts.optimize {
@@ -557,6 +570,13 @@ impl Session {
         }
     }
 
+    /// Whether the debug info of each codegen unit is written to a separate
+    /// `.dwo` file.
+    pub fn split_dwarf(&self) -> bool {
+        self.opts.debugging_opts.split_debuginfo != config::SplitDebuginfo::Off &&
+            self.opts.debuginfo != config::NoDebugInfo
+    }
+
     /// Returns the panic strategy for this compile session. If the user explicitly selected one
     /// using '-C panic', use that, otherwise use the panic strategy defined by the target.
     pub fn panic_strategy(&self) -> PanicStrategy {
@@ -798,6 +818,28 @@ impl Session {
         self.opts.debugging_opts.query_threads.unwrap_or(1)
     }
 
+    /// Returns the maximum number of steps any of the const evaluators may
+    /// take for a single constant
+    pub fn const_eval_step_limit(&self) -> u64 {
+        self.opts.debugging_opts.const_eval_step_limit.unwrap_or(1_000_000) as u64
+    }
+
+    /// Returns the maximum number of nested calls any of the const
+    /// evaluators may make for a single constant
+    pub fn const_eval_stack_limit(&self) -> usize {
+        self.opts.debugging_opts.const_eval_stack_limit.unwrap_or(100)
+    }
+
+    /// Returns the hash of everything besides the source that affects the
+    /// output of this compilation: the tracked command line options and the
+    /// contents of the files they name
+    pub fn dep_tracking_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.opts.dep_tracking_hash().hash(&mut hasher);
+        self.link_order.hash(&mut hasher);
+        hasher.finish()
+    }
+
     /// Returns the number of codegen units that should be used for this
     /// compilation
     pub fn codegen_units(&self) -> usize {
@@ -988,6 +1030,17 @@ pub fn build_session_(sopts: config::Options,
     };
     let working_dir = file_path_mapping.map_prefix(working_dir);
 
+    let link_order = sopts.cg.link_order_file.as_ref().and_then(|path| {
+        match LinkOrder::load(path) {
+            Ok(link_order) => Some(link_order),
+            Err(e) => {
+                p_s.span_diagnostic.err(&format!("failed to read link order file `{}`: {}",
+                                                 path.display(), e));
+                None
+            }
+        }
+    });
+
     let sess = Session {
         target: target_cfg,
         host,
@@ -1028,6 +1081,7 @@ pub fn build_session_(sopts: config::Options,
             decode_def_path_tables_time: Cell::new(Duration::from_secs(0)),
         },
         code_stats: RefCell::new(CodeStats::new()),
+        link_order,
         optimization_fuel_crate,
         optimization_fuel_limit,
         print_fuel_crate,
@@ -1053,6 +1107,25 @@ pub fn build_session_(sopts: config::Options,
         has_global_allocator: Cell::new(false),
     };
 
+    if sess.opts.debugging_opts.split_debuginfo != config::SplitDebuginfo::Off {
+        let options = &sess.target.target.options;
+        if options.is_like_osx || options.is_like_windows || options.is_like_emscripten {
+            sess.err("`-Z split-debuginfo` is only supported for ELF targets");
+        }
+    }
+
+    // LLVM only reads the profile once translation has started, so check
+    // that it exists up front.
+    if let Some(ref path) = sess.opts.cg.profile_use {
+        if !path.exists() {
+            sess.err(&format!("file `{}` passed to `-C profile-use` does not exist",
+                              path.display()));
+        }
+        if sess.opts.optimize == config::OptLevel::No {
+            sess.warn("`-C profile-use` has no effect without optimizations");
+        }
+    }
+
     sess
 }
 
@@ -107,7 +107,8 @@ impl<'tcx> InstanceDef<'tcx> {
             return true
         }
         requests_inline(&self.attrs(tcx)[..]) ||
-            tcx.is_const_fn(self.def_id())
+            tcx.is_const_fn(self.def_id()) ||
+            tcx.cross_crate_inlinable(self.def_id())
     }
 }
 
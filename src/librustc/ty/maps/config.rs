@@ -311,6 +311,12 @@ impl<'tcx> QueryDescription<'tcx> for queries::is_const_fn<'tcx> {
     }
 }
 
+impl<'tcx> QueryDescription<'tcx> for queries::cross_crate_inlinable<'tcx> {
+    fn describe(tcx: TyCtxt, def_id: DefId) -> String {
+        format!("checking if `{}` can be inlined across crates", tcx.item_path_str(def_id))
+    }
+}
+
 impl<'tcx> QueryDescription<'tcx> for queries::dylib_dependency_formats<'tcx> {
     fn describe(_: TyCtxt, _: CrateNum) -> String {
         "dylib dependency formats of crate".to_string()
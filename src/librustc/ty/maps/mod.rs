@@ -114,6 +114,11 @@ define_maps! { <'tcx>
     /// True if this is a const fn
     [] fn is_const_fn: IsConstFn(DefId) -> bool,
 
+    /// True if this function is small enough to be translated locally by
+    /// every crate using it, like an `#[inline]` function, due to
+    /// `-Z cross-crate-inline-threshold`.
+    [] fn cross_crate_inlinable: CrossCrateInlinable(DefId) -> bool,
+
     /// True if this is a foreign item (i.e., linked via `extern { ... }`).
     [] fn is_foreign_item: IsForeignItem(DefId) -> bool,
 
@@ -621,6 +621,12 @@ impl<'a, 'tcx> Lift<'tcx> for const_val::ErrKind<'a> {
             IndexedNonVec => IndexedNonVec,
             IndexNotUsize => IndexNotUsize,
             IndexOutOfBounds { len, index } => IndexOutOfBounds { len, index },
+            StepLimitReached { step_limit, ref backtrace } => {
+                StepLimitReached { step_limit, backtrace: backtrace.clone() }
+            }
+            StackFrameLimitReached { stack_limit, ref backtrace } => {
+                StackFrameLimitReached { stack_limit, backtrace: backtrace.clone() }
+            }
             MiscBinaryOp => MiscBinaryOp,
             MiscCatchAll => MiscCatchAll,
             IndexOpFeatureGated => IndexOpFeatureGated,
@@ -635,6 +641,7 @@ impl<'a, 'tcx> Lift<'tcx> for const_val::ErrKind<'a> {
 
             TypeckError => TypeckError,
             CheckMatchError => CheckMatchError,
+            AlreadyReported => AlreadyReported,
         })
     }
 }
@@ -28,7 +28,9 @@ use syntax::attr;
 use rustc::hir::{self, Expr};
 use syntax_pos::Span;
 
+use std::cell::Cell;
 use std::cmp::Ordering;
+use std::rc::Rc;
 
 use rustc_const_math::*;
 macro_rules! signal {
@@ -64,7 +66,12 @@ pub struct ConstContext<'a, 'tcx: 'a> {
     tables: &'a ty::TypeckTables<'tcx>,
     param_env: ty::ParamEnv<'tcx>,
     substs: &'tcx Substs<'tcx>,
-    fn_args: Option<NodeMap<&'tcx ty::Const<'tcx>>>
+    fn_args: Option<NodeMap<&'tcx ty::Const<'tcx>>>,
+    /// The number of `const fn` calls this context is nested in.
+    depth: usize,
+    /// The number of expressions evaluated so far, shared with the contexts
+    /// of the `const fn`s this one calls.
+    steps: Rc<Cell<u64>>,
 }
 
 impl<'a, 'tcx> ConstContext<'a, 'tcx> {
@@ -77,7 +84,9 @@ impl<'a, 'tcx> ConstContext<'a, 'tcx> {
             param_env: param_env_and_substs.param_env,
             tables,
             substs: param_env_and_substs.value,
-            fn_args: None
+            fn_args: None,
+            depth: 0,
+            steps: Rc::new(Cell::new(0)),
         }
     }
 
@@ -97,6 +106,13 @@ fn eval_const_expr_partial<'a, 'tcx>(cx: &ConstContext<'a, 'tcx>,
                                      e: &'tcx Expr) -> EvalResult<'tcx> {
     trace!("eval_const_expr_partial: {:?}", e);
     let tcx = cx.tcx;
+
+    let step_limit = tcx.sess.const_eval_step_limit();
+    cx.steps.set(cx.steps.get() + 1);
+    if cx.steps.get() > step_limit {
+        signal!(e, StepLimitReached { step_limit, backtrace: vec![] });
+    }
+
     let ty = cx.tables.expr_ty(e).subst(tcx, cx.substs);
     let mk_const = |val| tcx.mk_const(ty::Const { val, ty });
 
@@ -272,6 +288,9 @@ fn eval_const_expr_partial<'a, 'tcx>(cx: &ConstContext<'a, 'tcx>,
                         Err(ConstEvalErr { kind: TypeckError, .. }) => {
                             signal!(e, TypeckError);
                         }
+                        Err(ConstEvalErr { kind: AlreadyReported, .. }) => {
+                            signal!(e, AlreadyReported);
+                        }
                         Err(err) => {
                             debug!("bad reference: {:?}, {:?}", err.description(), err.span);
                             signal!(e, ErroneousReferencedConstant(box err))
@@ -368,20 +387,38 @@ fn eval_const_expr_partial<'a, 'tcx>(cx: &ConstContext<'a, 'tcx>,
               }
           }
           debug!("const call({:?})", call_args);
+          let stack_limit = tcx.sess.const_eval_stack_limit();
+          if cx.depth >= stack_limit {
+              signal!(e, StackFrameLimitReached { stack_limit, backtrace: vec![] });
+          }
           let callee_cx = ConstContext {
             tcx,
             param_env: cx.param_env,
             tables: tcx.typeck_tables_of(def_id),
             substs,
-            fn_args: Some(call_args)
+            fn_args: Some(call_args),
+            depth: cx.depth + 1,
+            steps: cx.steps.clone(),
           };
-          callee_cx.eval(&body.value)?
+          callee_cx.eval(&body.value).map_err(|err| err.inside_call(e.span, def_id))?
       },
       hir::ExprLit(ref lit) => match lit_to_const(&lit.node, tcx, ty) {
           Ok(val) => mk_const(val),
           Err(err) => signal!(e, err),
       },
       hir::ExprBlock(ref block) => {
+        // Statements other than items (only allowed in `const fn` with
+        // `#![feature(const_control_flow)]`) are left to miri.
+        let has_statements = block.stmts.iter().any(|stmt| match stmt.node {
+            hir::StmtDecl(ref decl, _) => match decl.node {
+                hir::DeclItem(_) => false,
+                hir::DeclLocal(_) => true,
+            },
+            hir::StmtExpr(..) | hir::StmtSemi(..) => true,
+        });
+        if has_statements {
+            signal!(e, UnimplementedConstVal("statements"));
+        }
         match block.expr {
             Some(ref expr) => cx.eval(expr)?,
             None => mk_const(Aggregate(Tuple(&[]))),
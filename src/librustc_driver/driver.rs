@@ -783,6 +783,7 @@ pub fn phase_2_configure_and_expand_inner<'a, F>(sess: &'a Session,
             features: Some(&features),
             recursion_limit: sess.recursion_limit.get(),
             trace_mac: sess.opts.debugging_opts.trace_macros,
+            explain_mac: sess.opts.debugging_opts.macro_backtrace_verbose,
             should_test: sess.opts.test,
             ..syntax::ext::expand::ExpansionConfig::default(crate_name.to_string())
         };
@@ -1073,6 +1074,10 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(trans: &TransCrate,
              || for def_id in tcx.body_owners() {
                  mir::transform::check_unsafety::check_unsafety(tcx, def_id)
              });
+
+        time(time_passes,
+             "static assertion checking",
+             || mir::interpret::check_static_asserts(tcx));
         // Avoid overwhelming user with errors if type checking failed.
         // I'm not sure how helpful this is, to be honest, but it avoids
         // a
@@ -957,6 +957,28 @@ impl RustcDefaultCalls {
             return Compilation::Continue;
         }
 
+        // The input of `--print crate-info` is a compiled crate, not a source file.
+        if let Some(&req) = sess.opts.prints.iter().find(|&&p| p == CrateInfo ||
+                                                               p == CrateInfoJson) {
+            match input {
+                Some(&Input::File(ref path)) => {
+                    let mut v = Vec::new();
+                    locator::print_file_crate_info(&sess.target.target,
+                                                   path,
+                                                   &*trans.metadata_loader(),
+                                                   req == CrateInfoJson,
+                                                   &mut v)
+                            .unwrap();
+                    print!("{}", String::from_utf8(v).unwrap());
+                }
+                _ => {
+                    early_error(ErrorOutputType::default(),
+                                "--print crate-info requires the path of a compiled crate");
+                }
+            }
+            return Compilation::Stop;
+        }
+
         let attrs = match input {
             None => None,
             Some(input) => {
@@ -1050,6 +1072,8 @@ impl RustcDefaultCalls {
                 }
                 // Any output here interferes with Cargo's parsing of other printed output
                 PrintRequest::NativeStaticLibs => {}
+                // Handled above
+                CrateInfo | CrateInfoJson => {}
             }
         }
         return Compilation::Stop;
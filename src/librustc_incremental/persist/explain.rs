@@ -0,0 +1,121 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Implementation of `-Z incremental-explain`: for every query and codegen
+//! unit from the previous compilation session that could not be re-used,
+//! print the chain of changed dependencies leading from it to the input
+//! (usually the hash of some HIR item) that caused it to be recomputed.
+
+use rustc::dep_graph::{DepKind, DepNode, RecomputeReason};
+use rustc::session::config::IncrementalExplain;
+use rustc::ty::TyCtxt;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_serialize::json;
+
+#[derive(RustcEncodable)]
+struct Explanation {
+    node: String,
+    kind: String,
+    /// Whether the recomputed value turned out to be the same as before.
+    unchanged: bool,
+    causes: Vec<Cause>,
+}
+
+#[derive(RustcEncodable)]
+struct Cause {
+    node: String,
+    kind: String,
+    /// One of `changed`, `removed` or `new`.
+    change: &'static str,
+}
+
+pub fn explain_recomputations<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
+    let format = match tcx.sess.opts.debugging_opts.incremental_explain {
+        Some(format) => format,
+        None => return,
+    };
+
+    let mut explanations = {
+        let reasons = tcx.dep_graph.recompute_reasons();
+
+        reasons.iter().map(|(dep_node, &reason)| {
+            let mut causes = vec![];
+            let mut visited = FxHashSet();
+            let mut reason = Some(reason);
+            let mut current = *dep_node;
+
+            while let Some(r) = reason {
+                let (dep_dep_node, change) = match r {
+                    RecomputeReason::New => {
+                        causes.push(cause(&current, "new"));
+                        break
+                    }
+                    RecomputeReason::DependencyRemoved(dep_dep_node) => {
+                        (dep_dep_node, "removed")
+                    }
+                    RecomputeReason::DependencyChanged(dep_dep_node) => {
+                        (dep_dep_node, "changed")
+                    }
+                };
+                causes.push(cause(&dep_dep_node, change));
+
+                // Anonymous nodes can in principle reach themselves again.
+                if !visited.insert(dep_dep_node) {
+                    break
+                }
+                current = dep_dep_node;
+                reason = reasons.get(&current).cloned();
+            }
+
+            (dep_node.kind == DepKind::CompileCodegenUnit, Explanation {
+                node: format!("{:?}", dep_node),
+                kind: format!("{:?}", dep_node.kind),
+                unchanged: tcx.dep_graph.node_color(dep_node)
+                                        .map_or(false, |color| color.is_green()),
+                causes,
+            })
+        }).collect::<Vec<_>>()
+    };
+
+    // Codegen units first, as those are usually what one is interested in.
+    explanations.sort_by(|&(a_is_cgu, ref a), &(b_is_cgu, ref b)| {
+        b_is_cgu.cmp(&a_is_cgu).then_with(|| a.node.cmp(&b.node))
+    });
+    let explanations = explanations.into_iter()
+                                   .map(|(_, explanation)| explanation)
+                                   .collect::<Vec<_>>();
+
+    match format {
+        IncrementalExplain::Json => {
+            println!("{}", json::as_json(&explanations));
+        }
+        IncrementalExplain::Text => {
+            for explanation in &explanations {
+                println!("[incremental] recomputed {}{}",
+                         explanation.node,
+                         if explanation.unchanged { " (result unchanged)" } else { "" });
+                for cause in &explanation.causes {
+                    println!("[incremental]     because {} {}", cause.node, match cause.change {
+                        "new" => "did not exist in the previous session",
+                        change => change,
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn cause(dep_node: &DepNode, change: &'static str) -> Cause {
+    Cause {
+        node: format!("{:?}", dep_node),
+        kind: format!("{:?}", dep_node.kind),
+        change,
+    }
+}
@@ -61,6 +61,14 @@
 //! any more and will delete those. It will also delete any finalized session
 //! directories for a given crate except for the most recent one.
 //!
+//! Optionally, the compiler can also bound the total size and age of the
+//! incremental compilation directory (`-Z incremental-max-size` and
+//! `-Z incremental-max-age`). These limits apply to all crates sharing the
+//! directory: finalized session directories that are too old, and then the
+//! least recently used ones (by the timestamp in their name, i.e. the time
+//! that crate was last compiled), are deleted until the directory is within
+//! bounds. Session directories that are currently in use are never evicted.
+//!
 //! ## Synchronization
 //!
 //! There is some synchronization needed in order for the compiler to be able to
@@ -860,9 +868,184 @@ pub fn garbage_collect_session_directories(sess: &Session) -> io::Result<()> {
         mem::drop(lock);
     }
 
+    if sess.opts.debugging_opts.incremental_max_size.is_some() ||
+       sess.opts.debugging_opts.incremental_max_age.is_some() {
+        try!(enforce_incr_comp_dir_limits(sess, crate_directory));
+    }
+
+    Ok(())
+}
+
+/// Deletes finalized session directories of any crate in the incremental
+/// compilation directory until it satisfies `-Z incremental-max-age` and
+/// `-Z incremental-max-size`. The crate currently being compiled is left
+/// alone.
+fn enforce_incr_comp_dir_limits(sess: &Session, current_crate_dir: &Path) -> io::Result<()> {
+    let incr_dir = current_crate_dir.parent().unwrap();
+    debug!("enforce_incr_comp_dir_limits() - incremental directory: {}",
+           incr_dir.display());
+
+    let max_size = sess.opts.debugging_opts.incremental_max_size
+                            .map(|megabytes| megabytes as u64 * 1024 * 1024);
+    let max_age = sess.opts.debugging_opts.incremental_max_age
+                           .map(|days| Duration::from_secs(days as u64 * 24 * 60 * 60));
+
+    let mut total_size = 0;
+    let mut candidates = vec![];
+
+    for crate_dir in try!(incr_dir.read_dir()) {
+        let crate_dir = match crate_dir {
+            Ok(crate_dir) => crate_dir.path(),
+            _ => continue,
+        };
+        if !crate_dir.is_dir() {
+            continue
+        }
+
+        for dir_entry in try!(crate_dir.read_dir()) {
+            let session_dir = match dir_entry {
+                Ok(dir_entry) => dir_entry.path(),
+                _ => continue,
+            };
+            let size = dir_size(&session_dir);
+            total_size += size;
+
+            let directory_name = session_dir.file_name().unwrap().to_string_lossy().into_owned();
+            if crate_dir == current_crate_dir ||
+               !is_session_directory(&directory_name) ||
+               !is_finalized(&directory_name) {
+                continue
+            }
+
+            let timestamp = match extract_timestamp_from_session_dir(&directory_name) {
+                Ok(timestamp) => timestamp,
+                Err(()) => continue,
+            };
+
+            candidates.push((timestamp, size, session_dir));
+        }
+    }
+
+    let evictions = select_for_eviction(candidates,
+                                        total_size,
+                                        max_size,
+                                        max_age,
+                                        SystemTime::now());
+
+    let mut evicted_size = 0;
+    for (timestamp, size, path) in evictions {
+        if let Some(max_size) = max_size {
+            // Earlier evictions may have been skipped because the session was
+            // in use, so recheck whether we are within the limit already
+            // unless this session is old enough to be evicted anyway.
+            let too_old = max_age.map_or(false, |max_age| {
+                SystemTime::now().duration_since(timestamp).map_or(false, |age| age > max_age)
+            });
+            if !too_old && total_size - evicted_size <= max_size {
+                break
+            }
+        }
+
+        // Don't evict sessions that are being read from right now.
+        let lock = match flock::Lock::new(&lock_file_path(&path),
+                                          false,  // don't wait
+                                          false,  // don't create the lock-file
+                                          true) { // get an exclusive lock
+            Ok(lock) => lock,
+            Err(_) => {
+                debug!("enforce_incr_comp_dir_limits() - not evicting `{}`, still in use",
+                       path.display());
+                continue
+            }
+        };
+
+        if let Err(err) = safe_remove_dir_all(&path) {
+            sess.warn(&format!("Failed to evict incremental compilation session \
+                                directory `{}`: {}",
+                               path.display(),
+                               err));
+        } else {
+            delete_session_dir_lock_file(sess, &lock_file_path(&path));
+            evicted_size += size;
+
+            if sess.opts.debugging_opts.incremental_info {
+                let age = SystemTime::now().duration_since(timestamp)
+                                           .map(|age| age.as_secs() / (60 * 60))
+                                           .unwrap_or(0);
+                println!("[incremental] evicted session directory `{}` \
+                          ({} KiB, last used {} hours ago)",
+                         path.display(),
+                         size / 1024,
+                         age);
+            }
+        }
+
+        mem::drop(lock);
+    }
+
+    if sess.opts.debugging_opts.incremental_info && evicted_size > 0 {
+        println!("[incremental] evicted {} KiB, incremental directory is now {} KiB",
+                 evicted_size / 1024,
+                 (total_size - evicted_size) / 1024);
+    }
+
     Ok(())
 }
 
+/// Given the finalized session directories that may be evicted and the total
+/// size of the incremental compilation directory, returns the directories to
+/// evict: first all of those older than `max_age`, then the least recently
+/// used ones until the total size is at most `max_size`. The result is sorted
+/// from least to most recently used.
+fn select_for_eviction<T>(mut candidates: Vec<(SystemTime, u64, T)>,
+                          total_size: u64,
+                          max_size: Option<u64>,
+                          max_age: Option<Duration>,
+                          now: SystemTime)
+                          -> Vec<(SystemTime, u64, T)> {
+    candidates.sort_by_key(|&(timestamp, ..)| timestamp);
+
+    let mut remaining_size = total_size;
+    let mut evictions = vec![];
+    for (timestamp, size, data) in candidates {
+        let too_old = max_age.map_or(false, |max_age| {
+            now.duration_since(timestamp).map_or(false, |age| age > max_age)
+        });
+        let too_big = max_size.map_or(false, |max_size| remaining_size > max_size);
+
+        if !too_old && !too_big {
+            break
+        }
+
+        remaining_size = remaining_size.saturating_sub(size);
+        evictions.push((timestamp, size, data));
+    }
+
+    evictions
+}
+
+/// The total size of the files in a directory, ignoring anything that can't
+/// be read.
+fn dir_size(path: &Path) -> u64 {
+    let metadata = match std_fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return 0,
+    };
+
+    if !metadata.is_dir() {
+        return metadata.len()
+    }
+
+    match path.read_dir() {
+        Ok(entries) => {
+            entries.filter_map(|e| e.ok())
+                   .map(|e| dir_size(&e.path()))
+                   .sum()
+        }
+        Err(_) => 0,
+    }
+}
+
 fn all_except_most_recent(deletion_candidates: Vec<(SystemTime, PathBuf, Option<flock::Lock>)>)
                           -> FxHashMap<PathBuf, Option<flock::Lock>> {
     let most_recent = deletion_candidates.iter()
@@ -928,6 +1111,42 @@ fn test_all_except_most_recent() {
     );
 }
 
+#[test]
+fn test_select_for_eviction() {
+    let now = UNIX_EPOCH + Duration::new(100, 0);
+    let candidates = || vec![
+        (UNIX_EPOCH + Duration::new(40, 0), 10, "40"),
+        (UNIX_EPOCH + Duration::new(10, 0), 20, "10"),
+        (UNIX_EPOCH + Duration::new(90, 0), 30, "90"),
+        (UNIX_EPOCH + Duration::new(20, 0), 40, "20"),
+    ];
+    let names = |evictions: Vec<(SystemTime, u64, &'static str)>| {
+        evictions.into_iter().map(|(.., name)| name).collect::<Vec<_>>()
+    };
+
+    // No limits
+    assert_eq!(names(select_for_eviction(candidates(), 100, None, None, now)),
+               Vec::<&str>::new());
+
+    // Within size limit
+    assert_eq!(names(select_for_eviction(candidates(), 100, Some(100), None, now)),
+               Vec::<&str>::new());
+
+    // Least recently used first, until within size limit
+    assert_eq!(names(select_for_eviction(candidates(), 100, Some(50), None, now)),
+               vec!["10", "20"]);
+
+    // Everything older than the maximum age
+    assert_eq!(names(select_for_eviction(candidates(), 100, None,
+                                         Some(Duration::from_secs(50)), now)),
+               vec!["10", "20", "40"]);
+
+    // Both
+    assert_eq!(names(select_for_eviction(candidates(), 100, Some(10),
+                                         Some(Duration::from_secs(85)), now)),
+               vec!["10", "20", "40", "90"]);
+}
+
 #[test]
 fn test_timestamp_serialization() {
     for i in 0 .. 1_000u64 {
@@ -164,7 +164,7 @@ pub fn load_dep_graph(sess: &Session, time_passes: bool) ->
     // Fortunately, we just checked that this isn't the case.
     let path = dep_graph_path_from(&sess.incr_comp_session_dir());
     let report_incremental_info = sess.opts.debugging_opts.incremental_info;
-    let expected_hash = sess.opts.dep_tracking_hash();
+    let expected_hash = sess.dep_tracking_hash();
 
     MaybeAsync::Async(std::thread::spawn(move || {
         time(time_passes, "background load prev dep-graph", move || {
@@ -14,6 +14,7 @@
 
 mod data;
 mod dirty_clean;
+mod explain;
 mod fs;
 mod load;
 mod save;
@@ -22,6 +22,7 @@ use std::path::PathBuf;
 use super::data::*;
 use super::fs::*;
 use super::dirty_clean;
+use super::explain;
 use super::file_format;
 use super::work_product;
 
@@ -47,6 +48,8 @@ pub fn save_dep_graph<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
             });
         }
 
+        explain::explain_recomputations(tcx);
+
         dirty_clean::check_dirty_clean_annotations(tcx);
     })
 }
@@ -142,7 +145,7 @@ fn encode_dep_graph(tcx: TyCtxt,
                     encoder: &mut Encoder)
                     -> io::Result<()> {
     // First encode the commandline arguments hash
-    tcx.sess.opts.dep_tracking_hash().encode(encoder)?;
+    tcx.sess.dep_tracking_hash().encode(encoder)?;
 
     // Encode the graph data.
     let serialized_graph = tcx.dep_graph.serialize();
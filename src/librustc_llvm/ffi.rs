@@ -322,6 +322,7 @@ pub enum DiagnosticKind {
     OptimizationRemarkAnalysisAliasing,
     OptimizationRemarkOther,
     OptimizationFailure,
+    PGOProfile,
 }
 
 /// LLVMRustArchiveKind
@@ -450,6 +451,10 @@ pub type LinkerRef = *mut Linker_opaque;
 
 pub type DiagnosticHandler = unsafe extern "C" fn(DiagnosticInfoRef, *mut c_void);
 pub type InlineAsmDiagHandler = unsafe extern "C" fn(SMDiagnosticRef, *const c_void, c_uint);
+pub type SymbolSizeCallback = unsafe extern "C" fn(*mut c_void,
+                                                   *const c_char, size_t,
+                                                   *const c_char, size_t,
+                                                   u64);
 
 
 pub mod debuginfo {
@@ -1581,6 +1586,7 @@ extern "C" {
                                        Singlethread: bool)
                                        -> TargetMachineRef;
     pub fn LLVMRustDisposeTargetMachine(T: TargetMachineRef);
+    pub fn LLVMRustSetSplitDwarfFile(T: TargetMachineRef, SplitDwarfFile: *const c_char);
     pub fn LLVMRustAddAnalysisPasses(T: TargetMachineRef, PM: PassManagerRef, M: ModuleRef);
     pub fn LLVMRustAddBuilderLibraryInfo(PMB: PassManagerBuilderRef,
                                          M: ModuleRef,
@@ -1589,7 +1595,9 @@ extern "C" {
                                                OptLevel: CodeGenOptLevel,
                                                MergeFunctions: bool,
                                                SLPVectorize: bool,
-                                               LoopVectorize: bool);
+                                               LoopVectorize: bool,
+                                               PGOGenPath: *const c_char,
+                                               PGOUsePath: *const c_char);
     pub fn LLVMRustAddLibraryInfo(PM: PassManagerRef,
                                   M: ModuleRef,
                                   DisableSimplifyLibCalls: bool);
@@ -1624,6 +1632,11 @@ extern "C" {
     pub fn LLVMRustDestroyArchive(AR: ArchiveRef);
 
     pub fn LLVMRustGetSectionName(SI: SectionIteratorRef, data: *mut *const c_char) -> size_t;
+    pub fn LLVMRustForEachSymbolSize(Data: *const c_char,
+                                     Len: size_t,
+                                     Callback: SymbolSizeCallback,
+                                     Ctx: *mut c_void)
+                                     -> bool;
 
     pub fn LLVMRustWriteTwineToString(T: TwineRef, s: RustStringRef);
 
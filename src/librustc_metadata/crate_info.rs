@@ -0,0 +1,227 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A summary of the `CrateRoot` of a compiled crate, as printed by
+//! `rustc --print crate-info path/to/libfoo.rlib`.
+//!
+//! The metadata is read without loading the crate into a compilation
+//! session, so everything here must be decodable from the raw metadata
+//! blob: crate numbers are the ones local to the inspected crate and spans
+//! are not available.
+
+use cstore::{MetadataBlob, NativeLibrary};
+use decoder::Metadata;
+use schema::{CrateRoot, EntryKind, LazySeq};
+
+use rustc::hir::def_id::{DefId, DefIndex, CRATE_DEF_INDEX, LOCAL_CRATE};
+use rustc::hir::map::definitions::DefPathTable;
+use rustc::hir::map::DefPath;
+use rustc::middle::cstore::NativeLibraryKind;
+use rustc::middle::lang_items::LangItem;
+use rustc::ty;
+use rustc_serialize::json;
+
+use std::io;
+
+#[derive(RustcEncodable)]
+pub struct CrateInfo {
+    pub name: String,
+    pub rustc_version: String,
+    pub triple: String,
+    pub hash: String,
+    pub disambiguator: String,
+    pub panic_strategy: String,
+    pub has_global_allocator: bool,
+    pub has_default_lib_allocator: bool,
+    pub is_plugin: bool,
+    pub is_proc_macro: bool,
+    pub dependencies: Vec<Dependency>,
+    pub native_libraries: Vec<NativeLib>,
+    pub lang_items: Vec<(String, String)>,
+    pub missing_lang_items: Vec<String>,
+    pub exported_symbols: Vec<String>,
+    pub public_items: Vec<(String, String)>,
+}
+
+#[derive(RustcEncodable)]
+pub struct Dependency {
+    pub name: String,
+    pub hash: String,
+    pub kind: String,
+}
+
+#[derive(RustcEncodable)]
+pub struct NativeLib {
+    pub name: String,
+    pub kind: String,
+    pub cfg: Option<String>,
+}
+
+impl CrateInfo {
+    pub fn from_metadata(blob: &MetadataBlob) -> CrateInfo {
+        let root = blob.get_root();
+        let def_path_table = root.def_path_table.decode(blob);
+        let name = root.name.as_str();
+        let path = |index| item_path(&name, &def_path_table, index);
+
+        let dependencies = root.crate_deps.decode(blob).map(|dep| {
+            Dependency {
+                name: dep.name.to_string(),
+                hash: dep.hash.to_string(),
+                kind: format!("{:?}", dep.kind),
+            }
+        }).collect();
+
+        let native_libraries = root.native_libraries.decode(blob).map(|lib: NativeLibrary| {
+            NativeLib {
+                name: lib.name.to_string(),
+                kind: match lib.kind {
+                    NativeLibraryKind::NativeStatic => "static",
+                    NativeLibraryKind::NativeStaticNobundle => "static-nobundle",
+                    NativeLibraryKind::NativeFramework => "framework",
+                    NativeLibraryKind::NativeUnknown => "dylib",
+                }.to_string(),
+                cfg: lib.cfg.map(|cfg| ::syntax::print::pprust::meta_item_to_string(&cfg)),
+            }
+        }).collect();
+
+        let lang_items = root.lang_items.decode(blob).map(|(index, item)| {
+            let item = LangItem::from_u32(item as u32)
+                .map_or_else(|| format!("#{}", item), |item| item.name().to_string());
+            (item, path(index))
+        }).collect();
+
+        let mut exported_symbols = root.exported_symbols.decode(blob)
+                                                        .map(&path)
+                                                        .collect::<Vec<_>>();
+        exported_symbols.sort();
+
+        let mut public_items = vec![];
+        public_items_in(blob, &root, &path, CRATE_DEF_INDEX, &mut public_items);
+        public_items.sort_by(|a, b| a.1.cmp(&b.1));
+
+        CrateInfo {
+            rustc_version: blob.get_rustc_version(),
+            triple: root.triple.clone(),
+            hash: root.hash.to_string(),
+            disambiguator: root.disambiguator.to_fingerprint().to_hex(),
+            panic_strategy: root.panic_strategy.desc().to_string(),
+            has_global_allocator: root.has_global_allocator,
+            has_default_lib_allocator: root.has_default_lib_allocator,
+            is_plugin: root.plugin_registrar_fn.is_some(),
+            is_proc_macro: root.macro_derive_registrar.is_some(),
+            dependencies,
+            native_libraries,
+            lang_items,
+            missing_lang_items: root.lang_items_missing.decode(blob)
+                                                       .map(|item| item.name().to_string())
+                                                       .collect(),
+            exported_symbols,
+            public_items,
+            name: root.name.to_string(),
+        }
+    }
+
+    pub fn write_json(&self, out: &mut io::Write) -> io::Result<()> {
+        write!(out, "{}\n", json::as_pretty_json(self))
+    }
+
+    pub fn write_text(&self, out: &mut io::Write) -> io::Result<()> {
+        write!(out, "name: {}\n", self.name)?;
+        write!(out, "rustc version: {}\n", self.rustc_version)?;
+        write!(out, "target: {}\n", self.triple)?;
+        write!(out, "hash (svh): {}\n", self.hash)?;
+        write!(out, "disambiguator: {}\n", self.disambiguator)?;
+        write!(out, "panic strategy: {}\n", self.panic_strategy)?;
+        write!(out, "global allocator: {}\n", self.has_global_allocator)?;
+        write!(out, "default lib allocator: {}\n", self.has_default_lib_allocator)?;
+        write!(out, "plugin: {}\n", self.is_plugin)?;
+        write!(out, "proc macro: {}\n", self.is_proc_macro)?;
+
+        write!(out, "\n=External Dependencies=\n")?;
+        for (i, dep) in self.dependencies.iter().enumerate() {
+            write!(out, "{} {}-{} ({})\n", i + 1, dep.name, dep.hash, dep.kind)?;
+        }
+
+        write!(out, "\n=Native Libraries=\n")?;
+        for lib in &self.native_libraries {
+            write!(out, "{} ({})", lib.name, lib.kind)?;
+            if let Some(ref cfg) = lib.cfg {
+                write!(out, " if cfg({})", cfg)?;
+            }
+            write!(out, "\n")?;
+        }
+
+        write!(out, "\n=Lang Items=\n")?;
+        for &(ref item, ref path) in &self.lang_items {
+            write!(out, "{}: {}\n", item, path)?;
+        }
+        for item in &self.missing_lang_items {
+            write!(out, "{}: (missing)\n", item)?;
+        }
+
+        write!(out, "\n=Exported Symbols=\n")?;
+        for symbol in &self.exported_symbols {
+            write!(out, "{}\n", symbol)?;
+        }
+
+        write!(out, "\n=Public Items=\n")?;
+        for &(ref kind, ref path) in &self.public_items {
+            write!(out, "{} {}\n", kind, path)?;
+        }
+        Ok(())
+    }
+}
+
+fn item_path(crate_name: &str, def_path_table: &DefPathTable, index: DefIndex) -> String {
+    let def_path = DefPath::make(LOCAL_CRATE, index, |parent| def_path_table.def_key(parent));
+    let mut path = crate_name.to_string();
+    for component in &def_path.data {
+        path.push_str("::");
+        path.push_str(&component.data.as_interned_str());
+    }
+    path
+}
+
+/// Collects the items reachable through public modules, starting at `module`.
+/// Re-exports are not included, as their targets can't be resolved without
+/// loading the crate's dependencies.
+fn public_items_in<F>(blob: &MetadataBlob,
+                      root: &CrateRoot,
+                      path: &F,
+                      module: DefIndex,
+                      items: &mut Vec<(String, String)>)
+    where F: Fn(DefIndex) -> String
+{
+    let children: LazySeq<DefIndex> = match root.index.lookup(blob.raw_bytes(), module) {
+        Some(entry) => entry.decode(blob).children,
+        None => return,
+    };
+
+    for child in children.decode(blob) {
+        let entry = match root.index.lookup(blob.raw_bytes(), child) {
+            Some(entry) => entry.decode(blob),
+            None => continue,
+        };
+        if entry.visibility.decode(blob) != ty::Visibility::Public {
+            continue
+        }
+
+        let def = match entry.kind.to_def(DefId { krate: LOCAL_CRATE, index: child }) {
+            Some(def) => def,
+            None => continue,
+        };
+        items.push((def.kind_name().to_string(), path(child)));
+
+        if let EntryKind::Mod(_) = entry.kind {
+            public_items_in(blob, root, path, child, items);
+        }
+    }
+}
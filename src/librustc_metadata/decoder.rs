@@ -221,7 +221,11 @@ impl<'a, 'tcx: 'a> TyDecoder<'a, 'tcx> for DecodeContext<'a, 'tcx> {
     }
 
     fn map_encoded_cnum_to_current(&self, cnum: CrateNum) -> CrateNum {
-        if cnum == LOCAL_CRATE {
+        if self.cdata.is_none() {
+            // We are looking at a metadata blob on its own (see `crate_info`),
+            // so crate numbers stay relative to the crate being decoded.
+            cnum
+        } else if cnum == LOCAL_CRATE {
             self.cdata().cnum
         } else {
             self.cdata().cnum_map.borrow()[cnum]
@@ -282,6 +286,11 @@ impl<'a, 'tcx> SpecializedDecoder<Span> for DecodeContext<'a, 'tcx> {
         let len = BytePos::decode(self)?;
         let hi = lo + len;
 
+        if self.cdata.is_none() {
+            // Without a crate, there is no codemap to map the span into.
+            return Ok(DUMMY_SP)
+        }
+
         let sess = if let Some(sess) = self.sess {
             sess
         } else {
@@ -379,7 +388,7 @@ impl<'a, 'tcx> MetadataBlob {
 }
 
 impl<'tcx> EntryKind<'tcx> {
-    fn to_def(&self, did: DefId) -> Option<Def> {
+    pub fn to_def(&self, did: DefId) -> Option<Def> {
         Some(match *self {
             EntryKind::Const(_) => Def::Const(did),
             EntryKind::AssociatedConst(..) => Def::AssociatedConst(did),
@@ -1030,6 +1039,14 @@ impl<'a, 'tcx> CrateMetadata {
         constness == hir::Constness::Const
     }
 
+    pub fn cross_crate_inlinable(&self, id: DefIndex) -> bool {
+        match self.entry(id).kind {
+            EntryKind::Method(data) => data.decode(self).fn_data.cross_crate_inlinable,
+            EntryKind::Fn(data) => data.decode(self).cross_crate_inlinable,
+            _ => false,
+        }
+    }
+
     pub fn is_foreign_item(&self, id: DefIndex) -> bool {
         match self.entry(id).kind {
             EntryKind::ForeignImmStatic |
@@ -733,6 +733,7 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
                         constness: hir::Constness::NotConst,
                         arg_names,
                         sig: self.lazy(&tcx.fn_sig(def_id)),
+                        cross_crate_inlinable: false,
                     }
                 } else {
                     bug!()
@@ -812,6 +813,7 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
                         constness: sig.constness,
                         arg_names: self.encode_fn_arg_names_for_body(body),
                         sig: self.lazy(&tcx.fn_sig(def_id)),
+                        cross_crate_inlinable: tcx.cross_crate_inlinable(def_id),
                     }
                 } else {
                     bug!()
@@ -830,7 +832,8 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
         } else if let hir::ImplItemKind::Method(ref sig, body) = ast_item.node {
             let generics = self.tcx.generics_of(def_id);
             let types = generics.parent_types as usize + generics.types.len();
-            let needs_inline = types > 0 || attr::requests_inline(&ast_item.attrs);
+            let needs_inline = types > 0 || attr::requests_inline(&ast_item.attrs) ||
+                               self.tcx.cross_crate_inlinable(def_id);
             let is_const_fn = sig.constness == hir::Constness::Const;
             let ast = if is_const_fn { Some(body) } else { None };
             let always_encode_mir = self.tcx.sess.opts.debugging_opts.always_encode_mir;
@@ -931,6 +934,7 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
                     constness,
                     arg_names: self.encode_fn_arg_names_for_body(body),
                     sig: self.lazy(&tcx.fn_sig(def_id)),
+                    cross_crate_inlinable: tcx.cross_crate_inlinable(def_id),
                 };
 
                 EntryKind::Fn(self.lazy(&data))
@@ -1121,7 +1125,8 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
                 hir::ItemConst(..) => self.encode_optimized_mir(def_id),
                 hir::ItemFn(_, _, constness, _, ref generics, _) => {
                     let has_tps = generics.ty_params().next().is_some();
-                    let needs_inline = has_tps || attr::requests_inline(&item.attrs);
+                    let needs_inline = has_tps || attr::requests_inline(&item.attrs) ||
+                                       self.tcx.cross_crate_inlinable(def_id);
                     let always_encode_mir = self.tcx.sess.opts.debugging_opts.always_encode_mir;
                     if needs_inline || constness == hir::Constness::Const || always_encode_mir {
                         self.encode_optimized_mir(def_id)
@@ -1423,6 +1428,7 @@ impl<'a, 'b: 'a, 'tcx: 'b> IsolatedEncoder<'a, 'b, 'tcx> {
                     constness: hir::Constness::NotConst,
                     arg_names: self.encode_fn_arg_names(names),
                     sig: self.lazy(&tcx.fn_sig(def_id)),
+                    cross_crate_inlinable: false,
                 };
                 EntryKind::ForeignFn(self.lazy(&data))
             }
@@ -51,6 +51,7 @@ mod decoder;
 mod cstore_impl;
 mod isolated_encoder;
 mod schema;
+mod crate_info;
 mod native_libs;
 mod link_args;
 
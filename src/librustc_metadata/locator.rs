@@ -221,6 +221,7 @@
 
 use cstore::MetadataBlob;
 use creader::Library;
+use crate_info::CrateInfo;
 use schema::{METADATA_HEADER, rustc_version};
 
 use rustc::hir::svh::Svh;
@@ -303,7 +304,36 @@ impl CratePaths {
 
 impl<'a> Context<'a> {
     pub fn maybe_load_library_crate(&mut self) -> Option<Library> {
-        self.find_library_crate()
+        if self.sess.opts.debugging_opts.explain_crate_search {
+            let hash = self.hash.map(|h| format!(" with hash {}", h)).unwrap_or_default();
+            let root = match *self.root {
+                Some(ref r) => format!(", which `{}` depends on", r.ident),
+                None => String::new(),
+            };
+            self.explain(&format!("looking for crate `{}`{} for target `{}`{}",
+                                  self.crate_name, hash, self.triple, root));
+        }
+
+        let library = self.find_library_crate();
+
+        if let Some(ref library) = library {
+            let paths = library.dylib.iter()
+                                     .chain(library.rlib.iter())
+                                     .chain(library.rmeta.iter())
+                                     .map(|&(ref path, _)| path.display().to_string())
+                                     .collect::<Vec<_>>();
+            self.explain(&format!("selected {}", paths.join(", ")));
+        } else {
+            self.explain("no matching crate found");
+        }
+        library
+    }
+
+    /// Print a message for `-Z explain-crate-search`.
+    fn explain(&self, msg: &str) {
+        if self.sess.opts.debugging_opts.explain_crate_search {
+            println!("[crate search: {}] {}", self.ident, msg);
+        }
     }
 
     pub fn report_errs(&mut self) -> ! {
@@ -425,6 +455,7 @@ impl<'a> Context<'a> {
         if self.hash.is_none() {
             self.should_match_name = false;
             if let Some(s) = self.sess.opts.externs.get(&self.crate_name.as_str()) {
+                self.explain("only considering the paths given with `--extern`");
                 return self.find_commandline_library(s.iter());
             }
             self.should_match_name = true;
@@ -454,6 +485,18 @@ impl<'a> Context<'a> {
         // of the crate id (path/name/id).
         //
         // The goal of this step is to look at as little metadata as possible.
+        if self.sess.opts.debugging_opts.explain_crate_search {
+            self.filesearch.for_each_lib_search_path(|dir, kind| {
+                self.explain(&format!("searching directory {} ({:?})", dir.display(), kind));
+            });
+        }
+        let explain_search = self.sess.opts.debugging_opts.explain_crate_search;
+        let ident = self.ident;
+        let explain = |msg: String| {
+            if explain_search {
+                println!("[crate search: {}] {}", ident, msg);
+            }
+        };
         self.filesearch.search(|path, kind| {
             let file = match path.file_name().and_then(|s| s.to_str()) {
                 None => return FileDoesntMatch,
@@ -469,6 +512,8 @@ impl<'a> Context<'a> {
                     (&file[(dylib_prefix.len())..(file.len() - dypair.1.len())], CrateFlavor::Dylib)
                 } else {
                     if file.starts_with(&staticlib_prefix) && file.ends_with(&staticpair.1) {
+                        explain(format!("rejected {}: it is a static library",
+                                        path.display()));
                         staticlibs.push(CrateMismatch {
                             path: path.to_path_buf(),
                             got: "static".to_string(),
@@ -477,6 +522,7 @@ impl<'a> Context<'a> {
                     return FileDoesntMatch;
                 };
             info!("lib candidate: {}", path.display());
+            explain(format!("found candidate {}", path.display()));
 
             let hash_str = hash.to_string();
             let slot = candidates.entry(hash_str)
@@ -527,6 +573,7 @@ impl<'a> Context<'a> {
             0 => None,
             1 => Some(libraries.into_iter().next().unwrap().1),
             _ => {
+                self.explain("rejected all candidates: more than one crate matches");
                 let mut err = struct_span_err!(self.sess,
                                                self.span,
                                                E0464,
@@ -598,11 +645,18 @@ impl<'a> Context<'a> {
                     }
                     Err(err) => {
                         info!("no metadata found: {}", err);
+                        self.explain(&format!("rejected {}: no metadata found: {}",
+                                              lib.display(),
+                                              err));
                         continue;
                     }
                 };
             // If we see multiple hashes, emit an error about duplicate candidates.
             if slot.as_ref().map_or(false, |s| s.0 != hash) {
+                self.explain(&format!("rejected {}: duplicate {} with a different hash than {}",
+                                      lib.display(),
+                                      flavor,
+                                      ret.as_ref().unwrap().0.display()));
                 let mut e = struct_span_err!(self.sess,
                                              self.span,
                                              E0465,
@@ -655,6 +709,10 @@ impl<'a> Context<'a> {
                 let sysroot = sysroot.canonicalize()
                                      .unwrap_or(sysroot.to_path_buf());
                 if prev.starts_with(&sysroot) {
+                    self.explain(&format!("ignored {}: the identical {} in the sysroot \
+                                           is preferred",
+                                          lib.display(),
+                                          prev.display()));
                     continue
                 }
             }
@@ -677,6 +735,11 @@ impl<'a> Context<'a> {
             info!("Rejecting via version: expected {} got {}",
                   rustc_version,
                   found_version);
+            self.explain(&format!("rejected {}: wrong metadata version, it was compiled by \
+                                   {} but this is {}",
+                                  libpath.display(),
+                                  found_version,
+                                  rustc_version));
             self.rejected_via_version.push(CrateMismatch {
                 path: libpath.to_path_buf(),
                 got: found_version,
@@ -687,6 +750,13 @@ impl<'a> Context<'a> {
         let root = metadata.get_root();
         if let Some(is_proc_macro) = self.is_proc_macro {
             if root.macro_derive_registrar.is_some() != is_proc_macro {
+                self.explain(&format!("rejected {}: {}",
+                                      libpath.display(),
+                                      if is_proc_macro {
+                                          "expected a proc-macro crate"
+                                      } else {
+                                          "did not expect a proc-macro crate"
+                                      }));
                 return None;
             }
         }
@@ -694,6 +764,9 @@ impl<'a> Context<'a> {
         if self.should_match_name {
             if self.crate_name != root.name {
                 info!("Rejecting via crate name");
+                self.explain(&format!("rejected {}: crate name is `{}`",
+                                      libpath.display(),
+                                      root.name));
                 return None;
             }
         }
@@ -702,6 +775,9 @@ impl<'a> Context<'a> {
             info!("Rejecting via crate triple: expected {} got {}",
                   self.triple,
                   root.triple);
+            self.explain(&format!("rejected {}: target mismatch, it was compiled for `{}`",
+                                  libpath.display(),
+                                  root.triple));
             self.rejected_via_triple.push(CrateMismatch {
                 path: libpath.to_path_buf(),
                 got: root.triple,
@@ -712,6 +788,10 @@ impl<'a> Context<'a> {
         if let Some(myhash) = self.hash {
             if *myhash != root.hash {
                 info!("Rejecting via hash: expected {} got {}", *myhash, root.hash);
+                self.explain(&format!("rejected {}: SVH mismatch, expected {} but found {}",
+                                      libpath.display(),
+                                      *myhash,
+                                      root.hash));
                 self.rejected_via_hash.push(CrateMismatch {
                     path: libpath.to_path_buf(),
                     got: myhash.to_string(),
@@ -776,6 +856,8 @@ impl<'a> Context<'a> {
                     }
                 }
 
+                self.explain(&format!("rejected {}: not named like a Rust library",
+                                      loc.display()));
                 self.rejected_via_filename.push(CrateMismatch {
                     path: loc.clone(),
                     got: String::new(),
@@ -902,3 +984,34 @@ pub fn list_file_metadata(target: &Target,
         Err(msg) => write!(out, "{}\n", msg),
     }
 }
+
+/// Print a summary of the metadata of the compiled crate at `path`, for
+/// `--print crate-info` and `--print crate-info-json`.
+pub fn print_file_crate_info(target: &Target,
+                             path: &Path,
+                             loader: &MetadataLoader,
+                             json: bool,
+                             out: &mut io::Write)
+                             -> io::Result<()> {
+    let flavor = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("rlib") => CrateFlavor::Rlib,
+        Some("rmeta") => CrateFlavor::Rmeta,
+        _ => CrateFlavor::Dylib,
+    };
+    match get_metadata_section(target, flavor, path, loader) {
+        Ok(ref metadata) if !metadata.is_compatible() => {
+            write!(out, "{}: incompatible metadata version (compiled by {})\n",
+                   path.display(),
+                   metadata.get_rustc_version())
+        }
+        Ok(metadata) => {
+            let info = CrateInfo::from_metadata(&metadata);
+            if json {
+                info.write_json(out)
+            } else {
+                info.write_text(out)
+            }
+        }
+        Err(msg) => write!(out, "{}\n", msg),
+    }
+}
@@ -395,9 +395,10 @@ pub struct FnData<'tcx> {
     pub constness: hir::Constness,
     pub arg_names: LazySeq<ast::Name>,
     pub sig: Lazy<ty::PolyFnSig<'tcx>>,
+    pub cross_crate_inlinable: bool,
 }
 
-impl_stable_hash_for!(struct FnData<'tcx> { constness, arg_names, sig });
+impl_stable_hash_for!(struct FnData<'tcx> { constness, arg_names, sig, cross_crate_inlinable });
 
 #[derive(RustcEncodable, RustcDecodable)]
 pub struct VariantData<'tcx> {
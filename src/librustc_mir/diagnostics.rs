@@ -1768,6 +1768,36 @@ b.resume();
 ```
 "##,
 
+E0693: r##"
+A constant, static or constant function matched on an enum.
+
+Erroneous code example:
+
+```compile_fail,E0693
+#![feature(const_fn, const_control_flow)]
+
+const fn unwrap_or_zero(x: Option<u32>) -> u32 {
+    match x { // error: matching on an enum is not yet supported
+        Some(x) => x,
+        None => 0,
+    }
+}
+```
+
+With `#![feature(const_control_flow)]`, constants and constant functions may
+branch on integers, `bool` and `char`, but the constant evaluator cannot yet
+read the discriminant of an enum. This also rules out `if let` and `while let`
+on enums. Branch on an integer instead:
+
+```
+#![feature(const_fn, const_control_flow)]
+
+const fn value_or_zero(present: bool, x: u32) -> u32 {
+    if present { x } else { 0 }
+}
+```
+"##,
+
 }
 
 register_diagnostics! {
@@ -3,8 +3,9 @@ use rustc::ty::layout::{self, LayoutOf};
 use rustc::ty::subst::Substs;
 use rustc::hir::def_id::DefId;
 use rustc::mir;
-use rustc::middle::const_val::ErrKind::{CheckMatchError, TypeckError};
-use rustc::middle::const_val::{ConstEvalErr, ConstVal};
+use rustc::middle::const_val::ErrKind::{AlreadyReported, CheckMatchError, TypeckError,
+                                        MiscCatchAll, UnimplementedConstVal};
+use rustc::middle::const_val::{ConstEvalErr, ConstVal, ConstAggregate};
 use rustc_const_eval::{lookup_const_by_id, ConstContext};
 use rustc::mir::Field;
 use rustc_data_structures::indexed_vec::Idx;
@@ -27,7 +28,7 @@ pub fn mk_eval_cx<'a, 'tcx>(
     param_env: ty::ParamEnv<'tcx>,
 ) -> EvalResult<'tcx, EvalContext<'a, 'tcx, CompileTimeEvaluator>> {
     debug!("mk_eval_cx: {:?}, {:?}", instance, param_env);
-    let limits = super::ResourceLimits::default();
+    let limits = super::ResourceLimits::for_session(tcx.sess);
     let mut ecx = EvalContext::new(tcx, param_env, limits, CompileTimeEvaluator, ());
     let mir = ecx.load_mir(instance.def)?;
     // insert a stack frame so any queries have the correct substs
@@ -46,9 +47,28 @@ pub fn eval_body<'a, 'tcx>(
     instance: Instance<'tcx>,
     param_env: ty::ParamEnv<'tcx>,
 ) -> EvalResult<'tcx, (Pointer, Ty<'tcx>)> {
+    eval_body_and_ecx(tcx, instance, param_env).0
+}
+
+/// Like `eval_body`, but also returns the `EvalContext`, so errors can be
+/// reported with the stack of the evaluation that failed.
+pub fn eval_body_and_ecx<'a, 'tcx>(
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    instance: Instance<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+) -> (EvalResult<'tcx, (Pointer, Ty<'tcx>)>, EvalContext<'a, 'tcx, CompileTimeEvaluator>) {
     debug!("eval_body: {:?}, {:?}", instance, param_env);
-    let limits = super::ResourceLimits::default();
+    let limits = super::ResourceLimits::for_session(tcx.sess);
     let mut ecx = EvalContext::new(tcx, param_env, limits, CompileTimeEvaluator, ());
+    let res = eval_body_using_ecx(&mut ecx, instance);
+    (res, ecx)
+}
+
+fn eval_body_using_ecx<'a, 'tcx>(
+    ecx: &mut EvalContext<'a, 'tcx, CompileTimeEvaluator>,
+    instance: Instance<'tcx>,
+) -> EvalResult<'tcx, (Pointer, Ty<'tcx>)> {
+    let tcx = ecx.tcx;
     let cid = GlobalId {
         instance,
         promoted: None,
@@ -343,6 +363,45 @@ pub fn const_eval_provider<'a, 'tcx>(
     trace!("running old const eval");
     let old_result = ConstContext::new(tcx, key.param_env.and(substs), tables).eval(&body.value);
     trace!("old const eval produced {:?}", old_result);
+
+    // The old const evaluator does not understand control flow or statements,
+    // which `const fn`s may contain under `#![feature(const_control_flow)]`,
+    // so fall back to miri for those. The feature has to be enabled in the
+    // crate evaluating the constant, too.
+    let unsupported_by_ctfe = match old_result {
+        Err(ConstEvalErr { kind: MiscCatchAll, .. }) |
+        Err(ConstEvalErr { kind: UnimplementedConstVal(_), .. }) => {
+            tcx.sess.features.borrow().const_control_flow
+        }
+        _ => false,
+    };
+    if unsupported_by_ctfe {
+        let instance = ty::Instance::new(def_id, substs);
+        trace!("falling back to miri for {:?}", instance);
+        let (miri_result, mut ecx) = eval_body_and_ecx(tcx, instance, key.param_env);
+        return match miri_result {
+            Ok((miri_val, miri_ty)) => {
+                let layout = ecx.layout_of(miri_ty).unwrap();
+                let miri_place = Place::from_primval_ptr(miri_val, layout.align);
+                match miri_to_const(&mut ecx, miri_place, miri_ty) {
+                    Ok(Some(val)) => Ok(val),
+                    // Values miri can compute but the old const evaluator cannot
+                    // represent are still reported with the original error.
+                    Ok(None) => old_result,
+                    Err(mut err) => {
+                        ecx.report(&mut err);
+                        Err(ConstEvalErr { span: body.value.span, kind: AlreadyReported })
+                    }
+                }
+            }
+            Err(mut err) => {
+                ecx.report(&mut err);
+                // The error has already been reported with the evaluation backtrace.
+                Err(ConstEvalErr { span: body.value.span, kind: AlreadyReported })
+            }
+        };
+    }
+
     if tcx.sess.opts.debugging_opts.miri {
         let instance = ty::Instance::new(def_id, substs);
         trace!("const eval instance: {:?}, {:?}", instance, key.param_env);
@@ -372,6 +431,101 @@ pub fn const_eval_provider<'a, 'tcx>(
     }
 }
 
+/// Converts the value miri computed for a constant into the representation
+/// of the old const evaluator. Returns `None` for types that representation
+/// does not cover (e.g. references and pointers).
+fn miri_to_const<'a, 'tcx>(
+    ecx: &mut EvalContext<'a, 'tcx, CompileTimeEvaluator>,
+    miri_place: Place,
+    miri_ty: Ty<'tcx>,
+) -> EvalResult<'tcx, Option<&'tcx ty::Const<'tcx>>> {
+    use rustc::ty::TypeVariants::*;
+    use rustc_const_math::ConstFloat;
+
+    let tcx = ecx.tcx;
+    let mk_const = |val| -> EvalResult<'tcx, Option<&'tcx ty::Const<'tcx>>> {
+        Ok(Some(tcx.mk_const(ty::Const { val, ty: miri_ty })))
+    };
+    let prim = |ecx: &mut EvalContext<'a, 'tcx, CompileTimeEvaluator>| -> EvalResult<'tcx, u128> {
+        let value = ecx.read_place(miri_place)?;
+        ecx.value_to_primval(ValTy { value, ty: miri_ty })?.to_bytes()
+    };
+    match miri_ty.sty {
+        TyInt(int_ty) => {
+            let bits = prim(ecx)?;
+            let i = ConstInt::new_signed_truncating(bits as i128,
+                                                    int_ty,
+                                                    tcx.sess.target.isize_ty);
+            mk_const(ConstVal::Integral(i))
+        }
+        TyUint(uint_ty) => {
+            let bits = prim(ecx)?;
+            let i = ConstInt::new_unsigned_truncating(bits,
+                                                      uint_ty,
+                                                      tcx.sess.target.usize_ty);
+            mk_const(ConstVal::Integral(i))
+        }
+        TyFloat(ty) => {
+            let bits = prim(ecx)?;
+            mk_const(ConstVal::Float(ConstFloat { bits, ty }))
+        }
+        TyBool => {
+            match prim(ecx)? {
+                0 => mk_const(ConstVal::Bool(false)),
+                1 => mk_const(ConstVal::Bool(true)),
+                _ => err!(InvalidBool),
+            }
+        }
+        TyChar => {
+            let bits = prim(ecx)?;
+            match ::std::char::from_u32(bits as u32) {
+                Some(c) => mk_const(ConstVal::Char(c)),
+                None => err!(InvalidChar(bits)),
+            }
+        }
+        TyArray(elem_ty, n) => {
+            let n = n.val.to_const_int().unwrap().to_u64().unwrap();
+            let layout = ecx.layout_of(miri_ty)?;
+            let mut elems = Vec::with_capacity(n as usize);
+            for i in 0..n {
+                let (field_place, _) = ecx.place_field(miri_place, Field::new(i as usize), layout)?;
+                match miri_to_const(ecx, field_place, elem_ty)? {
+                    Some(elem) => elems.push(elem),
+                    None => return Ok(None),
+                }
+            }
+            mk_const(ConstVal::Aggregate(ConstAggregate::Array(tcx.alloc_const_slice(&elems))))
+        }
+        TyTuple(tys, _) => {
+            let layout = ecx.layout_of(miri_ty)?;
+            let mut fields = Vec::with_capacity(tys.len());
+            for (i, &field_ty) in tys.iter().enumerate() {
+                let (field_place, _) = ecx.place_field(miri_place, Field::new(i), layout)?;
+                match miri_to_const(ecx, field_place, field_ty)? {
+                    Some(field) => fields.push(field),
+                    None => return Ok(None),
+                }
+            }
+            mk_const(ConstVal::Aggregate(ConstAggregate::Tuple(tcx.alloc_const_slice(&fields))))
+        }
+        TyAdt(def, substs) if def.is_struct() => {
+            let layout = ecx.layout_of(miri_ty)?;
+            let variant = def.non_enum_variant();
+            let mut fields = Vec::with_capacity(variant.fields.len());
+            for (i, field) in variant.fields.iter().enumerate() {
+                let (field_place, _) = ecx.place_field(miri_place, Field::new(i), layout)?;
+                match miri_to_const(ecx, field_place, field.ty(tcx, substs))? {
+                    Some(val) => fields.push((field.name, val)),
+                    None => return Ok(None),
+                }
+            }
+            mk_const(ConstVal::Aggregate(ConstAggregate::Struct(
+                tcx.alloc_name_const_slice(&fields))))
+        }
+        _ => Ok(None),
+    }
+}
+
 fn check_ctfe_against_miri<'a, 'tcx>(
     ecx: &mut EvalContext<'a, 'tcx, CompileTimeEvaluator>,
     miri_place: Place,
@@ -5,6 +5,7 @@ use rustc::hir::def_id::DefId;
 use rustc::hir::map::definitions::DefPathData;
 use rustc::middle::const_val::ConstVal;
 use rustc::mir;
+use rustc::session::Session;
 use rustc::traits::Reveal;
 use rustc::ty::layout::{self, Size, Align, HasDataLayout, LayoutOf, TyLayout};
 use rustc::ty::subst::{Subst, Substs, Kind};
@@ -40,6 +41,10 @@ pub struct EvalContext<'a, 'tcx: 'a, M: Machine<'tcx>> {
     /// The maximum number of stack frames allowed
     pub(crate) stack_limit: usize,
 
+    /// The total number of operations that may be executed, used for reporting
+    /// once `steps_remaining` runs out.
+    pub(crate) step_limit: u64,
+
     /// The maximum number of operations that may be executed.
     /// This prevents infinite loops and huge computations from freezing up const eval.
     /// Remove once halting problem is solved.
@@ -119,6 +124,18 @@ impl Default for ResourceLimits {
     }
 }
 
+impl ResourceLimits {
+    /// The default limits, with the step and stack limits taken from
+    /// `-Z const-eval-step-limit` and `-Z const-eval-stack-limit`.
+    pub fn for_session(sess: &Session) -> Self {
+        ResourceLimits {
+            step_limit: sess.const_eval_step_limit(),
+            stack_limit: sess.const_eval_stack_limit(),
+            ..ResourceLimits::default()
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct TyAndPacked<'tcx> {
     pub ty: Ty<'tcx>,
@@ -202,6 +219,7 @@ impl<'a, 'tcx, M: Machine<'tcx>> EvalContext<'a, 'tcx, M> {
             memory: Memory::new(tcx, limits.memory_size, memory_data),
             stack: Vec::new(),
             stack_limit: limits.stack_limit,
+            step_limit: limits.step_limit,
             steps_remaining: limits.step_limit,
         }
     }
@@ -457,7 +475,7 @@ impl<'a, 'tcx, M: Machine<'tcx>> EvalContext<'a, 'tcx, M> {
         self.memory.cur_frame = self.cur_frame();
 
         if self.stack.len() > self.stack_limit {
-            err!(StackFrameLimitReached)
+            err!(StackFrameLimitReached { stack_limit: self.stack_limit })
         } else {
             Ok(())
         }
@@ -1614,6 +1632,15 @@ impl<'a, 'tcx, M: Machine<'tcx>> EvalContext<'a, 'tcx, M> {
                 }
                 err.span_note(span, &format!("inside call to {}", instance));
             }
+            match e.kind {
+                EvalErrorKind::ExecutionTimeLimitReached { .. } => {
+                    err.help("use `-Z const-eval-step-limit=N` to allow more steps");
+                }
+                EvalErrorKind::StackFrameLimitReached { .. } => {
+                    err.help("use `-Z const-eval-stack-limit=N` to allow deeper recursion");
+                }
+                _ => {}
+            }
             err.emit();
         } else {
             self.tcx.sess.err(&e.to_string());
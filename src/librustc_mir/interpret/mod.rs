@@ -7,6 +7,7 @@ mod place;
 mod machine;
 mod memory;
 mod operator;
+mod static_assert;
 mod step;
 mod terminator;
 mod traits;
@@ -21,3 +22,5 @@ pub use self::memory::{Memory, MemoryKind, HasMemory};
 pub use self::const_eval::{eval_body_as_integer, eval_body, CompileTimeEvaluator, const_eval_provider};
 
 pub use self::machine::Machine;
+
+pub use self::static_assert::check_static_asserts;
@@ -0,0 +1,199 @@
+//! Checking of the constants generated by `static_assert!`.
+//!
+//! Each `static_assert!(EXPR)` expands to a `bool` constant marked with
+//! `#[rustc_static_assert = "message"]`. Nothing else uses these constants,
+//! so they are evaluated here with miri and a `false` value is reported as an
+//! error, together with the operands miri computed if `EXPR` is a comparison.
+
+use rustc::hir;
+use rustc::hir::itemlikevisit::ItemLikeVisitor;
+use rustc::mir::{self, BinOp, Operand, Rvalue, StatementKind, RETURN_PLACE};
+use rustc::mir::interpret::EvalResult;
+use rustc::ty::{Instance, TyCtxt};
+use rustc::ty::layout::LayoutOf;
+use rustc::ty::subst::Substs;
+use syntax::ast;
+use syntax_pos::Span;
+
+use super::{CompileTimeEvaluator, EvalContext, Place, ResourceLimits, StackPopCleanup, ValTy};
+
+pub fn check_static_asserts<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
+    tcx.hir.krate().visit_all_item_likes(&mut StaticAssertVisitor { tcx });
+}
+
+struct StaticAssertVisitor<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+}
+
+/// The values of the two sides of the comparison an assertion ends with.
+type Operands = (Option<String>, Option<String>);
+
+impl<'a, 'tcx> StaticAssertVisitor<'a, 'tcx> {
+    fn check(&self, id: ast::NodeId, attrs: &[ast::Attribute], body: hir::BodyId, span: Span) {
+        let message = match static_assert_message(attrs) {
+            Some(message) => message,
+            None => return,
+        };
+
+        let tcx = self.tcx;
+        let def_id = tcx.hir.local_def_id(id);
+        let substs = Substs::identity_for_item(tcx, def_id);
+        if substs.types().next().is_some() {
+            tcx.sess.span_err(span, "`static_assert!` cannot be used in a generic context");
+            return;
+        }
+
+        let param_env = tcx.param_env(def_id);
+        let limits = ResourceLimits::for_session(tcx.sess);
+        let mut ecx = EvalContext::new(tcx, param_env, limits, CompileTimeEvaluator, ());
+        let mut operands = None;
+        match eval_assertion(&mut ecx, Instance::new(def_id, substs), &mut operands) {
+            Ok(true) => {}
+            Ok(false) => {
+                let mut err = tcx.sess.struct_span_err(span, &message.as_str());
+                let expr = &tcx.hir.body(body).value;
+                if let (&hir::ExprBinary(op, ref lhs, ref rhs), Some((lhs_value, rhs_value))) =
+                       (&expr.node, operands) {
+                    if op.node.is_comparison() {
+                        for &(operand, ref value) in &[(lhs, lhs_value), (rhs, rhs_value)] {
+                            if let Some(ref value) = *value {
+                                err.span_label(operand.span,
+                                               format!("this evaluates to `{}`", value));
+                            }
+                        }
+                    }
+                }
+                err.emit();
+            }
+            Err(mut err) => ecx.report(&mut err),
+        }
+    }
+}
+
+fn static_assert_message(attrs: &[ast::Attribute]) -> Option<ast::Name> {
+    attrs.iter()
+         .find(|a| a.check_name("rustc_static_assert"))
+         .and_then(|attr| attr.value_str())
+}
+
+/// Evaluates the assertion `instance`, recording the values of the operands
+/// if its value is computed by a comparison.
+fn eval_assertion<'a, 'tcx>(
+    ecx: &mut EvalContext<'a, 'tcx, CompileTimeEvaluator>,
+    instance: Instance<'tcx>,
+    operands: &mut Option<Operands>,
+) -> EvalResult<'tcx, bool> {
+    let bool_ty = ecx.tcx.types.bool;
+    let mir = ecx.load_mir(instance.def)?;
+    let layout = ecx.layout_of(bool_ty)?;
+    let ptr = ecx.memory.allocate(layout.size.bytes(), layout.align, None)?;
+    let place = Place::from_ptr(ptr, layout.align);
+    ecx.push_stack_frame(instance, mir.span, mir, place, StackPopCleanup::None)?;
+
+    loop {
+        let comparison = if ecx.stack().len() == 1 {
+            let frame = ecx.frame();
+            comparison_operands(frame.mir, frame.block, frame.stmt)
+                .map(|operands| (operands, frame.block, frame.stmt))
+        } else {
+            None
+        };
+
+        if !ecx.step()? {
+            break;
+        }
+
+        // Referenced constants are evaluated in frames of their own before
+        // the statement using them, so only read the operands once the
+        // comparison itself has been executed.
+        if let Some(((lhs, rhs), block, stmt)) = comparison {
+            if ecx.stack().len() == 1 && ecx.frame().block == block &&
+               ecx.frame().stmt == stmt + 1 {
+                let lhs = ecx.eval_operand(lhs)?;
+                let rhs = ecx.eval_operand(rhs)?;
+                *operands = Some((describe(ecx, lhs), describe(ecx, rhs)));
+            }
+        }
+    }
+
+    let value = ecx.read_place(place)?;
+    ecx.value_to_primval(ValTy { value, ty: bool_ty })?.to_bool()
+}
+
+/// The operands of the statement at `block[stmt]` if it stores the result of
+/// a comparison in the return place.
+fn comparison_operands<'tcx>(mir: &'tcx mir::Mir<'tcx>,
+                             block: mir::BasicBlock,
+                             stmt: usize)
+                             -> Option<(&'tcx Operand<'tcx>, &'tcx Operand<'tcx>)> {
+    let statement = mir[block].statements.get(stmt)?;
+    match statement.kind {
+        StatementKind::Assign(mir::Place::Local(RETURN_PLACE),
+                              Rvalue::BinaryOp(op, ref lhs, ref rhs)) => {
+            match op {
+                BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge => {
+                    Some((lhs, rhs))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// A human-readable form of a primitive value miri computed.
+fn describe<'a, 'tcx>(ecx: &EvalContext<'a, 'tcx, CompileTimeEvaluator>,
+                      value: ValTy<'tcx>)
+                      -> Option<String> {
+    use rustc::ty::TypeVariants::*;
+
+    let ty = value.ty;
+    let render = || -> EvalResult<'tcx, Option<String>> {
+        Ok(Some(match ty.sty {
+            TyBool => ecx.value_to_primval(value)?.to_bool()?.to_string(),
+            TyChar => {
+                let bits = ecx.value_to_primval(value)?.to_bytes()?;
+                match ::std::char::from_u32(bits as u32) {
+                    Some(c) => format!("{:?}", c),
+                    None => return Ok(None),
+                }
+            }
+            TyInt(int_ty) => {
+                let bits = ecx.value_to_primval(value)?.to_bytes()?;
+                let width = int_ty.bit_width()
+                    .unwrap_or(ecx.tcx.data_layout.pointer_size.bits() as usize);
+                let shift = 128 - width;
+                (((bits << shift) as i128) >> shift).to_string()
+            }
+            TyUint(_) => ecx.value_to_primval(value)?.to_bytes()?.to_string(),
+            TyFloat(ast::FloatTy::F32) => ecx.value_to_primval(value)?.to_f32()?.to_string(),
+            TyFloat(ast::FloatTy::F64) => ecx.value_to_primval(value)?.to_f64()?.to_string(),
+            _ => return Ok(None),
+        }))
+    };
+    render().ok().and_then(|value| value)
+}
+
+impl<'a, 'tcx, 'v> ItemLikeVisitor<'v> for StaticAssertVisitor<'a, 'tcx> {
+    fn visit_item(&mut self, item: &hir::Item) {
+        if let hir::ItemConst(_, body) = item.node {
+            self.check(item.id, &item.attrs, body, item.span);
+        }
+    }
+
+    fn visit_trait_item(&mut self, trait_item: &hir::TraitItem) {
+        // Assertions in traits would have to hold for every implementation,
+        // and depend on `Self` like any other generic context.
+        if static_assert_message(&trait_item.attrs).is_some() {
+            self.tcx.sess.span_err(trait_item.span,
+                                   "`static_assert!` cannot be used in a trait; \
+                                    assert in its implementations instead");
+        }
+    }
+
+    fn visit_impl_item(&mut self, impl_item: &hir::ImplItem) {
+        if let hir::ImplItemKind::Const(_, body) = impl_item.node {
+            self.check(impl_item.id, &impl_item.attrs, body, impl_item.span);
+        }
+    }
+}
@@ -22,7 +22,7 @@ impl<'a, 'tcx, M: Machine<'tcx>> EvalContext<'a, 'tcx, M> {
         if self.steps_remaining > 0 {
             Ok(())
         } else {
-            err!(ExecutionTimeLimitReached)
+            err!(ExecutionTimeLimitReached { step_limit: self.step_limit })
         }
     }
 
@@ -153,14 +153,20 @@ pub trait CodegenUnitExt<'tcx> {
                                         tcx: TyCtxt<'a, 'tcx, 'tcx>)
                                         -> Vec<(MonoItem<'tcx>,
                                                 (Linkage, Visibility))> {
-        // The codegen tests rely on items being process in the same order as
-        // they appear in the file, so for local items, we sort by node_id first
+        // Items listed in the `-C link-order-file` come first, in the order
+        // given there. The codegen tests rely on the remaining items being
+        // processed in the same order as they appear in the file, so for
+        // local items, we sort by node_id next
         #[derive(PartialEq, Eq, PartialOrd, Ord)]
-        pub struct ItemSortKey(Option<NodeId>, ty::SymbolName);
+        pub struct ItemSortKey(usize, Option<NodeId>, ty::SymbolName);
 
         fn item_sort_key<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                                    item: MonoItem<'tcx>) -> ItemSortKey {
-            ItemSortKey(match item {
+            let symbol_name = item.symbol_name(tcx);
+            let link_position = tcx.sess.link_order.as_ref()
+                .and_then(|link_order| link_order.position(&symbol_name))
+                .unwrap_or(usize::MAX);
+            ItemSortKey(link_position, match item {
                 MonoItem::Fn(ref instance) => {
                     match instance.def {
                         // We only want to take NodeIds of user-defined
@@ -184,7 +190,7 @@ pub trait CodegenUnitExt<'tcx> {
                 MonoItem::GlobalAsm(node_id) => {
                     Some(node_id)
                 }
-            }, item.symbol_name(tcx))
+            }, symbol_name)
         }
 
         let items: Vec<_> = self.items().iter().map(|(&i, &l)| (i, l)).collect();
@@ -212,6 +218,18 @@ fn fallback_cgu_name(tcx: TyCtxt) -> InternedString {
     }
 }
 
+// Items listed in the `-C link-order-file` go into this, so that they end up
+// next to each other in a single object file.
+fn link_order_cgu_name(tcx: TyCtxt) -> InternedString {
+    const LINK_ORDER_CODEGEN_UNIT: &'static str = "__rustc_link_order_codegen_unit";
+
+    if tcx.sess.opts.debugging_opts.human_readable_cgu_names {
+        Symbol::intern(LINK_ORDER_CODEGEN_UNIT).as_str()
+    } else {
+        Symbol::intern(&CodegenUnit::mangle_name(LINK_ORDER_CODEGEN_UNIT)).as_str()
+    }
+}
+
 
 pub fn partition<'a, 'tcx, I>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                               trans_items: I,
@@ -310,7 +328,12 @@ fn place_root_translation_items<'a, 'tcx, I>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         let is_volatile = is_incremental_build &&
                           trans_item.is_generic_fn();
 
+        let is_link_ordered = tcx.sess.link_order.as_ref().map_or(false, |link_order| {
+            link_order.position(&trans_item.symbol_name(tcx)).is_some()
+        });
+
         let codegen_unit_name = match characteristic_def_id {
+            _ if is_link_ordered => link_order_cgu_name(tcx),
             Some(def_id) => compute_codegen_unit_name(tcx, def_id, is_volatile),
             None => fallback_cgu_name(tcx),
         };
@@ -0,0 +1,161 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Decides which functions are small enough to be made available for
+//! inlining in other crates without an `#[inline]` attribute
+//! (`-Z cross-crate-inline-threshold`).
+//!
+//! Such functions are treated like `#[inline]` ones: their optimized MIR is
+//! encoded in the crate metadata, and every crate (or codegen unit) using
+//! them translates its own local copy, which LLVM can then inline without
+//! any kind of LTO. To keep this from affecting which symbols have to be
+//! exported, only leaf functions are considered: they may not call other
+//! functions, run drop glue, or refer to statics.
+
+use rustc::hir::def_id::DefId;
+use rustc::hir::map as hir_map;
+use rustc::hir;
+use rustc::mir::*;
+use rustc::mir::visit::*;
+use rustc::session::config::OptLevel;
+use rustc::ty::{self, TyCtxt};
+use rustc::ty::maps::Providers;
+use syntax::abi::Abi;
+use syntax::attr::{self, InlineAttr};
+
+pub fn provide(providers: &mut Providers) {
+    *providers = Providers {
+        cross_crate_inlinable,
+        ..*providers
+    };
+}
+
+fn cross_crate_inlinable<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, def_id: DefId) -> bool {
+    let threshold = match tcx.sess.opts.debugging_opts.cross_crate_inline_threshold {
+        Some(threshold) => threshold,
+        None => return false,
+    };
+    if tcx.sess.opts.optimize == OptLevel::No {
+        return false
+    }
+
+    let node_id = tcx.hir.as_local_node_id(def_id)
+                         .expect("Non-local call to local provider cross_crate_inlinable");
+    match tcx.hir.get(node_id) {
+        hir_map::NodeItem(&hir::Item { node: hir::ItemFn(..), .. }) |
+        hir_map::NodeImplItem(&hir::ImplItem { node: hir::ImplItemKind::Method(..), .. }) => {}
+        _ => return false,
+    }
+
+    let generics = tcx.generics_of(def_id);
+    if generics.parent_types as usize + generics.types.len() > 0 {
+        // Already instantiated in every crate using it.
+        return false
+    }
+    if tcx.fn_sig(def_id).abi() != Abi::Rust ||
+       tcx.contains_extern_indicator(def_id) ||
+       tcx.is_const_fn(def_id) {
+        return false
+    }
+    match attr::find_inline_attr(None, &tcx.get_attrs(def_id)) {
+        InlineAttr::None => {}
+        // `#[inline]` already does what we want, and `#[inline(never)]`
+        // means the function is to stay out of line.
+        _ => return false,
+    }
+
+    let mir = tcx.optimized_mir(def_id);
+    if !mir.promoted.is_empty() {
+        return false
+    }
+
+    let mut checker = LeafChecker {
+        is_leaf: true,
+        size: 0,
+    };
+    checker.visit_mir(mir);
+    debug!("cross_crate_inlinable({:?}): leaf = {}, size = {}",
+           def_id, checker.is_leaf, checker.size);
+    checker.is_leaf && checker.size <= threshold
+}
+
+struct LeafChecker {
+    is_leaf: bool,
+    size: usize,
+}
+
+impl<'tcx> Visitor<'tcx> for LeafChecker {
+    fn visit_statement(&mut self,
+                       block: BasicBlock,
+                       statement: &Statement<'tcx>,
+                       location: Location) {
+        match statement.kind {
+            StatementKind::StorageLive(_) |
+            StatementKind::StorageDead(_) |
+            StatementKind::EndRegion(_) |
+            StatementKind::Validate(..) |
+            StatementKind::Nop => {}
+            StatementKind::InlineAsm { .. } => self.is_leaf = false,
+            _ => self.size += 1,
+        }
+        self.super_statement(block, statement, location);
+    }
+
+    fn visit_terminator_kind(&mut self,
+                             block: BasicBlock,
+                             kind: &TerminatorKind<'tcx>,
+                             location: Location) {
+        match *kind {
+            TerminatorKind::Goto { .. } |
+            TerminatorKind::SwitchInt { .. } |
+            TerminatorKind::Resume |
+            TerminatorKind::Abort |
+            TerminatorKind::Return |
+            TerminatorKind::Unreachable |
+            TerminatorKind::Assert { .. } |
+            TerminatorKind::FalseEdges { .. } |
+            TerminatorKind::FalseUnwind { .. } => {}
+            TerminatorKind::Drop { .. } |
+            TerminatorKind::DropAndReplace { .. } |
+            TerminatorKind::Call { .. } |
+            TerminatorKind::Yield { .. } |
+            TerminatorKind::GeneratorDrop => self.is_leaf = false,
+        }
+        self.size += 1;
+        self.super_terminator_kind(block, kind, location);
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
+        match *rvalue {
+            Rvalue::Cast(CastKind::Misc, ..) => {}
+            // Unsizing may need a vtable, and the others a function pointer.
+            Rvalue::Cast(..) |
+            Rvalue::NullaryOp(NullOp::Box, _) => self.is_leaf = false,
+            _ => {}
+        }
+        self.super_rvalue(rvalue, location);
+    }
+
+    fn visit_static(&mut self,
+                    static_: &Static<'tcx>,
+                    context: PlaceContext<'tcx>,
+                    location: Location) {
+        self.is_leaf = false;
+        self.super_static(static_, context, location);
+    }
+
+    fn visit_constant(&mut self, constant: &Constant<'tcx>, location: Location) {
+        // Function items are only used to call or reify them.
+        if let ty::TyFnDef(..) = constant.ty.sty {
+            self.is_leaf = false;
+        }
+        self.super_constant(constant, location);
+    }
+}
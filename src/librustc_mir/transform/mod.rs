@@ -41,6 +41,7 @@ pub mod dump_mir;
 pub mod deaggregator;
 pub mod instcombine;
 pub mod copy_prop;
+pub mod cross_crate_inline;
 pub mod generator;
 pub mod inline;
 pub mod lower_128bit;
@@ -49,6 +50,7 @@ pub mod uniform_array_move_out;
 pub(crate) fn provide(providers: &mut Providers) {
     self::qualify_consts::provide(providers);
     self::check_unsafety::provide(providers);
+    self::cross_crate_inline::provide(providers);
     *providers = Providers {
         mir_keys,
         mir_built,
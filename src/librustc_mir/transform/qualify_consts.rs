@@ -124,6 +124,9 @@ struct Qualifier<'a, 'gcx: 'a+'tcx, 'tcx: 'a> {
     return_qualif: Option<Qualif>,
     qualif: Qualif,
     const_fn_arg_vars: BitVector,
+    // Whether `#![feature(const_control_flow)]` permits branches,
+    // loops and local variables in this constant or `const fn`.
+    control_flow: bool,
     temp_promotion_state: IndexVec<Local, TempState>,
     promotion_candidates: Vec<Candidate>
 }
@@ -147,6 +150,9 @@ impl<'a, 'tcx> Qualifier<'a, 'tcx, 'tcx> {
             temp_qualif[arg] = Some(qualif);
         }
 
+        let control_flow = mode != Mode::Fn &&
+            tcx.sess.features.borrow().const_control_flow;
+
         Qualifier {
             mode,
             span: mir.span,
@@ -159,6 +165,7 @@ impl<'a, 'tcx> Qualifier<'a, 'tcx, 'tcx> {
             return_qualif: None,
             qualif: Qualif::empty(),
             const_fn_arg_vars: BitVector::new(mir.local_decls.len()),
+            control_flow,
             temp_promotion_state: temps,
             promotion_candidates: vec![]
         }
@@ -187,6 +194,25 @@ impl<'a, 'tcx> Qualifier<'a, 'tcx, 'tcx> {
         }
     }
 
+    /// Error about branches or loops in a constant.
+    fn control_flow_not_const(&mut self) {
+        self.add(Qualif::NOT_CONST);
+        if self.mode != Mode::Fn {
+            let mut err = struct_span_err!(
+                self.tcx.sess,
+                self.span,
+                E0019,
+                "{} contains unimplemented expression type",
+                self.mode
+            );
+            if UnstableFeatures::from_environment().is_nightly_build() {
+                err.help(&format!("add #![feature(const_control_flow)] to the crate attributes \
+                                   to allow branches and loops in {}s", self.mode));
+            }
+            err.emit();
+        }
+    }
+
     /// Error about extra statements in a constant.
     fn statement_like(&mut self) {
         self.add(Qualif::NOT_CONST);
@@ -268,9 +294,16 @@ impl<'a, 'tcx> Qualifier<'a, 'tcx, 'tcx> {
     fn assign(&mut self, dest: &Place<'tcx>, location: Location) {
         let qualif = self.qualif;
         let span = self.span;
+        let control_flow = self.control_flow;
         let store = |slot: &mut Option<Qualif>| {
-            if slot.is_some() {
-                span_bug!(span, "multiple assignments to {:?}", dest);
+            // With control flow, the same temp (e.g. the result of an `if`)
+            // can be assigned on several paths, so merge the qualifications.
+            if let Some(previous) = *slot {
+                if !control_flow {
+                    span_bug!(span, "multiple assignments to {:?}", dest);
+                }
+                *slot = Some(previous | qualif);
+                return;
             }
             *slot = Some(qualif);
         };
@@ -296,6 +329,13 @@ impl<'a, 'tcx> Qualifier<'a, 'tcx, 'tcx> {
                 debug!("store to return place {:?}", index);
                 store(&mut self.return_qualif)
             }
+            Place::Local(index) if self.control_flow &&
+                                   (self.mir.local_kind(index) == LocalKind::Var ||
+                                    self.mir.local_kind(index) == LocalKind::Arg) => {
+                // Variables (and `mut` arguments) may be assigned any number of
+                // times; reads of them are qualified by their type instead.
+                debug!("store to variable {:?}", index);
+            }
 
             Place::Projection(box Projection {
                 base: Place::Local(index),
@@ -322,6 +362,10 @@ impl<'a, 'tcx> Qualifier<'a, 'tcx, 'tcx> {
     fn qualify_const(&mut self) -> (Qualif, Rc<IdxSetBuf<Local>>) {
         debug!("qualifying {} {:?}", self.mode, self.def_id);
 
+        if self.control_flow {
+            return self.qualify_const_control_flow();
+        }
+
         let mir = self.mir;
 
         let mut seen_blocks = BitVector::new(mir.basic_blocks().len());
@@ -402,12 +446,69 @@ impl<'a, 'tcx> Qualifier<'a, 'tcx, 'tcx> {
                     bb = target;
                 }
                 _ => {
-                    self.not_const();
+                    self.control_flow_not_const();
                     break;
                 }
             }
         }
 
+        self.finish_qualify_const()
+    }
+
+    /// Qualify a constant, static initializer or `const fn` which may contain
+    /// arbitrary (reachable) control flow, visiting every block instead of
+    /// following a single path.
+    fn qualify_const_control_flow(&mut self) -> (Qualif, Rc<IdxSetBuf<Local>>) {
+        let mir = self.mir;
+        let mut returns = false;
+
+        while let Some((bb, data)) = self.rpo.next() {
+            self.visit_basic_block_data(bb, data);
+
+            match data.terminator().kind {
+                TerminatorKind::DropAndReplace { .. } |
+                TerminatorKind::GeneratorDrop |
+                TerminatorKind::Yield { .. } => {
+                    self.span = data.terminator().source_info.span;
+                    self.not_const();
+                }
+                TerminatorKind::Return => returns = true,
+                _ => {}
+            }
+        }
+
+        if !returns {
+            // A body that never returns cannot produce any value.
+            self.return_qualif = None;
+        }
+
+        // Check for unused values, which usually means there are
+        // expression statements without any effect. Unit temps are
+        // produced by loop bodies and `if` without `else`, and are fine.
+        for temp in mir.temps_iter() {
+            if self.temp_qualif[temp].is_none() || mir.local_decls[temp].ty.is_nil() {
+                continue;
+            }
+
+            if let TempState::Defined { location, uses: 0 } = self.temp_promotion_state[temp] {
+                let data = &mir[location.block];
+                let stmt_idx = location.statement_index;
+                let source_info = if stmt_idx < data.statements.len() {
+                    data.statements[stmt_idx].source_info
+                } else {
+                    data.terminator().source_info
+                };
+                self.span = source_info.span;
+                self.statement_like();
+            }
+        }
+
+        self.finish_qualify_const()
+    }
+
+    fn finish_qualify_const(&mut self) -> (Qualif, Rc<IdxSetBuf<Local>>) {
+        let mir = self.mir;
+
         self.qualif = self.return_qualif.unwrap_or(Qualif::NOT_CONST);
 
         // Account for errors in consts by using the
@@ -453,6 +554,10 @@ impl<'a, 'tcx> Visitor<'tcx> for Qualifier<'a, 'tcx, 'tcx> {
             LocalKind::ReturnPointer => {
                 self.not_const();
             }
+            LocalKind::Var if self.control_flow => {
+                let ty = self.mir.local_decls[local].ty;
+                self.add_type(ty);
+            }
             LocalKind::Var => {
                 self.add(Qualif::NOT_CONST);
             }
@@ -652,6 +757,18 @@ impl<'a, 'tcx> Visitor<'tcx> for Qualifier<'a, 'tcx, 'tcx> {
         }
 
         match *rvalue {
+            Rvalue::Discriminant(..) if self.control_flow => {
+                // Constant translation cannot read enum discriminants,
+                // so `match` is limited to integers, `bool` and `char`.
+                self.add(Qualif::NOT_CONST);
+                struct_span_err!(self.tcx.sess, self.span, E0693,
+                                 "matching on an enum is not yet supported in {}s",
+                                 self.mode)
+                    .span_label(self.span, "reads the discriminant of an enum")
+                    .note("only integers, `bool` and `char` can be matched on")
+                    .emit();
+            }
+
             Rvalue::Use(_) |
             Rvalue::Repeat(..) |
             Rvalue::UnaryOp(UnOp::Neg, _) |
@@ -1040,7 +1157,8 @@ This does not pose a problem by itself because they can't be accessed directly."
         self.visit_rvalue(rvalue, location);
 
         // Check the allowed const fn argument forms.
-        if let (Mode::ConstFn, &Place::Local(index)) = (self.mode, dest) {
+        if let (Mode::ConstFn, &Place::Local(index), false) =
+               (self.mode, dest, self.control_flow) {
             if self.mir.local_kind(index) == LocalKind::Var &&
                self.const_fn_arg_vars.insert(index.index()) {
 
@@ -14,9 +14,10 @@ use super::linker::Linker;
 use super::command::Command;
 use super::rpath::RPathConfig;
 use super::rpath;
+use super::size_report;
 use metadata::METADATA_FILENAME;
 use rustc::session::config::{self, NoDebugInfo, OutputFilenames, OutputType, PrintRequest};
-use rustc::session::config::{RUST_CGU_EXT, Lto};
+use rustc::session::config::{RUST_CGU_EXT, Lto, SplitDebuginfo};
 use rustc::session::filesearch;
 use rustc::session::search_paths::PathKind;
 use rustc::session::Session;
@@ -179,6 +180,15 @@ pub(crate) fn link_binary(sess: &Session,
         if let Some(ref obj) = trans.metadata_module.object {
             remove(sess, obj);
         }
+        // Once packaged, the `.dwo` files are only needed by the crates
+        // linking to an rlib of this one.
+        if sess.opts.debugging_opts.split_debuginfo == SplitDebuginfo::Packed &&
+           !sess.crate_types.borrow().contains(&config::CrateTypeRlib)
+        {
+            for dwo in trans.modules.iter().filter_map(|m| m.dwarf_object.as_ref()) {
+                remove(sess, dwo);
+            }
+        }
         if let Some(ref allocator) = trans.allocator_module {
             if let Some(ref obj) = allocator.object {
                 remove(sess, obj);
@@ -752,6 +762,12 @@ fn link_natively(sess: &Session,
             }
             info!("linker stderr:\n{}", escape_string(&prog.stderr));
             info!("linker stdout:\n{}", escape_string(&prog.stdout));
+
+            if sess.opts.debugging_opts.emit_link_map {
+                time(sess.time_passes(), "writing size report", || {
+                    size_report::write_size_report(sess, out_filename, trans)
+                });
+            }
         },
         Err(e) => {
             let linker_not_found = e.kind() == io::ErrorKind::NotFound;
@@ -783,6 +799,10 @@ fn link_natively(sess: &Session,
     }
 
 
+    if sess.split_dwarf() && sess.opts.debugging_opts.split_debuginfo == SplitDebuginfo::Packed {
+        package_dwarf_objects(sess, out_filename);
+    }
+
     // On macOS, debuggers need this utility to get run to do some munging of
     // the symbols. Note, though, that if the object files are being preserved
     // for their debug information there's no need for us to run dsymutil.
@@ -797,6 +817,34 @@ fn link_natively(sess: &Session,
     }
 }
 
+/// Packages the `.dwo` files referred to by the skeleton compile units of
+/// `out_filename`, including those of upstream crates, into the `.dwp` file
+/// debuggers look for next to it.
+fn package_dwarf_objects(sess: &Session, out_filename: &Path) {
+    let mut dwp_out = out_filename.as_os_str().to_owned();
+    dwp_out.push(".dwp");
+
+    let mut cmd = Command::new("dwp");
+    cmd.arg("-e").arg(out_filename).arg("-o").arg(&dwp_out);
+    info!("{:?}", &cmd);
+    let prog = time(sess.time_passes(), "packaging split debuginfo", || cmd.output());
+    match prog {
+        Ok(prog) => {
+            if !prog.status.success() {
+                let mut output = prog.stderr.clone();
+                output.extend_from_slice(&prog.stdout);
+                sess.struct_err(&format!("packaging the split debuginfo with `dwp` failed: {}",
+                                         prog.status))
+                    .note(&format!("{:?}", &cmd))
+                    .note(&String::from_utf8_lossy(&output))
+                    .emit();
+            }
+        }
+        Err(e) => sess.err(&format!("could not exec `dwp` to package the split debuginfo: {}",
+                                    e)),
+    }
+}
+
 fn exec_linker(sess: &Session, cmd: &mut Command, tmpdir: &Path)
     -> io::Result<Output>
 {
@@ -982,6 +1030,19 @@ fn link_args(cmd: &mut Linker,
     // Pass debuginfo flags down to the linker.
     cmd.debuginfo();
 
+    // Make sure the profiler runtime is pulled in when instrumenting for PGO.
+    if sess.opts.cg.profile_generate.enabled() {
+        cmd.pgo_gen();
+    }
+
+    if sess.opts.debugging_opts.emit_link_map {
+        cmd.link_map(&out_filename.with_extension("map"));
+    }
+
+    if let Some(ref link_order) = sess.link_order {
+        cmd.link_order_file(tmpdir, link_order.symbols());
+    }
+
     // We want to prevent the compiler from accidentally leaking in any system
     // libraries, so we explicitly ask gcc to not link to any libraries by
     // default. Note that this does not happen for windows because windows pulls
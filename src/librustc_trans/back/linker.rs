@@ -115,6 +115,9 @@ pub trait Linker {
     fn args(&mut self, args: &[String]);
     fn export_symbols(&mut self, tmpdir: &Path, crate_type: CrateType);
     fn subsystem(&mut self, subsystem: &str);
+    fn pgo_gen(&mut self);
+    fn link_map(&mut self, path: &Path);
+    fn link_order_file(&mut self, tmpdir: &Path, symbols: &[String]);
     // Should have been finalize(self), but we don't support self-by-value on trait objects (yet?).
     fn finalize(&mut self) -> Command;
 }
@@ -168,6 +171,17 @@ impl<'a> GccLinker<'a> {
             self.hinted_static = false;
         }
     }
+
+    /// Whether the linker is `ld.<name>`, either selected with
+    /// `-fuse-ld=<name>` or given directly with `-C linker`.
+    fn uses_ld_flavor(&self, name: &str) -> bool {
+        let opts = &self.sess.opts.cg;
+        let fuse_ld = format!("-fuse-ld={}", name);
+        let ld = format!("ld.{}", name);
+        opts.link_args.iter().flat_map(|args| args).chain(&opts.link_arg)
+            .any(|arg| *arg == fuse_ld) ||
+        opts.linker.as_ref().and_then(|l| l.file_name()).map_or(false, |l| l == &ld[..])
+    }
 }
 
 impl<'a> Linker for GccLinker<'a> {
@@ -373,6 +387,61 @@ impl<'a> Linker for GccLinker<'a> {
         self.linker_arg(&format!("--subsystem,{}", subsystem));
     }
 
+    fn pgo_gen(&mut self) {
+        if !self.sess.target.target.options.linker_is_gnu { return }
+
+        // LLVM doesn't emit a reference to the profiler runtime on Linux and
+        // instead expects the linker to be told to pull it in, as clang does.
+        self.cmd.arg("-u");
+        self.cmd.arg("__llvm_profile_runtime");
+    }
+
+    fn link_map(&mut self, path: &Path) {
+        if self.sess.target.target.options.is_like_osx {
+            self.linker_arg("-map");
+            self.linker_arg(path);
+        } else {
+            let mut arg = OsString::from("-Map=");
+            arg.push(path);
+            self.linker_arg(&arg);
+        }
+    }
+
+    fn link_order_file(&mut self, tmpdir: &Path, symbols: &[String]) {
+        let path = tmpdir.join("link-order");
+        let is_like_osx = self.sess.target.target.options.is_like_osx;
+
+        // ld64 takes an order file directly and lld a symbol ordering file.
+        // gold can only order input sections, which works just as well as
+        // every function gets its own section. Other linkers, like BFD ld,
+        // keep the functions in the order the codegen unit they were placed
+        // in emits them, so there's nothing to pass to those.
+        let (arg, prefix) = if is_like_osx {
+            ("-order_file,", "_")
+        } else if self.uses_ld_flavor("lld") {
+            ("--symbol-ordering-file=", "")
+        } else if self.uses_ld_flavor("gold") {
+            ("--section-ordering-file=", ".text.")
+        } else {
+            return
+        };
+
+        let res = (|| -> io::Result<()> {
+            let mut f = BufWriter::new(File::create(&path)?);
+            for sym in symbols {
+                writeln!(f, "{}{}", prefix, sym)?;
+            }
+            Ok(())
+        })();
+        if let Err(e) = res {
+            self.sess.fatal(&format!("failed to write link order file: {}", e));
+        }
+
+        let mut os = OsString::from(arg);
+        os.push(&path);
+        self.linker_arg(&os);
+    }
+
     fn finalize(&mut self) -> Command {
         self.hint_dynamic(); // Reset to default before returning the composed command line.
         let mut cmd = Command::new("");
@@ -590,6 +659,34 @@ impl<'a> Linker for MsvcLinker<'a> {
         }
     }
 
+    fn pgo_gen(&mut self) {
+        // Nothing needed here: on Windows LLVM references the profiler
+        // runtime from the instrumented code itself.
+    }
+
+    fn link_map(&mut self, path: &Path) {
+        let mut arg = OsString::from("/MAP:");
+        arg.push(path);
+        self.cmd.arg(arg);
+    }
+
+    fn link_order_file(&mut self, tmpdir: &Path, symbols: &[String]) {
+        let path = tmpdir.join("link-order.txt");
+        let res = (|| -> io::Result<()> {
+            let mut f = BufWriter::new(File::create(&path)?);
+            for sym in symbols {
+                writeln!(f, "{}", sym)?;
+            }
+            Ok(())
+        })();
+        if let Err(e) = res {
+            self.sess.fatal(&format!("failed to write link order file: {}", e));
+        }
+        let mut arg = OsString::from("/ORDER:@");
+        arg.push(path);
+        self.cmd.arg(arg);
+    }
+
     fn finalize(&mut self) -> Command {
         let mut cmd = Command::new("");
         ::std::mem::swap(&mut cmd, &mut self.cmd);
@@ -740,6 +837,18 @@ impl<'a> Linker for EmLinker<'a> {
         // noop
     }
 
+    fn pgo_gen(&mut self) {
+        // noop
+    }
+
+    fn link_map(&mut self, _path: &Path) {
+        self.sess.warn("emscripten doesn't support emitting a linker map");
+    }
+
+    fn link_order_file(&mut self, _tmpdir: &Path, _symbols: &[String]) {
+        // noop
+    }
+
     fn finalize(&mut self) -> Command {
         let mut cmd = Command::new("");
         ::std::mem::swap(&mut cmd, &mut self.cmd);
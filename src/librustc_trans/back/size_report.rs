@@ -0,0 +1,203 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The per-symbol and per-crate size report written next to the linker map
+//! by `-Z emit-link-map`.
+//!
+//! The sizes are those of the symbols in the linked output, so anything the
+//! linker discarded is not accounted for. Each symbol is attributed to a
+//! crate as follows: Rust symbols belong to the crate their demangled path
+//! starts with (which for generic code is the crate defining it, not the one
+//! instantiating it), and other symbols to the crate whose object files
+//! define them, e.g. `#[no_mangle]` functions or native code bundled into an
+//! rlib. Anything else, like symbols from system libraries, is reported as
+//! `<unknown>`.
+
+use CrateTranslation;
+use llvm;
+use llvm::archive_ro::ArchiveRO;
+use rustc::session::Session;
+use rustc::util::nodemap::FxHashMap;
+use rustc_demangle;
+use serialize::json::{self, Json};
+
+use libc::{c_char, c_void, size_t};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::slice;
+
+const UNKNOWN_CRATE: &'static str = "<unknown>";
+
+struct Symbol {
+    name: String,
+    section: String,
+    size: u64,
+}
+
+/// Returns the path of the size report for the linked file `out_filename`.
+fn size_report_path(out_filename: &Path) -> PathBuf {
+    out_filename.with_extension("sizes.json")
+}
+
+pub fn write_size_report(sess: &Session, out_filename: &Path, trans: &CrateTranslation) {
+    let symbols = match fs::read(out_filename).map_err(|e| e.to_string())
+                                               .and_then(|data| symbol_sizes(&data)) {
+        Ok(symbols) => symbols,
+        Err(e) => {
+            sess.warn(&format!("failed to read the symbols of `{}` for the size report: {}",
+                               out_filename.display(), e));
+            return
+        }
+    };
+
+    let defining_crates = defining_crates(trans);
+
+    let mut crates: FxHashMap<String, (u64, usize)> = FxHashMap();
+    let mut symbol_entries = symbols.iter().map(|symbol| {
+        let demangled = rustc_demangle::try_demangle(&symbol.name).ok()
+                                                                  .map(|d| format!("{:#}", d));
+        let krate = demangled.as_ref()
+                             .and_then(|d| crate_of_path(d))
+                             .or_else(|| defining_crates.get(&symbol.name[..]).cloned())
+                             .unwrap_or(UNKNOWN_CRATE.to_string());
+
+        {
+            let totals = crates.entry(krate.clone()).or_insert((0, 0));
+            totals.0 += symbol.size;
+            totals.1 += 1;
+        }
+
+        let mut entry = BTreeMap::new();
+        entry.insert("name".to_string(), Json::String(symbol.name.clone()));
+        entry.insert("demangled".to_string(), demangled.map_or(Json::Null, Json::String));
+        entry.insert("crate".to_string(), Json::String(krate));
+        entry.insert("section".to_string(), Json::String(symbol.section.clone()));
+        entry.insert("size".to_string(), Json::U64(symbol.size));
+        (symbol.size, Json::Object(entry))
+    }).collect::<Vec<_>>();
+    symbol_entries.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let mut crates = crates.into_iter().collect::<Vec<_>>();
+    crates.sort_by(|a, b| (b.1).0.cmp(&(a.1).0).then_with(|| a.0.cmp(&b.0)));
+    let crate_entries = crates.into_iter().map(|(name, (size, symbols))| {
+        let mut entry = BTreeMap::new();
+        entry.insert("name".to_string(), Json::String(name));
+        entry.insert("size".to_string(), Json::U64(size));
+        entry.insert("symbols".to_string(), Json::U64(symbols as u64));
+        Json::Object(entry)
+    }).collect();
+
+    let mut report = BTreeMap::new();
+    report.insert("output".to_string(), Json::String(out_filename.display().to_string()));
+    report.insert("total_size".to_string(),
+                  Json::U64(symbols.iter().map(|symbol| symbol.size).sum()));
+    report.insert("crates".to_string(), Json::Array(crate_entries));
+    report.insert("symbols".to_string(),
+                  Json::Array(symbol_entries.into_iter().map(|(_, entry)| entry).collect()));
+
+    let path = size_report_path(out_filename);
+    let result = File::create(&path).and_then(|mut file| {
+        write!(file, "{}\n", json::as_pretty_json(&Json::Object(report)))
+    });
+    if let Err(e) = result {
+        sess.err(&format!("failed to write size report `{}`: {}", path.display(), e));
+    }
+}
+
+/// Returns the name of the crate the demangled Rust path `path` starts
+/// with, looking through the `<` of paths like `<alloc::vec::Vec<T> as
+/// core::ops::Drop>::drop`.
+fn crate_of_path(path: &str) -> Option<String> {
+    let path = path.trim_left_matches('<');
+    let end = path.find("::")?;
+    let krate = &path[..end];
+    if !krate.is_empty() && krate.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        Some(krate.to_string())
+    } else {
+        None
+    }
+}
+
+/// Maps the symbols defined by the object files going into the link to the
+/// crates they belong to.
+fn defining_crates(trans: &CrateTranslation) -> FxHashMap<String, String> {
+    let mut defining_crates = FxHashMap();
+    {
+        let mut add_object = |data: &[u8], krate: &str| {
+            if let Ok(symbols) = symbol_sizes(data) {
+                for symbol in symbols {
+                    defining_crates.entry(symbol.name).or_insert_with(|| krate.to_string());
+                }
+            }
+        };
+
+        let local_crate = trans.crate_name.as_str();
+        for module in trans.modules.iter().chain(&trans.allocator_module) {
+            if let Some(ref object) = module.object {
+                if let Ok(data) = fs::read(object) {
+                    add_object(&data, &local_crate);
+                }
+            }
+        }
+
+        for &(cnum, _) in &trans.crate_info.used_crates_static {
+            let src = &trans.crate_info.used_crate_source[&cnum];
+            let rlib = match src.rlib {
+                Some((ref rlib, _)) => rlib,
+                None => continue,
+            };
+            let archive = match ArchiveRO::open(rlib) {
+                Ok(archive) => archive,
+                Err(_) => continue,
+            };
+            let krate = &trans.crate_info.crate_name[&cnum];
+            for child in archive.iter().filter_map(|child| child.ok()) {
+                add_object(child.data(), krate);
+            }
+        }
+    }
+
+    defining_crates
+}
+
+/// Returns the defined function and data symbols of the object file `data`,
+/// along with their sizes.
+fn symbol_sizes(data: &[u8]) -> Result<Vec<Symbol>, String> {
+    unsafe extern "C" fn callback(ctx: *mut c_void,
+                                  name: *const c_char,
+                                  name_len: size_t,
+                                  section: *const c_char,
+                                  section_len: size_t,
+                                  size: u64) {
+        let symbols = &mut *(ctx as *mut Vec<Symbol>);
+        let name = slice::from_raw_parts(name as *const u8, name_len);
+        let section = slice::from_raw_parts(section as *const u8, section_len);
+        symbols.push(Symbol {
+            name: String::from_utf8_lossy(name).into_owned(),
+            section: String::from_utf8_lossy(section).into_owned(),
+            size,
+        });
+    }
+
+    let mut symbols = Vec::new();
+    let ok = unsafe {
+        llvm::LLVMRustForEachSymbolSize(data.as_ptr() as *const c_char,
+                                        data.len() as size_t,
+                                        callback,
+                                        &mut symbols as *mut Vec<Symbol> as *mut c_void)
+    };
+    if ok {
+        Ok(symbols)
+    } else {
+        Err(llvm::last_error().unwrap_or("unknown LLVM error".to_string()))
+    }
+}
@@ -20,7 +20,7 @@ use rustc_incremental::{save_trans_partition, in_incr_comp_dir};
 use rustc::dep_graph::{DepGraph, WorkProductFileKind};
 use rustc::middle::cstore::{LinkMeta, EncodedMetadata};
 use rustc::session::config::{self, OutputFilenames, OutputType, Passes, SomePasses,
-                             AllPasses, Sanitizer, Lto};
+                             AllPasses, Sanitizer, Lto, SwitchWithOptPath};
 use rustc::session::Session;
 use rustc::util::nodemap::FxHashMap;
 use rustc_back::LinkerFlavor;
@@ -50,6 +50,7 @@ use std::ffi::{CString, CStr};
 use std::fs;
 use std::io::{self, Write};
 use std::mem;
+use std::ptr;
 use std::path::{Path, PathBuf};
 use std::str;
 use std::sync::Arc;
@@ -76,6 +77,9 @@ pub const CODE_GEN_MODEL_ARGS: &[(&str, llvm::CodeModel)] = &[
     ("large", llvm::CodeModel::Large),
 ];
 
+/// The extension of the split DWARF object of each codegen unit.
+pub const DWARF_OBJECT_EXT: &'static str = "dwo";
+
 pub const TLS_MODEL_ARGS : [(&'static str, llvm::ThreadLocalMode); 4] = [
     ("global-dynamic", llvm::ThreadLocalMode::GeneralDynamic),
     ("local-dynamic", llvm::ThreadLocalMode::LocalDynamic),
@@ -167,7 +171,9 @@ pub fn target_machine_factory(sess: &Session)
     let opt_level = get_llvm_opt_level(sess.opts.optimize);
     let use_softfp = sess.opts.cg.soft_float;
 
-    let ffunction_sections = sess.target.target.options.function_sections;
+    // Ordering functions at link time needs each of them in its own section.
+    let ffunction_sections = sess.target.target.options.function_sections ||
+                             sess.link_order.is_some();
     let fdata_sections = ffunction_sections;
 
     let code_model_arg = sess.opts.cg.code_model.as_ref().or(
@@ -239,6 +245,14 @@ pub struct ModuleConfig {
     /// Some(level) to optimize binary size, or None to not affect program size.
     opt_size: Option<llvm::CodeGenOptSize>,
 
+    /// Whether to instrument the code for profile-guided optimization, and
+    /// the profile to optimize with.
+    pgo_gen: SwitchWithOptPath,
+    pgo_use: Option<PathBuf>,
+
+    /// Whether to move the debug info into a separate `.dwo` file.
+    split_dwarf: bool,
+
     // Flags indicating which outputs to produce.
     emit_no_opt_bc: bool,
     emit_bc: bool,
@@ -271,6 +285,11 @@ impl ModuleConfig {
             opt_level: None,
             opt_size: None,
 
+            pgo_gen: SwitchWithOptPath::Disabled,
+            pgo_use: None,
+
+            split_dwarf: false,
+
             emit_no_opt_bc: false,
             emit_bc: false,
             emit_bc_compressed: false,
@@ -478,6 +497,12 @@ unsafe extern "C" fn diagnostic_handler(info: DiagnosticInfoRef, user: *mut c_vo
                                                 opt.message));
             }
         }
+        llvm::diagnostic::PGO(diagnostic_ref) => {
+            let msg = llvm::build_string(|s| {
+                llvm::LLVMRustWriteDiagnosticInfoToString(diagnostic_ref, s)
+            }).expect("non-UTF8 PGO diagnostic");
+            diag_handler.warn(&msg);
+        }
 
         _ => (),
     }
@@ -661,6 +686,8 @@ unsafe fn codegen(cgcx: &CodegenContext,
 
     let bc_out = cgcx.output_filenames.temp_path(OutputType::Bitcode, module_name);
     let obj_out = cgcx.output_filenames.temp_path(OutputType::Object, module_name);
+    let split_dwarf = config.split_dwarf && write_obj;
+    let dwo_out = cgcx.output_filenames.temp_path_ext(DWARF_OBJECT_EXT, module_name);
 
 
     if write_bc || config.emit_bc_compressed {
@@ -769,11 +796,20 @@ unsafe fn codegen(cgcx: &CodegenContext,
                 drop(fs::remove_file(&assembly));
             }
         } else if write_obj {
+            if split_dwarf {
+                let dwo_out = path2cstr(&dwo_out);
+                llvm::LLVMRustSetSplitDwarfFile(tm, dwo_out.as_ptr());
+            }
             with_codegen(tm, llmod, config.no_builtins, |cpm| {
                 write_output_file(diag_handler, tm, cpm, llmod, &obj_out,
                                   llvm::FileType::ObjectFile)
             })?;
             timeline.record("obj");
+
+            if split_dwarf {
+                split_dwarf_object(diag_handler, &obj_out, &dwo_out);
+                timeline.record("split-dwarf");
+            }
         } else if asm_to_obj {
             let assembly = cgcx.output_filenames.temp_path(OutputType::Assembly, module_name);
             run_assembler(cgcx, diag_handler, &assembly, &obj_out);
@@ -805,9 +841,46 @@ unsafe fn codegen(cgcx: &CodegenContext,
     Ok(mtrans.into_compiled_module(config.emit_obj,
                                    config.emit_bc,
                                    config.emit_bc_compressed,
+                                   split_dwarf,
                                    &cgcx.output_filenames))
 }
 
+/// Moves the debug info sections meant for the `.dwo` file out of `object`
+/// and into `dwo`, leaving only the skeleton compile unit behind. LLVM emits
+/// both into the same object file, so like `clang -gsplit-dwarf` we rely on
+/// `objcopy` to separate them.
+fn split_dwarf_object(handler: &Handler, object: &Path, dwo: &Path) {
+    let mut extract = Command::new("objcopy");
+    extract.arg("--extract-dwo").arg(object).arg(dwo);
+    let mut strip = Command::new("objcopy");
+    strip.arg("--strip-dwo").arg(object);
+
+    for cmd in &mut [extract, strip] {
+        debug!("{:?}", cmd);
+        match cmd.output() {
+            Ok(prog) => {
+                if !prog.status.success() {
+                    let mut note = prog.stderr.clone();
+                    note.extend_from_slice(&prog.stdout);
+
+                    handler.struct_err(&format!("splitting the debug info of `{}` failed: {}",
+                                                object.display(),
+                                                prog.status))
+                        .note(&format!("{:?}", cmd))
+                        .note(&String::from_utf8_lossy(&note))
+                        .emit();
+                    return
+                }
+            }
+            Err(e) => {
+                handler.err(&format!("could not exec `objcopy` to split the debug info: {}",
+                                     e));
+                return
+            }
+        }
+    }
+}
+
 /// Translates the LLVM-generated `assembly` on the filesystem into a wasm
 /// module using binaryen, placing the output at `object`.
 ///
@@ -911,6 +984,11 @@ pub fn start_async_translation(tcx: TyCtxt,
         modules_config.passes.push("insert-gcov-profiling".to_owned())
     }
 
+    modules_config.pgo_gen = sess.opts.cg.profile_generate.clone();
+    modules_config.pgo_use = sess.opts.cg.profile_use.clone();
+
+    modules_config.split_dwarf = sess.split_dwarf() && !sess.opts.cg.no_integrated_as;
+
     modules_config.opt_level = Some(get_llvm_opt_level(sess.opts.optimize));
     modules_config.opt_size = Some(get_llvm_opt_size(sess.opts.optimize));
 
@@ -1027,6 +1105,9 @@ fn copy_module_artifacts_into_incr_comp_cache(sess: &Session,
         if let Some(ref path) = module.bytecode_compressed {
             files.push((WorkProductFileKind::BytecodeCompressed, path.clone()));
         }
+        if let Some(ref path) = module.dwarf_object {
+            files.push((WorkProductFileKind::DwarfObject, path.clone()));
+        }
 
         save_trans_partition(sess, dep_graph, &module.name, &files);
     }
@@ -1246,6 +1327,7 @@ fn execute_work_item(cgcx: &CodegenContext,
         let mut object = None;
         let mut bytecode = None;
         let mut bytecode_compressed = None;
+        let mut dwarf_object = None;
         for (kind, saved_file) in wp.saved_files {
             let obj_out = match kind {
                 WorkProductFileKind::Object => {
@@ -1264,6 +1346,11 @@ fn execute_work_item(cgcx: &CodegenContext,
                     bytecode_compressed = Some(path.clone());
                     path
                 }
+                WorkProductFileKind::DwarfObject => {
+                    let path = cgcx.output_filenames.temp_path_ext(DWARF_OBJECT_EXT, Some(name));
+                    dwarf_object = Some(path.clone());
+                    path
+                }
             };
             let source_file = in_incr_comp_dir(&incr_comp_session_dir,
                                                &saved_file);
@@ -1293,6 +1380,7 @@ fn execute_work_item(cgcx: &CodegenContext,
             object,
             bytecode,
             bytecode_compressed,
+            dwarf_object,
         }))
     } else {
         debug!("llvm-optimizing {:?}", module_name);
@@ -2015,11 +2103,27 @@ pub unsafe fn with_llvm_pmb(llmod: ModuleRef,
     let opt_size = config.opt_size.unwrap_or(llvm::CodeGenOptSizeNone);
     let inline_threshold = config.inline_threshold;
 
+    // Like clang, write the raw profiles as `default_%m.profraw`, where `%m`
+    // is replaced by a hash of the instrumented binary at runtime.
+    let pgo_gen_path = match config.pgo_gen {
+        SwitchWithOptPath::Enabled(ref dir) => {
+            let path = dir.as_ref().map_or(PathBuf::from("default_%m.profraw"),
+                                           |dir| dir.join("default_%m.profraw"));
+            Some(path2cstr(&path))
+        }
+        SwitchWithOptPath::Disabled => None,
+    };
+    let pgo_use_path = config.pgo_use.as_ref().map(|path| path2cstr(path));
+
     llvm::LLVMRustConfigurePassManagerBuilder(builder,
                                               opt_level,
                                               config.merge_functions,
                                               config.vectorize_slp,
-                                              config.vectorize_loop);
+                                              config.vectorize_loop,
+                                              pgo_gen_path.as_ref().map_or(ptr::null(),
+                                                                           |s| s.as_ptr()),
+                                              pgo_use_path.as_ref().map_or(ptr::null(),
+                                                                           |s| s.as_ptr()));
     llvm::LLVMPassManagerBuilderSetSizeLevel(builder, opt_size as u32);
 
     if opt_size != llvm::CodeGenOptSizeNone {
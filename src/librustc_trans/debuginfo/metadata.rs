@@ -35,6 +35,7 @@ use rustc::ty::layout::{self, Align, LayoutOf, Size, TyLayout};
 use rustc::session::config;
 use rustc::util::nodemap::FxHashMap;
 use rustc::util::common::path2cstr;
+use back::write::DWARF_OBJECT_EXT;
 
 use libc::{c_uint, c_longlong};
 use std::ffi::CString;
@@ -812,7 +813,15 @@ pub fn compile_unit_metadata(tcx: TyCtxt,
     let work_dir = CString::new(&tcx.sess.working_dir.0.to_string_lossy()[..]).unwrap();
     let producer = CString::new(producer).unwrap();
     let flags = "\0";
-    let split_name = "\0";
+    // With split DWARF, the skeleton compile unit left in the object file
+    // refers to the `.dwo` file holding the rest of the debug info.
+    let split_name = if tcx.sess.split_dwarf() {
+        let dwo_out = tcx.output_filenames(LOCAL_CRATE)
+                         .temp_path_ext(DWARF_OBJECT_EXT, Some(codegen_unit_name));
+        path2cstr(&dwo_out)
+    } else {
+        CString::new("").unwrap()
+    };
 
     unsafe {
         let file_metadata = llvm::LLVMRustDIBuilderCreateFile(
@@ -826,7 +835,7 @@ pub fn compile_unit_metadata(tcx: TyCtxt,
             tcx.sess.opts.optimize != config::OptLevel::No,
             flags.as_ptr() as *const _,
             0,
-            split_name.as_ptr() as *const _);
+            split_name.as_ptr());
 
         if tcx.sess.opts.debugging_opts.profile {
             let cu_desc_metadata = llvm::LLVMRustMetadataAsValue(debug_context.llcontext,
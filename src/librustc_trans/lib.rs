@@ -97,6 +97,7 @@ mod back {
     pub mod linker;
     pub mod link;
     mod lto;
+    mod size_report;
     pub mod symbol_export;
     pub mod write;
     mod rpath;
@@ -303,6 +304,7 @@ impl ModuleTranslation {
                                 emit_obj: bool,
                                 emit_bc: bool,
                                 emit_bc_compressed: bool,
+                                emit_dwo: bool,
                                 outputs: &OutputFilenames) -> CompiledModule {
         let pre_existing = match self.source {
             ModuleSource::Preexisting(_) => true,
@@ -325,6 +327,12 @@ impl ModuleTranslation {
             None
         };
 
+        let dwarf_object = if emit_dwo {
+            Some(outputs.temp_path_ext(back::write::DWARF_OBJECT_EXT, Some(&self.name)))
+        } else {
+            None
+        };
+
         CompiledModule {
             llmod_id: self.llmod_id,
             name: self.name.clone(),
@@ -333,6 +341,7 @@ impl ModuleTranslation {
             object,
             bytecode,
             bytecode_compressed,
+            dwarf_object,
         }
     }
 }
@@ -346,6 +355,7 @@ struct CompiledModule {
     object: Option<PathBuf>,
     bytecode: Option<PathBuf>,
     bytecode_compressed: Option<PathBuf>,
+    dwarf_object: Option<PathBuf>,
 }
 
 enum ModuleSource {
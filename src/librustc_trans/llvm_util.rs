@@ -61,6 +61,11 @@ unsafe fn configure_llvm(sess: &Session) {
         add("rustc"); // fake program name
         if sess.time_llvm_passes() { add("-time-passes"); }
         if sess.print_llvm_passes() { add("-debug-pass=Structure"); }
+        // Before LLVM 7 split DWARF can only be turned on globally, later
+        // versions enable it for the target machines given a `.dwo` file.
+        if sess.split_dwarf() && llvm::LLVMRustVersionMajor() < 7 {
+            add("-split-dwarf=Enable");
+        }
 
         for arg in &sess.opts.cg.llvm_args {
             add(&(*arg));
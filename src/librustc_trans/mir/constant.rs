@@ -259,7 +259,10 @@ struct MirConstContext<'a, 'tcx: 'a> {
     substs: &'tcx Substs<'tcx>,
 
     /// Values of locals in a constant or const fn.
-    locals: IndexVec<mir::Local, Option<Result<Const<'tcx>, ConstEvalErr<'tcx>>>>
+    locals: IndexVec<mir::Local, Option<Result<Const<'tcx>, ConstEvalErr<'tcx>>>>,
+
+    /// The `const fn` calls this context is nested in, outermost first.
+    calls: Vec<(Span, DefId)>,
 }
 
 fn add_err<'tcx, U, V>(failure: &mut Result<U, ConstEvalErr<'tcx>>,
@@ -276,13 +279,15 @@ impl<'a, 'tcx> MirConstContext<'a, 'tcx> {
     fn new(cx: &'a CodegenCx<'a, 'tcx>,
            mir: &'a mir::Mir<'tcx>,
            substs: &'tcx Substs<'tcx>,
-           args: IndexVec<mir::Local, Result<Const<'tcx>, ConstEvalErr<'tcx>>>)
+           args: IndexVec<mir::Local, Result<Const<'tcx>, ConstEvalErr<'tcx>>>,
+           calls: Vec<(Span, DefId)>)
            -> MirConstContext<'a, 'tcx> {
         let mut context = MirConstContext {
             cx,
             mir,
             substs,
             locals: (0..mir.local_decls.len()).map(|_| None).collect(),
+            calls,
         };
         for (i, arg) in args.into_iter().enumerate() {
             // Locals after local 0 are the function arguments
@@ -295,14 +300,26 @@ impl<'a, 'tcx> MirConstContext<'a, 'tcx> {
     fn trans_def(cx: &'a CodegenCx<'a, 'tcx>,
                  def_id: DefId,
                  substs: &'tcx Substs<'tcx>,
-                 args: IndexVec<mir::Local, Result<Const<'tcx>, ConstEvalErr<'tcx>>>)
+                 args: IndexVec<mir::Local, Result<Const<'tcx>, ConstEvalErr<'tcx>>>,
+                 calls: Vec<(Span, DefId)>)
                  -> Result<Const<'tcx>, ConstEvalErr<'tcx>> {
         let instance = ty::Instance::resolve(cx.tcx,
                                              ty::ParamEnv::empty(traits::Reveal::All),
                                              def_id,
                                              substs).unwrap();
         let mir = cx.tcx.instance_mir(instance.def);
-        MirConstContext::new(cx, &mir, instance.substs, args).trans()
+        MirConstContext::new(cx, &mir, instance.substs, args, calls).trans()
+    }
+
+    /// Reports an error about one of the evaluation limits, with the calls
+    /// this context is nested in as its backtrace.
+    fn limit_reached(&self, span: Span, kind: ErrKind<'tcx>) -> ConstEvalErr<'tcx> {
+        let mut err = ConstEvalErr { span, kind };
+        for &(call_span, callee) in self.calls.iter().rev() {
+            err = err.inside_call(call_span, callee);
+        }
+        err.report(self.cx.tcx, span, "expression");
+        err
     }
 
     fn monomorphize<T>(&self, value: &T) -> T
@@ -319,7 +336,18 @@ impl<'a, 'tcx> MirConstContext<'a, 'tcx> {
         // report as many errors as we possibly can.
         let mut failure = Ok(());
 
+        // `const fn`s with `#![feature(const_control_flow)]` may loop.
+        let step_limit = tcx.sess.const_eval_step_limit();
+        let mut steps = 0;
+
         loop {
+            steps += 1;
+            if steps > step_limit {
+                let span = self.mir[bb].terminator().source_info.span;
+                let kind = ErrKind::StepLimitReached { step_limit, backtrace: vec![] };
+                return Err(self.limit_reached(span, kind));
+            }
+
             let data = &self.mir[bb];
             for statement in &data.statements {
                 let span = statement.source_info.span;
@@ -347,7 +375,23 @@ impl<'a, 'tcx> MirConstContext<'a, 'tcx> {
             let span = terminator.source_info.span;
             bb = match terminator.kind {
                 mir::TerminatorKind::Drop { target, .. } | // No dropping.
-                mir::TerminatorKind::Goto { target } => target,
+                mir::TerminatorKind::Goto { target } |
+                mir::TerminatorKind::FalseUnwind { real_target: target, .. } => target,
+                mir::TerminatorKind::FalseEdges { real_target, .. } => real_target,
+
+                mir::TerminatorKind::SwitchInt { ref discr, switch_ty, ref values, ref targets } => {
+                    let discr = self.const_operand(discr, span)?;
+                    let switch_ty = self.monomorphize(&switch_ty);
+                    let bits = match const_to_opt_u128(discr.llval, switch_ty.is_signed()) {
+                        Some(bits) => bits,
+                        None => span_bug!(span, "non-integer discriminant {:?} in constant",
+                                          discr),
+                    };
+                    let index = values.iter()
+                        .position(|value| value.to_u128_unchecked() == bits)
+                        .unwrap_or(values.len());
+                    targets[index]
+                }
                 mir::TerminatorKind::Return => {
                     failure?;
                     return self.locals[mir::RETURN_PLACE].clone().unwrap_or_else(|| {
@@ -454,8 +498,17 @@ impl<'a, 'tcx> MirConstContext<'a, 'tcx> {
                                     }
                                 }
                             })()
+                        } else if self.calls.len() >= tcx.sess.const_eval_stack_limit() {
+                            let stack_limit = tcx.sess.const_eval_stack_limit();
+                            let kind = ErrKind::StackFrameLimitReached {
+                                stack_limit,
+                                backtrace: vec![],
+                            };
+                            Err(self.limit_reached(span, kind))
                         } else {
-                            MirConstContext::trans_def(self.cx, def_id, substs, arg_vals)
+                            let mut calls = self.calls.clone();
+                            calls.push((span, def_id));
+                            MirConstContext::trans_def(self.cx, def_id, substs, arg_vals, calls)
                         };
                         add_err(&mut failure, &result);
                         self.store(dest, result, span);
@@ -624,12 +677,14 @@ impl<'a, 'tcx> MirConstContext<'a, 'tcx> {
                 match constant.literal.clone() {
                     mir::Literal::Promoted { index } => {
                         let mir = &self.mir.promoted[index];
-                        MirConstContext::new(self.cx, mir, self.substs, IndexVec::new()).trans()
+                        MirConstContext::new(self.cx, mir, self.substs, IndexVec::new(),
+                                             self.calls.clone()).trans()
                     }
                     mir::Literal::Value { value } => {
                         if let ConstVal::Unevaluated(def_id, substs) = value.val {
                             let substs = self.monomorphize(&substs);
-                            MirConstContext::trans_def(self.cx, def_id, substs, IndexVec::new())
+                            MirConstContext::trans_def(self.cx, def_id, substs, IndexVec::new(),
+                                                       self.calls.clone())
                         } else {
                             Ok(Const::from_constval(self.cx, &value.val, ty))
                         }
@@ -1141,12 +1196,13 @@ impl<'a, 'tcx> FunctionCx<'a, 'tcx> {
         let result = match constant.literal.clone() {
             mir::Literal::Promoted { index } => {
                 let mir = &self.mir.promoted[index];
-                MirConstContext::new(bx.cx, mir, self.param_substs, IndexVec::new()).trans()
+                MirConstContext::new(bx.cx, mir, self.param_substs, IndexVec::new(), vec![])
+                    .trans()
             }
             mir::Literal::Value { value } => {
                 if let ConstVal::Unevaluated(def_id, substs) = value.val {
                     let substs = self.monomorphize(&substs);
-                    MirConstContext::trans_def(bx.cx, def_id, substs, IndexVec::new())
+                    MirConstContext::trans_def(bx.cx, def_id, substs, IndexVec::new(), vec![])
                 } else {
                     Ok(Const::from_constval(bx.cx, &value.val, ty))
                 }
@@ -1170,7 +1226,7 @@ pub fn trans_static_initializer<'a, 'tcx>(
     def_id: DefId)
     -> Result<ValueRef, ConstEvalErr<'tcx>>
 {
-    MirConstContext::trans_def(cx, def_id, Substs::empty(), IndexVec::new())
+    MirConstContext::trans_def(cx, def_id, Substs::empty(), IndexVec::new(), vec![])
         .map(|c| c.llval)
 }
 
@@ -257,6 +257,13 @@ impl Error for allocator::CannotReallocInPlace {
     }
 }
 
+#[unstable(feature = "try_reserve", reason = "new API", issue="48043")]
+impl Error for allocator::CollectionAllocErr {
+    fn description(&self) -> &str {
+        allocator::CollectionAllocErr::description(self)
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Error for str::ParseBoolError {
     fn description(&self) -> &str { "failed to parse bool" }
@@ -19,7 +19,7 @@
 
 use fmt;
 use ffi::OsString;
-use io::{self, SeekFrom, Seek, Read, Initializer, Write};
+use io::{self, SeekFrom, Seek, Read, Initializer, Write, IoSlice, IoSliceMut};
 use path::{Path, PathBuf};
 use sys::fs as fs_imp;
 use sys_common::{AsInnerMut, FromInner, AsInner, IntoInner};
@@ -605,6 +605,10 @@ impl Read for File {
         self.inner.read(buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -615,6 +619,9 @@ impl Write for File {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.inner.write(buf)
     }
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -629,6 +636,10 @@ impl<'a> Read for &'a File {
         self.inner.read(buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -639,6 +650,9 @@ impl<'a> Write for &'a File {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.inner.write(buf)
     }
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -1527,8 +1541,9 @@ pub fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()>
 ///
 /// This function will **overwrite** the contents of `to`.
 ///
-/// Note that if `from` and `to` both point to the same file, then the file
-/// will likely get truncated by this operation.
+/// Note that if `from` and `to` both point to the same file, then on Unix an
+/// error is returned and the file is left untouched, while on other platforms
+/// the file will likely get truncated by this operation.
 ///
 /// On success, the total number of bytes copied is returned and it is equal to
 /// the length of the `to` file as reported by `metadata`.
@@ -1538,6 +1553,9 @@ pub fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()>
 /// This function currently corresponds to the `open` function in Unix
 /// with `O_RDONLY` for `from` and `O_WRONLY`, `O_CREAT`, and `O_TRUNC` for `to`.
 /// `O_CLOEXEC` is set for returned file descriptors.
+/// On Linux, the contents are shared with a reflink (the `FICLONE` `ioctl`)
+/// on filesystems that support it, and otherwise copied inside the kernel
+/// with `copy_file_range` or `sendfile` where available.
 /// On Windows, this function currently corresponds to `CopyFileEx`. Alternate
 /// NTFS streams are copied but only the size of the main stream is returned by
 /// this function.
@@ -1554,6 +1572,7 @@ pub fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()>
 /// * The `from` file does not exist.
 /// * The current process does not have the permission rights to access
 ///   `from` or write `to`.
+/// * `from` and `to` are the same file, on Unix.
 ///
 /// # Examples
 ///
@@ -2033,7 +2052,7 @@ mod tests {
     use io::prelude::*;
 
     use fs::{self, File, OpenOptions};
-    use io::{ErrorKind, SeekFrom};
+    use io::{self, ErrorKind, IoSlice, IoSliceMut, SeekFrom};
     use path::Path;
     use rand::{StdRng, Rng};
     use str;
@@ -2251,6 +2270,30 @@ mod tests {
         check!(fs::remove_file(filename));
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn file_test_io_vectored() {
+        let tmpdir = tmpdir();
+        let filename = &tmpdir.join("file_rt_io_file_test_vectored.txt");
+        {
+            let mut f = check!(File::create(filename));
+            let n = check!(f.write_vectored(&[IoSlice::new(b"header:"),
+                                              IoSlice::new(b"body")]));
+            assert_eq!(n, 11);
+        }
+        {
+            let mut f = check!(File::open(filename));
+            let mut header = [0; 7];
+            let mut body = [0; 8];
+            let n = check!(f.read_vectored(&mut [IoSliceMut::new(&mut header),
+                                                 IoSliceMut::new(&mut body)]));
+            assert_eq!(n, 11);
+            assert_eq!(&header, b"header:");
+            assert_eq!(&body[..4], b"body");
+        }
+        check!(fs::remove_file(filename));
+    }
+
     #[test]
     fn file_test_io_eof() {
         let tmpdir = tmpdir();
@@ -2692,6 +2735,40 @@ mod tests {
                    check!(out.metadata()).permissions());
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn copy_file_onto_itself() {
+        let tmpdir = tmpdir();
+        let file = tmpdir.join("in.txt");
+
+        check!(check!(File::create(&file)).write(b"hello"));
+        assert!(fs::copy(&file, &file).is_err());
+        let mut v = Vec::new();
+        check!(check!(File::open(&file)).read_to_end(&mut v));
+        assert_eq!(v, b"hello");
+    }
+
+    #[test]
+    fn io_copy_file_to_file_uses_offsets() {
+        let tmpdir = tmpdir();
+        let input = tmpdir.join("in.txt");
+        let out = tmpdir.join("out.txt");
+
+        check!(check!(File::create(&input)).write(b"skip:hello"));
+        let mut reader = check!(File::open(&input));
+        check!(reader.seek(SeekFrom::Start(5)));
+        let mut writer = check!(File::create(&out));
+        check!(writer.write(b"greeting: "));
+
+        assert_eq!(check!(io::copy(&mut reader, &mut writer)), 5);
+        assert_eq!(check!(reader.read(&mut [0; 1])), 0);
+        check!(writer.write(b"!"));
+
+        let mut v = Vec::new();
+        check!(check!(File::open(&out)).read_to_end(&mut v));
+        assert_eq!(v, b"greeting: hello!");
+    }
+
     #[test]
     fn copy_file_dst_dir() {
         let tmpdir = tmpdir();
@@ -277,7 +277,9 @@ use fmt;
 use result;
 use str;
 use memchr;
+use ops::{Deref, DerefMut};
 use ptr;
+use sys;
 
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::buffered::{BufReader, BufWriter, LineWriter};
@@ -526,6 +528,43 @@ pub trait Read {
     #[stable(feature = "rust1", since = "1.0.0")]
     fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
 
+    /// Like [`read`], except that it reads into a slice of buffers.
+    ///
+    /// Data is copied to fill each buffer in order, with the final buffer
+    /// written to possibly being only partially filled. This method must
+    /// behave as a single call to [`read`] with the buffers concatenated
+    /// would.
+    ///
+    /// The default implementation calls [`read`] with the first non-empty
+    /// buffer, or with an empty one if there is none. Readers backed by a
+    /// file descriptor override it to read into all of the buffers with a
+    /// single system call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iovec)]
+    /// use std::io::{self, IoSliceMut};
+    /// use std::io::prelude::*;
+    ///
+    /// # fn foo() -> io::Result<()> {
+    /// let mut input = &b"headerbody"[..];
+    /// let mut header = [0; 6];
+    /// let mut body = [0; 4];
+    ///
+    /// let n = input.read_vectored(&mut [IoSliceMut::new(&mut header),
+    ///                                   IoSliceMut::new(&mut body)])?;
+    /// assert!(n <= 10);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`read`]: #tymethod.read
+    #[unstable(feature = "iovec", issue = "0")]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> Result<usize> {
+        default_read_vectored(|b| self.read(b), bufs)
+    }
+
     /// Determines if this `Read`er can work with buffers of uninitialized
     /// memory.
     ///
@@ -951,6 +990,105 @@ impl Initializer {
     }
 }
 
+/// A buffer type used with `Read::read_vectored`.
+///
+/// It is semantically a wrapper around an `&mut [u8]`, but is guaranteed to
+/// be ABI compatible with the `iovec` type on Unix platforms, so that a slice
+/// of them can be passed to `readv` directly.
+#[unstable(feature = "iovec", issue = "0")]
+#[repr(transparent)]
+pub struct IoSliceMut<'a>(sys::iovec::IoSliceMut<'a>);
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> fmt::Debug for IoSliceMut<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.0.as_slice(), fmt)
+    }
+}
+
+impl<'a> IoSliceMut<'a> {
+    /// Creates a new `IoSliceMut` wrapping a byte slice.
+    #[unstable(feature = "iovec", issue = "0")]
+    #[inline]
+    pub fn new(buf: &'a mut [u8]) -> IoSliceMut<'a> {
+        IoSliceMut(sys::iovec::IoSliceMut::new(buf))
+    }
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> Deref for IoSliceMut<'a> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> DerefMut for IoSliceMut<'a> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.0.as_mut_slice()
+    }
+}
+
+/// A buffer type used with `Write::write_vectored`.
+///
+/// It is semantically a wrapper around an `&[u8]`, but is guaranteed to be
+/// ABI compatible with the `iovec` type on Unix platforms, so that a slice
+/// of them can be passed to `writev` directly.
+#[unstable(feature = "iovec", issue = "0")]
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct IoSlice<'a>(sys::iovec::IoSlice<'a>);
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> fmt::Debug for IoSlice<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.0.as_slice(), fmt)
+    }
+}
+
+impl<'a> IoSlice<'a> {
+    /// Creates a new `IoSlice` wrapping a byte slice.
+    #[unstable(feature = "iovec", issue = "0")]
+    #[inline]
+    pub fn new(buf: &'a [u8]) -> IoSlice<'a> {
+        IoSlice(sys::iovec::IoSlice::new(buf))
+    }
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> Deref for IoSlice<'a> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+// The fallbacks used by the default `read_vectored` and `write_vectored`
+// implementations, and by platform code without native vectored I/O.
+pub(crate) fn default_read_vectored<F>(read: F, bufs: &mut [IoSliceMut]) -> Result<usize>
+    where F: FnOnce(&mut [u8]) -> Result<usize>
+{
+    match bufs.iter_mut().find(|b| !b.is_empty()) {
+        Some(buf) => read(&mut **buf),
+        None => read(&mut []),
+    }
+}
+
+pub(crate) fn default_write_vectored<F>(write: F, bufs: &[IoSlice]) -> Result<usize>
+    where F: FnOnce(&[u8]) -> Result<usize>
+{
+    match bufs.iter().find(|b| !b.is_empty()) {
+        Some(buf) => write(&**buf),
+        None => write(&[]),
+    }
+}
+
 /// A trait for objects which are byte-oriented sinks.
 ///
 /// Implementors of the `Write` trait are sometimes called 'writers'.
@@ -1037,6 +1175,40 @@ pub trait Write {
     #[stable(feature = "rust1", since = "1.0.0")]
     fn write(&mut self, buf: &[u8]) -> Result<usize>;
 
+    /// Like [`write`], except that it writes from a slice of buffers.
+    ///
+    /// Data is copied from each buffer in order, with the final buffer read
+    /// from possibly being only partially consumed. This method must behave
+    /// as a single call to [`write`] with the buffers concatenated would.
+    ///
+    /// The default implementation calls [`write`] with the first non-empty
+    /// buffer, or with an empty one if there is none. Writers backed by a
+    /// file descriptor override it to write all of the buffers with a single
+    /// system call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iovec)]
+    /// use std::io::IoSlice;
+    /// use std::io::prelude::*;
+    /// use std::fs::File;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let mut file = File::create("foo.txt")?;
+    ///
+    /// // Writes some prefix of the header followed by the body, in one go.
+    /// file.write_vectored(&[IoSlice::new(b"header"), IoSlice::new(b"body")])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`write`]: #tymethod.write
+    #[unstable(feature = "iovec", issue = "0")]
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> Result<usize> {
+        default_write_vectored(|b| self.write(b), bufs)
+    }
+
     /// Flush this output stream, ensuring that all intermediately buffered
     /// contents reach their destination.
     ///
@@ -2369,6 +2541,50 @@ mod tests {
         assert_eq!("AB", s);
     }
 
+    #[test]
+    fn read_vectored_default_uses_first_nonempty_buffer() {
+        let mut r = (&b"abcdef"[..]).take(6);
+        let mut a = [0; 0];
+        let mut b = [0; 4];
+        let mut c = [0; 4];
+        let n = r.read_vectored(&mut [io::IoSliceMut::new(&mut a),
+                                      io::IoSliceMut::new(&mut b),
+                                      io::IoSliceMut::new(&mut c)]).unwrap();
+        assert_eq!(n, 4);
+        assert_eq!(b, *b"abcd");
+        assert_eq!(c, [0; 4]);
+    }
+
+    #[test]
+    fn read_vectored_slice() {
+        let mut r = &b"abcdef"[..];
+        let mut a = [0; 4];
+        let mut b = [0; 4];
+        let n = r.read_vectored(&mut [io::IoSliceMut::new(&mut a),
+                                      io::IoSliceMut::new(&mut b)]).unwrap();
+        assert_eq!(n, 6);
+        assert_eq!(a, *b"abcd");
+        assert_eq!(&b[..2], b"ef");
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn write_vectored_vec() {
+        let mut v = Vec::new();
+        let n = v.write_vectored(&[io::IoSlice::new(b"head"),
+                                   io::IoSlice::new(b""),
+                                   io::IoSlice::new(b"body")]).unwrap();
+        assert_eq!(n, 8);
+        assert_eq!(v, b"headbody");
+    }
+
+    #[test]
+    fn io_slices_are_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<io::IoSlice>();
+        assert_send_sync::<io::IoSliceMut>();
+    }
+
     #[bench]
     #[cfg_attr(target_os = "emscripten", ignore)]
     fn bench_read_to_end(b: &mut test::Bencher) {
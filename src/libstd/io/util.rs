@@ -29,6 +29,16 @@ use mem;
 /// `write` returns an error. All instances of `ErrorKind::Interrupted` are
 /// handled by this function and the underlying operation is retried.
 ///
+/// # Platform-specific behavior
+///
+/// On Linux, copies from a `File` to a `File` or a `TcpStream`, and from a
+/// `TcpStream` to a `File`, are done inside the kernel where possible, with
+/// the `copy_file_range`, `sendfile` and `splice` system calls. A
+/// `read`/`write` loop is used where they are not available.
+/// Note that this [may change in the future][changes].
+///
+/// [changes]: index.html#platform-specific-behavior
+///
 /// # Examples
 ///
 /// ```
@@ -48,6 +58,74 @@ use mem;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub fn copy<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W) -> io::Result<u64>
     where R: Read, W: Write
+{
+    Copier { reader: reader, writer: writer }.copy()
+}
+
+// `copy` is specialized for the reader and writer types that the OS can copy
+// between without going through a userspace buffer.
+struct Copier<'a, R: ?Sized + 'a, W: ?Sized + 'a> {
+    reader: &'a mut R,
+    writer: &'a mut W,
+}
+
+trait SpecCopy {
+    fn copy(self) -> io::Result<u64>;
+}
+
+impl<'a, R: Read + ?Sized, W: Write + ?Sized> SpecCopy for Copier<'a, R, W> {
+    default fn copy(self) -> io::Result<u64> {
+        generic_copy(self.reader, self.writer)
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod kernel_copy {
+    use fs::File;
+    use io;
+    use net::TcpStream;
+    use sys::kernel_copy::{self, CopyResult};
+    use sys_common::AsInner;
+    use super::{Copier, SpecCopy, generic_copy};
+
+    impl<'a> SpecCopy for Copier<'a, File, File> {
+        fn copy(self) -> io::Result<u64> {
+            let result = kernel_copy::copy_regular_files(self.reader.as_inner().fd().raw(),
+                                                         self.writer.as_inner().fd().raw());
+            self.finish(result)
+        }
+    }
+
+    impl<'a> SpecCopy for Copier<'a, File, TcpStream> {
+        fn copy(self) -> io::Result<u64> {
+            let result = kernel_copy::sendfile(self.reader.as_inner().fd().raw(),
+                                               *self.writer.as_inner().socket().as_inner());
+            self.finish(result)
+        }
+    }
+
+    impl<'a> SpecCopy for Copier<'a, TcpStream, File> {
+        fn copy(self) -> io::Result<u64> {
+            let result = kernel_copy::splice(*self.reader.as_inner().socket().as_inner(),
+                                             self.writer.as_inner().fd().raw());
+            self.finish(result)
+        }
+    }
+
+    impl<'a, R: io::Read, W: io::Write> Copier<'a, R, W> {
+        fn finish(self, result: CopyResult) -> io::Result<u64> {
+            match result {
+                CopyResult::Ended(result) => result,
+                CopyResult::Fallback(written) => {
+                    generic_copy(self.reader, self.writer).map(|n| n + written)
+                }
+            }
+        }
+    }
+}
+
+fn generic_copy<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W) -> io::Result<u64>
+    where R: Read, W: Write
 {
     let mut buf = unsafe {
         let mut buf: [u8; super::DEFAULT_BUF_SIZE] = mem::uninitialized();
@@ -298,12 +298,14 @@
 #![feature(ptr_internals)]
 #![feature(rand)]
 #![feature(raw)]
+#![feature(repr_transparent)]
 #![feature(rustc_attrs)]
 #![feature(sip_hash_13)]
 #![feature(slice_bytes)]
 #![feature(slice_concat_ext)]
 #![feature(slice_internals)]
 #![feature(slice_patterns)]
+#![feature(specialization)]
 #![feature(staged_api)]
 #![feature(stmt_expr_attributes)]
 #![feature(str_char)]
@@ -314,6 +316,7 @@
 #![feature(thread_local)]
 #![feature(toowned_clone_into)]
 #![feature(try_from)]
+#![feature(try_reserve)]
 #![feature(unboxed_closures)]
 #![feature(unicode)]
 #![feature(untagged_unions)]
@@ -466,6 +469,9 @@ pub use alloc::vec;
 pub use std_unicode::char;
 #[unstable(feature = "i128", issue = "35118")]
 pub use core::u128;
+#[unstable(feature = "primitive_internals", issue = "0")]
+#[doc(hidden)]
+pub use core::primitive;
 
 pub mod f32;
 pub mod f64;
@@ -245,9 +245,140 @@ macro_rules! eprintln {
 /// ```
 ///
 /// For more information about select, see the `std::sync::mpsc::Select` structure.
+///
+/// The macro also accepts a second syntax that works with the channels in
+/// `std::sync::mpsc::mpmc`. Each arm is either a receive, a send, or a
+/// default case:
+///
+/// * `recv(rx) -> res => body` runs `body` with `res` bound to the result of
+///   receiving from `rx`.
+/// * `send(tx, msg) -> res => body` runs `body` with `res` bound to the
+///   result of sending `msg` on `tx`. The message is only evaluated if this
+///   arm is selected.
+/// * `default => body` runs `body` if no other operation is ready right now,
+///   and `default(timeout) => body` runs it if none becomes ready within
+///   `timeout`. At most one default arm may be given.
+///
+/// Arms are separated by commas, which may be omitted after a block. If
+/// several operations are ready, the first one listed is picked.
+///
+/// ```
+/// #![feature(mpsc_select, mpmc_channel)]
+///
+/// use std::sync::mpsc::mpmc;
+/// use std::time::Duration;
+///
+/// let (tx1, rx1) = mpmc::channel::<u32>();
+/// let (tx2, rx2) = mpmc::sync_channel(1);
+///
+/// select! {
+///     recv(rx1) -> msg => panic!("unexpected message: {:?}", msg),
+///     send(tx2, "ping") -> res => res.unwrap(),
+/// }
+/// assert_eq!(rx2.try_recv(), Ok("ping"));
+///
+/// select! {
+///     recv(rx1) -> msg => panic!("unexpected message: {:?}", msg),
+///     default(Duration::from_millis(10)) => println!("timed out"),
+/// }
+/// # drop(tx1);
+/// ```
 #[macro_export]
 #[unstable(feature = "mpsc_select", issue = "27800")]
 macro_rules! select {
+    // The `mpmc` syntax is parsed one arm at a time into a list of operations,
+    // each carrying its index and a fresh identifier for its channel, and an
+    // optional default case.
+    (@parse $ops:tt $idx:tt $default:tt
+     recv($rx:expr) -> $res:pat => $($rest:tt)*) => {
+        select!(@body $ops $idx $default (recv ($rx) () $res) $($rest)*)
+    };
+    (@parse $ops:tt $idx:tt $default:tt
+     send($tx:expr, $msg:expr) -> $res:pat => $($rest:tt)*) => {
+        select!(@body $ops $idx $default (send ($tx) ($msg) $res) $($rest)*)
+    };
+    (@parse $ops:tt $idx:tt [] default($timeout:expr) => $($rest:tt)*) => {
+        select!(@body_default $ops $idx ($timeout) $($rest)*)
+    };
+    (@parse $ops:tt $idx:tt [] default => $($rest:tt)*) => {
+        select!(@body_default $ops $idx () $($rest)*)
+    };
+    (@parse $ops:tt $idx:tt $default:tt , $($rest:tt)*) => {
+        select!(@parse $ops $idx $default $($rest)*)
+    };
+    (@parse $ops:tt $idx:tt $default:tt) => {
+        select!(@emit $ops $default)
+    };
+
+    (@body [$($ops:tt)*] [$($idx:tt)*] $default:tt
+     ($kind:ident $ch:tt $msg:tt $res:pat) $body:expr, $($rest:tt)*) => {
+        select!(@parse [$($ops)* ($kind [$($idx)*] __chan $ch $msg $res => $body)]
+                       [$($idx)* + 1] $default $($rest)*)
+    };
+    (@body [$($ops:tt)*] [$($idx:tt)*] $default:tt
+     ($kind:ident $ch:tt $msg:tt $res:pat) $body:expr) => {
+        select!(@parse [$($ops)* ($kind [$($idx)*] __chan $ch $msg $res => $body)]
+                       [$($idx)* + 1] $default)
+    };
+    (@body [$($ops:tt)*] [$($idx:tt)*] $default:tt
+     ($kind:ident $ch:tt $msg:tt $res:pat) $body:block $($rest:tt)*) => {
+        select!(@parse [$($ops)* ($kind [$($idx)*] __chan $ch $msg $res => $body)]
+                       [$($idx)* + 1] $default $($rest)*)
+    };
+
+    (@body_default $ops:tt $idx:tt $d:tt $body:expr, $($rest:tt)*) => {
+        select!(@parse $ops $idx [$d $body] $($rest)*)
+    };
+    (@body_default $ops:tt $idx:tt $d:tt $body:expr) => {
+        select!(@parse $ops $idx [$d $body])
+    };
+    (@body_default $ops:tt $idx:tt $d:tt $body:block $($rest:tt)*) => {
+        select!(@parse $ops $idx [$d $body] $($rest)*)
+    };
+
+    (@emit [$(($kind:ident [$($idx:tt)*] $chan:ident ($ch:expr) ($($msg:expr),*)
+               $res:pat => $body:expr))*]
+           $default:tt) => ({
+        $( let $chan = &$ch; )*
+        let mut __sel = $crate::sync::mpsc::mpmc::Select::new();
+        $( select!(@register __sel $kind $chan); )*
+        match select!(@wait __sel $default) {
+            Some(__oper) => {
+                let __index = __oper.index();
+                $(
+                    if __index == $($idx)* {
+                        select!(@complete __oper $kind $chan ($($msg),*) $res => $body)
+                    } else
+                )*
+                { unreachable!() }
+            }
+            None => select!(@default $default),
+        }
+    });
+
+    (@register $sel:ident recv $chan:ident) => { $sel.recv($chan); };
+    (@register $sel:ident send $chan:ident) => { $sel.send($chan); };
+
+    (@wait $sel:ident []) => { Some($sel.select()) };
+    (@wait $sel:ident [() $body:expr]) => { $sel.try_select().ok() };
+    (@wait $sel:ident [($timeout:expr) $body:expr]) => { $sel.select_timeout($timeout).ok() };
+
+    (@complete $oper:ident recv $chan:ident () $res:pat => $body:expr) => ({
+        let $res = $oper.recv($chan);
+        $body
+    });
+    (@complete $oper:ident send $chan:ident ($msg:expr) $res:pat => $body:expr) => ({
+        let $res = $oper.send($chan, $msg);
+        $body
+    });
+
+    (@default []) => { unreachable!() };
+    (@default [$d:tt $body:expr]) => { $body };
+
+    (recv $($t:tt)*) => { select!(@parse [] [0] [] recv $($t)*) };
+    (send $($t:tt)*) => { select!(@parse [] [0] [] send $($t)*) };
+    (default $($t:tt)*) => { select!(@parse [] [0] [] default $($t)*) };
+
     (
         $($name:pat = $rx:ident.$meth:ident() => $code:expr),+
     ) => ({
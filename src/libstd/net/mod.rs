@@ -48,8 +48,12 @@ pub use self::ip::{IpAddr, Ipv4Addr, Ipv6Addr, Ipv6MulticastScope};
 pub use self::addr::{SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::tcp::{TcpStream, TcpListener, Incoming};
+#[unstable(feature = "net_socket_builder", issue = "0")]
+pub use self::tcp::TcpBuilder;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::udp::UdpSocket;
+#[unstable(feature = "net_socket_builder", issue = "0")]
+pub use self::udp::UdpBuilder;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::parser::AddrParseError;
 
@@ -11,7 +11,7 @@
 use io::prelude::*;
 
 use fmt;
-use io::{self, Initializer};
+use io::{self, Initializer, IoSlice, IoSliceMut};
 use net::{ToSocketAddrs, SocketAddr, Shutdown};
 use sys_common::net as net_imp;
 use sys_common::{AsInner, FromInner, IntoInner};
@@ -105,6 +105,41 @@ pub struct TcpListener(net_imp::TcpListener);
 #[derive(Debug)]
 pub struct Incoming<'a> { listener: &'a TcpListener }
 
+/// A builder for a TCP socket, which configures the socket before it is
+/// bound, connected or made to listen.
+///
+/// Some socket options, such as `SO_REUSEADDR` or the size of the receive
+/// buffer, only have an effect when they are set before the socket is bound
+/// or connected. A `TcpBuilder` creates the socket up front so that these can
+/// be set, and then turns it into a [`TcpListener`] with [`listen`] or into a
+/// [`TcpStream`] with [`connect`].
+///
+/// Each option is set on the socket right away, so setting one can fail.
+///
+/// [`TcpListener`]: ../../std/net/struct.TcpListener.html
+/// [`TcpStream`]: ../../std/net/struct.TcpStream.html
+/// [`listen`]: #method.listen
+/// [`connect`]: #method.connect
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(net_socket_builder)]
+/// use std::net::TcpBuilder;
+///
+/// # fn foo() -> std::io::Result<()> {
+/// let builder = TcpBuilder::new_v4()?;
+/// builder.reuse_address(true)?
+///        .reuse_port(true)?
+///        .bind(&"0.0.0.0:8080".parse().unwrap())?;
+/// let listener = builder.listen(1024)?;
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "net_socket_builder", issue = "0")]
+#[derive(Debug)]
+pub struct TcpBuilder(net_imp::SocketBuilder);
+
 impl TcpStream {
     /// Opens a TCP connection to a remote host.
     ///
@@ -548,6 +583,10 @@ impl TcpStream {
 impl Read for TcpStream {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.0.read(buf) }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -556,12 +595,21 @@ impl Read for TcpStream {
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Write for TcpStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.write(buf) }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a> Read for &'a TcpStream {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.0.read(buf) }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -570,6 +618,11 @@ impl<'a> Read for &'a TcpStream {
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a> Write for &'a TcpStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.write(buf) }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
 
@@ -885,6 +938,146 @@ impl fmt::Debug for TcpListener {
     }
 }
 
+impl TcpBuilder {
+    /// Creates a new IPv4 TCP socket.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn new_v4() -> io::Result<TcpBuilder> {
+        net_imp::SocketBuilder::new_tcp(false).map(TcpBuilder)
+    }
+
+    /// Creates a new IPv6 TCP socket.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn new_v6() -> io::Result<TcpBuilder> {
+        net_imp::SocketBuilder::new_tcp(true).map(TcpBuilder)
+    }
+
+    /// Sets the `SO_REUSEADDR` option on this socket.
+    ///
+    /// On Unix this allows a listener to bind to an address that still has
+    /// connections in the `TIME_WAIT` state. On Windows it allows other
+    /// sockets to bind to the very same address, so it should be used with
+    /// care there.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn reuse_address(&self, reuse: bool) -> io::Result<&TcpBuilder> {
+        self.0.set_reuse_address(reuse).map(|()| self)
+    }
+
+    /// Sets the `SO_REUSEPORT` option on this socket, which allows several
+    /// sockets to bind to the same address and port.
+    ///
+    /// On Linux, incoming connections are then balanced between the
+    /// listeners. An error is returned on platforms without this option,
+    /// which include Windows.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn reuse_port(&self, reuse: bool) -> io::Result<&TcpBuilder> {
+        self.0.set_reuse_port(reuse).map(|()| self)
+    }
+
+    /// Sets the size of the send buffer of this socket, the `SO_SNDBUF`
+    /// option.
+    ///
+    /// The operating system may round or clamp the size.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn send_buffer_size(&self, size: usize) -> io::Result<&TcpBuilder> {
+        self.0.set_send_buffer_size(size).map(|()| self)
+    }
+
+    /// Sets the size of the receive buffer of this socket, the `SO_RCVBUF`
+    /// option.
+    ///
+    /// The operating system may round or clamp the size. The receive buffer
+    /// size determines the TCP window scale, so it has to be set before the
+    /// socket is connected or listening to have its full effect.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn recv_buffer_size(&self, size: usize) -> io::Result<&TcpBuilder> {
+        self.0.set_recv_buffer_size(size).map(|()| self)
+    }
+
+    /// Configures TCP keepalive for this socket.
+    ///
+    /// `None` turns keepalive off. `Some(idle)` turns it on, with probes
+    /// starting once the connection has been idle for `idle`, rounded up to
+    /// a whole second. An error is returned, leaving the socket unchanged, if
+    /// the idle time can't be set on this platform; it is supported on Linux,
+    /// Android, macOS, iOS, FreeBSD, NetBSD, DragonFly BSD and Windows.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn keepalive(&self, keepalive: Option<Duration>) -> io::Result<&TcpBuilder> {
+        self.0.set_keepalive(keepalive).map(|()| self)
+    }
+
+    /// Sets the value of the `IP_TTL` option for this socket.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn ttl(&self, ttl: u32) -> io::Result<&TcpBuilder> {
+        self.0.set_ttl(ttl).map(|()| self)
+    }
+
+    /// Sets the value of the `IPV6_V6ONLY` option for this socket.
+    ///
+    /// If this is set to `true`, an IPv6 socket only communicates over IPv6;
+    /// otherwise it can also accept IPv4 connections through IPv4-mapped
+    /// addresses.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn only_v6(&self, only_v6: bool) -> io::Result<&TcpBuilder> {
+        self.0.set_only_v6(only_v6).map(|()| self)
+    }
+
+    /// Binds this socket to the network interface named `interface`, the
+    /// `SO_BINDTODEVICE` option, so that it only sends and receives packets
+    /// through that interface.
+    ///
+    /// This is only supported on Linux and Android, and usually requires
+    /// elevated privileges.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn bind_device(&self, interface: &str) -> io::Result<&TcpBuilder> {
+        self.0.bind_device(interface).map(|()| self)
+    }
+
+    /// Binds this socket to the local address `addr`.
+    ///
+    /// Binding is required before [`listen`], and can be used before
+    /// [`connect`] to choose the local address of the connection.
+    ///
+    /// [`listen`]: #method.listen
+    /// [`connect`]: #method.connect
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn bind(&self, addr: &SocketAddr) -> io::Result<&TcpBuilder> {
+        self.0.bind(addr).map(|()| self)
+    }
+
+    /// Starts listening for connections on this socket, turning it into a
+    /// [`TcpListener`].
+    ///
+    /// `backlog` is the maximum number of pending connections, which the
+    /// operating system may clamp.
+    ///
+    /// [`TcpListener`]: ../../std/net/struct.TcpListener.html
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn listen(self, backlog: i32) -> io::Result<TcpListener> {
+        self.0.listen(backlog).map(TcpListener)
+    }
+
+    /// Opens a connection to `addr` over this socket, turning it into a
+    /// [`TcpStream`].
+    ///
+    /// Unlike [`TcpStream::connect`], this takes a single address, as a
+    /// socket can only attempt to connect once.
+    ///
+    /// [`TcpStream`]: ../../std/net/struct.TcpStream.html
+    /// [`TcpStream::connect`]: ../../std/net/struct.TcpStream.html#method.connect
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn connect(self, addr: &SocketAddr) -> io::Result<TcpStream> {
+        self.0.connect(addr).map(TcpStream)
+    }
+}
+
+impl AsInner<net_imp::SocketBuilder> for TcpBuilder {
+    fn as_inner(&self) -> &net_imp::SocketBuilder { &self.0 }
+}
+
+impl FromInner<net_imp::SocketBuilder> for TcpBuilder {
+    fn from_inner(inner: net_imp::SocketBuilder) -> TcpBuilder { TcpBuilder(inner) }
+}
+
 #[cfg(all(test, not(any(target_os = "cloudabi", target_os = "emscripten"))))]
 mod tests {
     use io::ErrorKind;
@@ -1008,6 +1201,38 @@ mod tests {
         })
     }
 
+    #[test]
+    fn io_copy_file_through_socket() {
+        use fs::File;
+        use io;
+        use sys_common::io::test::tmpdir;
+
+        let tmpdir = tmpdir();
+        let input = tmpdir.join("in.txt");
+        let out = tmpdir.join("out.txt");
+        let data = (0..100_000).map(|i| i as u8).collect::<Vec<u8>>();
+        t!(t!(File::create(&input)).write_all(&data));
+
+        each_ip(&mut |addr| {
+            let acceptor = t!(TcpListener::bind(&addr));
+
+            let input = input.clone();
+            let _t = thread::spawn(move|| {
+                let mut stream = t!(TcpStream::connect(&addr));
+                let mut file = t!(File::open(&input));
+                assert_eq!(t!(io::copy(&mut file, &mut stream)), 100_000);
+            });
+
+            let mut stream = t!(acceptor.accept()).0;
+            let mut file = t!(File::create(&out));
+            assert_eq!(t!(io::copy(&mut stream, &mut file)), 100_000);
+
+            let mut v = Vec::new();
+            t!(t!(File::open(&out)).read_to_end(&mut v));
+            assert!(v == data);
+        })
+    }
+
     #[test]
     fn write_close() {
         each_ip(&mut |addr| {
@@ -1659,4 +1884,51 @@ mod tests {
         let addr = listener.local_addr().unwrap();
         TcpStream::connect_timeout(&addr, Duration::from_secs(2)).unwrap();
     }
+
+    #[test]
+    fn builder_listen_connect() {
+        each_ip(&mut |addr| {
+            let builder = t!(match addr {
+                SocketAddr::V4(..) => TcpBuilder::new_v4(),
+                SocketAddr::V6(..) => TcpBuilder::new_v6(),
+            });
+            t!(t!(builder.reuse_address(true)).recv_buffer_size(64 * 1024));
+            if addr.is_ipv4() {
+                t!(builder.ttl(100));
+            }
+            t!(builder.bind(&addr));
+            let listener = t!(builder.listen(16));
+            if addr.is_ipv4() {
+                assert_eq!(t!(listener.ttl()), 100);
+            }
+            assert_eq!(t!(listener.local_addr()), addr);
+
+            let builder = t!(match addr {
+                SocketAddr::V4(..) => TcpBuilder::new_v4(),
+                SocketAddr::V6(..) => TcpBuilder::new_v6(),
+            });
+            t!(builder.send_buffer_size(64 * 1024));
+            let mut stream = t!(builder.connect(&addr));
+            t!(stream.write(&[99]));
+
+            let mut buf = [0];
+            let (mut accepted, _) = t!(listener.accept());
+            t!(accepted.read(&mut buf));
+            assert_eq!(buf[0], 99);
+        })
+    }
+
+    #[test]
+    fn builder_keepalive() {
+        let builder = t!(TcpBuilder::new_v4());
+        t!(builder.keepalive(None));
+        if cfg!(any(target_os = "linux", target_os = "android",
+                    target_os = "macos", target_os = "ios",
+                    target_os = "freebsd", target_os = "netbsd",
+                    target_os = "dragonfly", windows)) {
+            t!(builder.keepalive(Some(Duration::from_secs(30))));
+        } else {
+            assert!(builder.keepalive(Some(Duration::from_secs(30))).is_err());
+        }
+    }
 }
@@ -64,6 +64,37 @@ use time::Duration;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct UdpSocket(net_imp::UdpSocket);
 
+/// A builder for a UDP socket, which configures the socket before it is
+/// bound.
+///
+/// Some socket options, such as `SO_REUSEPORT` or the size of the receive
+/// buffer, have to be set before the socket is bound. A `UdpBuilder` creates
+/// the socket up front so that these can be set, and then turns it into a
+/// [`UdpSocket`] with [`bind`].
+///
+/// Each option is set on the socket right away, so setting one can fail.
+///
+/// [`UdpSocket`]: ../../std/net/struct.UdpSocket.html
+/// [`bind`]: #method.bind
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(net_socket_builder)]
+/// use std::net::UdpBuilder;
+///
+/// # fn foo() -> std::io::Result<()> {
+/// let builder = UdpBuilder::new_v4()?;
+/// builder.reuse_port(true)?
+///        .recv_buffer_size(1 << 20)?;
+/// let socket = builder.bind(&"0.0.0.0:5353".parse().unwrap())?;
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "net_socket_builder", issue = "0")]
+#[derive(Debug)]
+pub struct UdpBuilder(net_imp::SocketBuilder);
+
 impl UdpSocket {
     /// Creates a UDP socket from the given address.
     ///
@@ -767,6 +798,103 @@ impl UdpSocket {
     }
 }
 
+impl UdpBuilder {
+    /// Creates a new IPv4 UDP socket.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn new_v4() -> io::Result<UdpBuilder> {
+        net_imp::SocketBuilder::new_udp(false).map(UdpBuilder)
+    }
+
+    /// Creates a new IPv6 UDP socket.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn new_v6() -> io::Result<UdpBuilder> {
+        net_imp::SocketBuilder::new_udp(true).map(UdpBuilder)
+    }
+
+    /// Sets the `SO_REUSEADDR` option on this socket.
+    ///
+    /// This allows several sockets to bind to the same address, which is
+    /// useful for receiving multicast traffic.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn reuse_address(&self, reuse: bool) -> io::Result<&UdpBuilder> {
+        self.0.set_reuse_address(reuse).map(|()| self)
+    }
+
+    /// Sets the `SO_REUSEPORT` option on this socket, which allows several
+    /// sockets to bind to the same address and port.
+    ///
+    /// On Linux, incoming datagrams are then balanced between the sockets.
+    /// An error is returned on platforms without this option, which include
+    /// Windows.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn reuse_port(&self, reuse: bool) -> io::Result<&UdpBuilder> {
+        self.0.set_reuse_port(reuse).map(|()| self)
+    }
+
+    /// Sets the size of the send buffer of this socket, the `SO_SNDBUF`
+    /// option.
+    ///
+    /// The operating system may round or clamp the size.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn send_buffer_size(&self, size: usize) -> io::Result<&UdpBuilder> {
+        self.0.set_send_buffer_size(size).map(|()| self)
+    }
+
+    /// Sets the size of the receive buffer of this socket, the `SO_RCVBUF`
+    /// option.
+    ///
+    /// The operating system may round or clamp the size.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn recv_buffer_size(&self, size: usize) -> io::Result<&UdpBuilder> {
+        self.0.set_recv_buffer_size(size).map(|()| self)
+    }
+
+    /// Sets the value of the `IP_TTL` option for this socket.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn ttl(&self, ttl: u32) -> io::Result<&UdpBuilder> {
+        self.0.set_ttl(ttl).map(|()| self)
+    }
+
+    /// Sets the value of the `IPV6_V6ONLY` option for this socket.
+    ///
+    /// If this is set to `true`, an IPv6 socket only communicates over IPv6;
+    /// otherwise it can also exchange datagrams with IPv4 peers through
+    /// IPv4-mapped addresses.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn only_v6(&self, only_v6: bool) -> io::Result<&UdpBuilder> {
+        self.0.set_only_v6(only_v6).map(|()| self)
+    }
+
+    /// Binds this socket to the network interface named `interface`, the
+    /// `SO_BINDTODEVICE` option, so that it only sends and receives packets
+    /// through that interface.
+    ///
+    /// This is only supported on Linux and Android, and usually requires
+    /// elevated privileges.
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn bind_device(&self, interface: &str) -> io::Result<&UdpBuilder> {
+        self.0.bind_device(interface).map(|()| self)
+    }
+
+    /// Binds this socket to the local address `addr`, turning it into a
+    /// [`UdpSocket`].
+    ///
+    /// [`UdpSocket`]: ../../std/net/struct.UdpSocket.html
+    #[unstable(feature = "net_socket_builder", issue = "0")]
+    pub fn bind(self, addr: &SocketAddr) -> io::Result<UdpSocket> {
+        self.0.bind(addr)?;
+        Ok(UdpSocket(self.0.into_udp()))
+    }
+}
+
+impl AsInner<net_imp::SocketBuilder> for UdpBuilder {
+    fn as_inner(&self) -> &net_imp::SocketBuilder { &self.0 }
+}
+
+impl FromInner<net_imp::SocketBuilder> for UdpBuilder {
+    fn from_inner(inner: net_imp::SocketBuilder) -> UdpBuilder { UdpBuilder(inner) }
+}
+
 impl AsInner<net_imp::UdpSocket> for UdpSocket {
     fn as_inner(&self) -> &net_imp::UdpSocket { &self.0 }
 }
@@ -1113,4 +1241,41 @@ mod tests {
             }
         })
     }
+
+    #[test]
+    fn builder_bind() {
+        each_ip(&mut |addr1, addr2| {
+            let builder = t!(match addr1 {
+                SocketAddr::V4(..) => UdpBuilder::new_v4(),
+                SocketAddr::V6(..) => UdpBuilder::new_v6(),
+            });
+            t!(t!(builder.reuse_address(true)).recv_buffer_size(64 * 1024));
+            t!(builder.send_buffer_size(64 * 1024));
+            let server = t!(builder.bind(&addr1));
+            assert_eq!(t!(server.local_addr()), addr1);
+
+            let client = t!(UdpSocket::bind(&addr2));
+            t!(client.send_to(&[99], &addr1));
+
+            let mut buf = [0];
+            let (len, from) = t!(server.recv_from(&mut buf));
+            assert_eq!(len, 1);
+            assert_eq!(buf[0], 99);
+            assert_eq!(from, addr2);
+        })
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn builder_reuse_port() {
+        let addr = next_test_ip4();
+        let sockets = (0..2).map(|_| {
+            let builder = t!(UdpBuilder::new_v4());
+            t!(builder.reuse_port(true));
+            t!(builder.bind(&addr))
+        }).collect::<Vec<_>>();
+        for socket in &sockets {
+            assert_eq!(t!(socket.local_addr()), addr);
+        }
+    }
 }
@@ -121,12 +121,15 @@ use io::prelude::*;
 use ffi::OsStr;
 use fmt;
 use fs;
-use io::{self, Initializer};
+use io::{self, Initializer, IoSlice};
+use mem;
 use path::Path;
+use ptr;
 use str;
 use sys::pipe::{read2, AnonPipe};
 use sys::process as imp;
 use sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+use time::Duration;
 
 /// Representation of a running or exited child process.
 ///
@@ -138,7 +141,8 @@ use sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
 /// There is no implementation of [`Drop`] for child processes,
 /// so if you do not ensure the `Child` has exited then it will continue to
 /// run, even after the `Child` handle to the child process has gone out of
-/// scope.
+/// scope. The exception is a child spawned from a [`Command`] configured with
+/// [`kill_on_drop`], which is killed once its `Child` goes out of scope.
 ///
 /// Calling [`wait`](#method.wait) (or other functions that wrap around it) will make
 /// the parent process wait until the child has actually exited before
@@ -163,9 +167,10 @@ use sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
 /// [`Command`]: struct.Command.html
 /// [`Drop`]: ../../core/ops/trait.Drop.html
 /// [`wait`]: #method.wait
+/// [`kill_on_drop`]: struct.Command.html#method.kill_on_drop
 #[stable(feature = "process", since = "1.0.0")]
 pub struct Child {
-    handle: imp::Process,
+    handle: ChildHandle,
 
     /// The handle for writing to the child's standard input (stdin), if it has
     /// been captured.
@@ -183,14 +188,41 @@ pub struct Child {
     pub stderr: Option<ChildStderr>,
 }
 
+// The process of a `Child`, kept apart so that killing the process on drop
+// doesn't require a `Drop` impl on `Child` itself, which would stop its
+// public fields from being moved out.
+struct ChildHandle {
+    process: imp::Process,
+    kill_on_drop: bool,
+}
+
+impl ChildHandle {
+    fn into_process(self) -> imp::Process {
+        let process = unsafe { ptr::read(&self.process) };
+        mem::forget(self);
+        process
+    }
+}
+
+impl Drop for ChildHandle {
+    fn drop(&mut self) {
+        if self.kill_on_drop {
+            if let Ok(None) = self.process.try_wait() {
+                let _ = self.process.kill();
+                let _ = self.process.wait();
+            }
+        }
+    }
+}
+
 impl AsInner<imp::Process> for Child {
-    fn as_inner(&self) -> &imp::Process { &self.handle }
+    fn as_inner(&self) -> &imp::Process { &self.handle.process }
 }
 
 impl FromInner<(imp::Process, imp::StdioPipes)> for Child {
-    fn from_inner((handle, io): (imp::Process, imp::StdioPipes)) -> Child {
+    fn from_inner((process, io): (imp::Process, imp::StdioPipes)) -> Child {
         Child {
-            handle,
+            handle: ChildHandle { process, kill_on_drop: false },
             stdin: io.stdin.map(ChildStdin::from_inner),
             stdout: io.stdout.map(ChildStdout::from_inner),
             stderr: io.stderr.map(ChildStderr::from_inner),
@@ -199,7 +231,7 @@ impl FromInner<(imp::Process, imp::StdioPipes)> for Child {
 }
 
 impl IntoInner<imp::Process> for Child {
-    fn into_inner(self) -> imp::Process { self.handle }
+    fn into_inner(self) -> imp::Process { self.handle.into_process() }
 }
 
 #[stable(feature = "std_debug", since = "1.16.0")]
@@ -235,6 +267,10 @@ impl Write for ChildStdin {
         self.inner.write(buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
@@ -384,6 +420,7 @@ impl fmt::Debug for ChildStderr {
 #[stable(feature = "process", since = "1.0.0")]
 pub struct Command {
     inner: imp::Command,
+    kill_on_drop: bool,
 }
 
 impl Command {
@@ -419,7 +456,7 @@ impl Command {
     /// ```
     #[stable(feature = "process", since = "1.0.0")]
     pub fn new<S: AsRef<OsStr>>(program: S) -> Command {
-        Command { inner: imp::Command::new(program.as_ref()) }
+        Command { inner: imp::Command::new(program.as_ref()), kill_on_drop: false }
     }
 
     /// Add an argument to pass to the program.
@@ -689,6 +726,41 @@ impl Command {
         self
     }
 
+    /// Sets whether the child process should be killed when the [`Child`]
+    /// returned by [`spawn`] is dropped while the child is still running.
+    ///
+    /// The child is killed as with [`Child::kill`] and then waited on, so that
+    /// it doesn't linger as a zombie. This makes sure that children are torn
+    /// down even if the parent unwinds past the code that was meant to wait
+    /// on them.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`Child`]: struct.Child.html
+    /// [`spawn`]: #method.spawn
+    /// [`Child::kill`]: struct.Child.html#method.kill
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_kill_on_drop)]
+    /// use std::process::Command;
+    ///
+    /// let child = Command::new("sleep")
+    ///                     .arg("1000")
+    ///                     .kill_on_drop(true)
+    ///                     .spawn()
+    ///                     .expect("sleep command failed to start");
+    ///
+    /// // `sleep` is killed here
+    /// drop(child);
+    /// ```
+    #[unstable(feature = "process_kill_on_drop", issue = "0")]
+    pub fn kill_on_drop(&mut self, kill_on_drop: bool) -> &mut Command {
+        self.kill_on_drop = kill_on_drop;
+        self
+    }
+
     /// Executes the command as a child process, returning a handle to it.
     ///
     /// By default, stdin, stdout and stderr are inherited from the parent.
@@ -706,7 +778,9 @@ impl Command {
     /// ```
     #[stable(feature = "process", since = "1.0.0")]
     pub fn spawn(&mut self) -> io::Result<Child> {
-        self.inner.spawn(imp::Stdio::Inherit, true).map(Child::from_inner)
+        let mut child = Child::from_inner(self.inner.spawn(imp::Stdio::Inherit, true)?);
+        child.handle.kill_on_drop = self.kill_on_drop;
+        Ok(child)
     }
 
     /// Executes the command as a child process, waiting for it to finish and
@@ -1100,7 +1174,7 @@ impl Child {
     /// ```
     #[stable(feature = "process", since = "1.0.0")]
     pub fn kill(&mut self) -> io::Result<()> {
-        self.handle.kill()
+        self.handle.process.kill()
     }
 
     /// Returns the OS-assigned process identifier associated with this child.
@@ -1121,7 +1195,7 @@ impl Child {
     /// ```
     #[stable(feature = "process_id", since = "1.3.0")]
     pub fn id(&self) -> u32 {
-        self.handle.id()
+        self.handle.process.id()
     }
 
     /// Waits for the child to exit completely, returning the status that it
@@ -1151,7 +1225,7 @@ impl Child {
     #[stable(feature = "process", since = "1.0.0")]
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         drop(self.stdin.take());
-        self.handle.wait().map(ExitStatus)
+        self.handle.process.wait().map(ExitStatus)
     }
 
     /// Attempts to collect the exit status of the child if it has already
@@ -1190,7 +1264,55 @@ impl Child {
     /// ```
     #[stable(feature = "process_try_wait", since = "1.18.0")]
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
-        Ok(self.handle.try_wait()?.map(ExitStatus))
+        Ok(self.handle.process.try_wait()?.map(ExitStatus))
+    }
+
+    /// Waits for at most `timeout` for the child to exit, returning its exit
+    /// status if it did.
+    ///
+    /// Returns `Ok(None)` if the child is still running once the timeout has
+    /// elapsed. As with [`try_wait`], the process id is reaped on Unix once
+    /// the child has exited, and the same exit status is returned by any
+    /// later call. Unlike [`wait`], this does not close the child's stdin.
+    ///
+    /// How the child is waited on depends on the platform:
+    ///
+    /// * On Linux 5.3 and later, a pidfd is opened for the child and polled,
+    ///   which briefly takes up a file descriptor. Older kernels, and
+    ///   sandboxes that forbid `pidfd_open`, fall back to polling as below.
+    /// * On macOS, iOS and the BSDs, the child's exit is awaited as a kqueue
+    ///   event, which likewise briefly takes up a file descriptor.
+    /// * On Windows, the child's handle is waited on directly.
+    /// * Everywhere else, the calling thread checks on the child with
+    ///   [`try_wait`] and sleeps in between, for up to 100 milliseconds at a
+    ///   time, so the exit may be noticed that much late.
+    ///
+    /// No signal handlers are installed on any platform.
+    ///
+    /// [`try_wait`]: #method.try_wait
+    /// [`wait`]: #method.wait
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(child_wait_timeout)]
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("sleep").arg("1000").spawn().unwrap();
+    /// match child.wait_timeout(Duration::from_secs(1)) {
+    ///     Ok(Some(status)) => println!("exited with: {}", status),
+    ///     Ok(None) => {
+    ///         println!("still running after a second, killing it");
+    ///         child.kill().unwrap();
+    ///         child.wait().unwrap();
+    ///     }
+    ///     Err(e) => println!("error attempting to wait: {}", e),
+    /// }
+    /// ```
+    #[unstable(feature = "child_wait_timeout", issue = "0")]
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        Ok(self.handle.process.wait_timeout(timeout)?.map(ExitStatus))
     }
 
     /// Simultaneously waits for the child to exit and collect all remaining
@@ -288,6 +288,8 @@ use self::select::StartResult;
 use self::select::StartResult::*;
 use self::blocking::SignalToken;
 
+pub mod mpmc;
+
 mod blocking;
 mod oneshot;
 mod select;
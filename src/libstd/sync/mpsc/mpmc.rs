@@ -0,0 +1,1150 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Multi-producer, multi-consumer FIFO queue communication primitives.
+//!
+//! This module provides a channel flavor whose [`Receiver`] can be cloned as
+//! freely as its [`Sender`], so that any number of threads can pull work off
+//! of a single queue. Channels can be unbounded, created with [`channel`], or
+//! bounded, created with [`sync_channel`], in which case sends block while
+//! the buffer is full.
+//!
+//! Unlike the receivers in the parent module, the channels here can be waited
+//! on together with [`Select`], which covers both receive and send operations
+//! as well as timeouts. The [`select!`] macro offers a more convenient syntax
+//! on top of it.
+//!
+//! [`Receiver`]: struct.Receiver.html
+//! [`Sender`]: struct.Sender.html
+//! [`channel`]: fn.channel.html
+//! [`sync_channel`]: fn.sync_channel.html
+//! [`Select`]: struct.Select.html
+//! [`select!`]: ../../../macro.select.html
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(mpmc_channel)]
+//! use std::sync::mpsc::mpmc;
+//! use std::thread;
+//!
+//! let (tx, rx) = mpmc::sync_channel(16);
+//!
+//! let workers: Vec<_> = (0..4).map(|_| {
+//!     let rx = rx.clone();
+//!     thread::spawn(move || rx.iter().map(|n: u32| n * 2).sum::<u32>())
+//! }).collect();
+//! drop(rx);
+//!
+//! for n in 0..100 {
+//!     tx.send(n).unwrap();
+//! }
+//! drop(tx);
+//!
+//! let total: u32 = workers.into_iter().map(|w| w.join().unwrap()).sum();
+//! assert_eq!(total, 9900);
+//! ```
+
+#![unstable(feature = "mpmc_channel", issue = "0")]
+
+use collections::VecDeque;
+use error;
+use fmt;
+use sync::{Arc, Mutex, MutexGuard};
+use sync::mpsc::blocking::{self, SignalToken};
+use sync::mpsc::{SendError, RecvError, TrySendError, TryRecvError, RecvTimeoutError};
+use time::{Duration, Instant};
+
+/// Creates a new unbounded channel whose sending and receiving halves can
+/// both be cloned.
+///
+/// Sends on an unbounded channel never block. Each message is delivered to
+/// exactly one of the receivers.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+/// use std::sync::mpsc::mpmc;
+///
+/// let (tx, rx) = mpmc::channel();
+/// let rx2 = rx.clone();
+///
+/// tx.send(1).unwrap();
+/// tx.send(2).unwrap();
+/// assert_eq!(rx.recv(), Ok(1));
+/// assert_eq!(rx2.recv(), Ok(2));
+/// ```
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    new_channel(None)
+}
+
+/// Creates a new bounded channel whose sending and receiving halves can both
+/// be cloned.
+///
+/// The channel buffers at most `bound` messages; once the buffer is full,
+/// [`send`] blocks until a receiver makes room.
+///
+/// [`send`]: struct.Sender.html#method.send
+///
+/// # Panics
+///
+/// Panics if `bound` is zero. Rendezvous channels are not supported by this
+/// flavor.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+/// use std::sync::mpsc::{mpmc, TrySendError};
+///
+/// let (tx, rx) = mpmc::sync_channel(1);
+///
+/// tx.send(1).unwrap();
+/// assert_eq!(tx.try_send(2), Err(TrySendError::Full(2)));
+/// assert_eq!(rx.recv(), Ok(1));
+/// tx.try_send(2).unwrap();
+/// ```
+pub fn sync_channel<T>(bound: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(bound > 0, "mpmc::sync_channel requires a non-zero bound");
+    new_channel(Some(bound))
+}
+
+fn new_channel<T>(cap: Option<usize>) -> (Sender<T>, Receiver<T>) {
+    let packet = Arc::new(Packet {
+        state: Mutex::new(State {
+            buf: VecDeque::new(),
+            cap,
+            senders: 1,
+            receivers: 1,
+            send_reserved: 0,
+            recv_reserved: 0,
+            send_waiters: Waiters::new(),
+            recv_waiters: Waiters::new(),
+        }),
+    });
+    (Sender { inner: packet.clone() }, Receiver { inner: packet })
+}
+
+/// The sending half of a multi-consumer channel.
+///
+/// Senders can be cloned to send to the same channel from multiple threads.
+/// The channel is disconnected once every [`Receiver`] has been dropped.
+///
+/// [`Receiver`]: struct.Receiver.html
+pub struct Sender<T> {
+    inner: Arc<Packet<T>>,
+}
+
+/// The receiving half of a multi-consumer channel.
+///
+/// Receivers can be cloned to receive from the same channel on multiple
+/// threads; each message is delivered to exactly one of them. The channel is
+/// disconnected once every [`Sender`] has been dropped and the buffer has
+/// been drained.
+///
+/// [`Sender`]: struct.Sender.html
+pub struct Receiver<T> {
+    inner: Arc<Packet<T>>,
+}
+
+/// An iterator over messages on a [`Receiver`], created by [`iter`].
+///
+/// [`Receiver`]: struct.Receiver.html
+/// [`iter`]: struct.Receiver.html#method.iter
+#[derive(Debug)]
+pub struct Iter<'a, T: 'a> {
+    rx: &'a Receiver<T>
+}
+
+/// An iterator that attempts to yield all pending values for a [`Receiver`],
+/// created by [`try_iter`].
+///
+/// [`Receiver`]: struct.Receiver.html
+/// [`try_iter`]: struct.Receiver.html#method.try_iter
+#[derive(Debug)]
+pub struct TryIter<'a, T: 'a> {
+    rx: &'a Receiver<T>
+}
+
+/// An owning iterator over messages on a [`Receiver`], created by
+/// **into_iter**.
+///
+/// [`Receiver`]: struct.Receiver.html
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    rx: Receiver<T>
+}
+
+/// The two kinds of operation that can be waited on through a `Select`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Operation {
+    Recv,
+    Send,
+}
+
+struct Packet<T> {
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    buf: VecDeque<T>,
+    cap: Option<usize>,
+    senders: usize,
+    receivers: usize,
+
+    /// Buffer slots promised to senders that won a `Select`, but have not
+    /// sent yet.
+    send_reserved: usize,
+    /// Buffered messages promised to receivers that won a `Select`, but have
+    /// not received yet.
+    recv_reserved: usize,
+
+    send_waiters: Waiters,
+    recv_waiters: Waiters,
+}
+
+/// Threads blocked until an operation on the channel might succeed.
+///
+/// A thread blocked in a plain `send` or `recv` always completes its
+/// operation on this channel once it can, so a single one of them is woken
+/// per event. A selecting thread registers the same token with several
+/// channels and may end up completing some other operation, so every one of
+/// those is woken and each retries its operations.
+struct Waiters {
+    next_id: usize,
+    blocked: VecDeque<(usize, SignalToken)>,
+    selecting: Vec<(usize, SignalToken)>,
+}
+
+impl Waiters {
+    fn new() -> Waiters {
+        Waiters { next_id: 0, blocked: VecDeque::new(), selecting: Vec::new() }
+    }
+
+    fn next_id(&mut self) -> usize {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        id
+    }
+
+    /// Registers a thread blocked in a plain `send` or `recv`.
+    fn register(&mut self, token: SignalToken) -> usize {
+        let id = self.next_id();
+        self.blocked.push_back((id, token));
+        id
+    }
+
+    /// Registers a thread blocked in a `Select`.
+    fn register_select(&mut self, token: SignalToken) -> usize {
+        let id = self.next_id();
+        self.selecting.push((id, token));
+        id
+    }
+
+    fn unregister(&mut self, id: usize) {
+        self.blocked.retain(|&(i, _)| i != id);
+        self.selecting.retain(|&(i, _)| i != id);
+    }
+
+    /// Wakes the threads that could use a single message or buffer slot.
+    fn notify(&mut self) {
+        if let Some((_, token)) = self.blocked.pop_front() {
+            token.signal();
+        }
+        for (_, token) in self.selecting.drain(..) {
+            token.signal();
+        }
+    }
+
+    /// Wakes every thread, e.g. once the channel is disconnected.
+    fn notify_all(&mut self) {
+        for (_, token) in self.blocked.drain(..) {
+            token.signal();
+        }
+        for (_, token) in self.selecting.drain(..) {
+            token.signal();
+        }
+    }
+}
+
+impl<T> State<T> {
+    fn try_send(&mut self, t: T) -> Result<(), TrySendError<T>> {
+        if self.receivers == 0 {
+            return Err(TrySendError::Disconnected(t));
+        }
+        if !self.has_room() {
+            return Err(TrySendError::Full(t));
+        }
+        self.buf.push_back(t);
+        self.recv_waiters.notify();
+        Ok(())
+    }
+
+    fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        if self.buf.len() > self.recv_reserved {
+            let t = self.buf.pop_front().unwrap();
+            self.send_waiters.notify();
+            Ok(t)
+        } else if self.senders == 0 {
+            Err(TryRecvError::Disconnected)
+        } else {
+            Err(TryRecvError::Empty)
+        }
+    }
+
+    fn has_room(&self) -> bool {
+        match self.cap {
+            Some(cap) => self.buf.len() + self.send_reserved < cap,
+            None => true,
+        }
+    }
+
+    /// Checks whether `op` can complete without blocking. If so, returns
+    /// `Some(true)` after reserving the slot or message it needs, or
+    /// `Some(false)` if the channel is disconnected.
+    fn try_reserve(&mut self, op: Operation) -> Option<bool> {
+        match op {
+            Operation::Recv => {
+                if self.buf.len() > self.recv_reserved {
+                    self.recv_reserved += 1;
+                    Some(true)
+                } else if self.senders == 0 {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+            Operation::Send => {
+                if self.receivers == 0 {
+                    Some(false)
+                } else if self.has_room() {
+                    self.send_reserved += 1;
+                    Some(true)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn waiters(&mut self, op: Operation) -> &mut Waiters {
+        match op {
+            Operation::Recv => &mut self.recv_waiters,
+            Operation::Send => &mut self.send_waiters,
+        }
+    }
+}
+
+impl<T> Packet<T> {
+    fn lock(&self) -> MutexGuard<State<T>> {
+        self.state.lock().unwrap()
+    }
+
+    fn send(&self, t: T) -> Result<(), SendError<T>> {
+        let mut t = t;
+        loop {
+            let (wait_token, signal_token) = blocking::tokens();
+            {
+                let mut state = self.lock();
+                match state.try_send(t) {
+                    Ok(()) => return Ok(()),
+                    Err(TrySendError::Disconnected(t)) => return Err(SendError(t)),
+                    Err(TrySendError::Full(back)) => {
+                        t = back;
+                        state.send_waiters.register(signal_token);
+                    }
+                }
+            }
+            wait_token.wait();
+        }
+    }
+
+    fn recv(&self, deadline: Option<Instant>) -> Result<T, RecvTimeoutError> {
+        loop {
+            let (wait_token, signal_token) = blocking::tokens();
+            let id = {
+                let mut state = self.lock();
+                match state.try_recv() {
+                    Ok(t) => return Ok(t),
+                    Err(TryRecvError::Disconnected) => return Err(RecvTimeoutError::Disconnected),
+                    Err(TryRecvError::Empty) => state.recv_waiters.register(signal_token),
+                }
+            };
+            if let Some(deadline) = deadline {
+                if !wait_token.wait_max_until(deadline) {
+                    let mut state = self.lock();
+                    state.recv_waiters.unregister(id);
+                    return state.try_recv().map_err(|e| match e {
+                        TryRecvError::Empty => RecvTimeoutError::Timeout,
+                        TryRecvError::Disconnected => RecvTimeoutError::Disconnected,
+                    });
+                }
+            } else {
+                wait_token.wait();
+            }
+        }
+    }
+}
+
+/// The type-erased view of a channel that `Select` works with.
+trait Selectable {
+    /// Reserves `op` if it can complete without blocking; see
+    /// `State::try_reserve`.
+    fn try_reserve(&self, op: Operation) -> Option<bool>;
+
+    /// Like `try_reserve`, but registers `token` to be signaled once `op`
+    /// might be able to proceed if it can't right now. Returns the id to
+    /// unregister the token with.
+    fn register(&self, op: Operation, token: SignalToken) -> Result<usize, bool>;
+
+    fn unregister(&self, op: Operation, id: usize);
+
+    /// Gives back a reservation that was never used.
+    fn release(&self, op: Operation);
+
+    /// Identifies the channel, to check that a `SelectedOperation` is
+    /// completed on the channel it was selected from.
+    fn addr(&self) -> usize;
+}
+
+impl<T> Selectable for Packet<T> {
+    fn try_reserve(&self, op: Operation) -> Option<bool> {
+        self.lock().try_reserve(op)
+    }
+
+    fn register(&self, op: Operation, token: SignalToken) -> Result<usize, bool> {
+        let mut state = self.lock();
+        match state.try_reserve(op) {
+            Some(reserved) => Err(reserved),
+            None => Ok(state.waiters(op).register_select(token)),
+        }
+    }
+
+    fn unregister(&self, op: Operation, id: usize) {
+        self.lock().waiters(op).unregister(id);
+    }
+
+    fn release(&self, op: Operation) {
+        let mut state = self.lock();
+        match op {
+            Operation::Recv => state.recv_reserved -= 1,
+            Operation::Send => state.send_reserved -= 1,
+        }
+        state.waiters(op).notify();
+    }
+
+    fn addr(&self) -> usize {
+        self as *const Packet<T> as usize
+    }
+}
+
+impl<T> Sender<T> {
+    /// Sends a value on this channel, blocking while a bounded channel is
+    /// full.
+    ///
+    /// An error is returned, handing back the value, if every [`Receiver`]
+    /// has been dropped.
+    ///
+    /// [`Receiver`]: struct.Receiver.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    /// use std::sync::mpsc::mpmc;
+    ///
+    /// let (tx, rx) = mpmc::channel();
+    /// tx.send(1).unwrap();
+    ///
+    /// drop(rx);
+    /// assert_eq!(tx.send(2).unwrap_err().0, 2);
+    /// ```
+    pub fn send(&self, t: T) -> Result<(), SendError<T>> {
+        self.inner.send(t)
+    }
+
+    /// Attempts to send a value on this channel without blocking.
+    ///
+    /// This fails with [`TrySendError::Full`] if a bounded channel has no
+    /// room for the value, or with [`TrySendError::Disconnected`] if every
+    /// [`Receiver`] has been dropped.
+    ///
+    /// [`TrySendError::Full`]: ../enum.TrySendError.html#variant.Full
+    /// [`TrySendError::Disconnected`]: ../enum.TrySendError.html#variant.Disconnected
+    /// [`Receiver`]: struct.Receiver.html
+    pub fn try_send(&self, t: T) -> Result<(), TrySendError<T>> {
+        self.inner.lock().try_send(t)
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        self.inner.lock().senders += 1;
+        Sender { inner: self.inner.clone() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            state.recv_waiters.notify_all();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Sender").finish()
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Attempts to receive a value from this channel without blocking.
+    ///
+    /// This fails with [`TryRecvError::Empty`] if no value is available right
+    /// now, or with [`TryRecvError::Disconnected`] if the channel is empty and
+    /// every [`Sender`] has been dropped.
+    ///
+    /// [`TryRecvError::Empty`]: ../enum.TryRecvError.html#variant.Empty
+    /// [`TryRecvError::Disconnected`]: ../enum.TryRecvError.html#variant.Disconnected
+    /// [`Sender`]: struct.Sender.html
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.inner.lock().try_recv()
+    }
+
+    /// Blocks until a value is available on this channel, and returns it.
+    ///
+    /// An error is returned once the channel is empty and every [`Sender`]
+    /// has been dropped.
+    ///
+    /// [`Sender`]: struct.Sender.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    /// use std::sync::mpsc::{mpmc, RecvError};
+    /// use std::thread;
+    ///
+    /// let (tx, rx) = mpmc::channel();
+    /// let handle = thread::spawn(move || {
+    ///     tx.send(1u8).unwrap();
+    /// });
+    ///
+    /// handle.join().unwrap();
+    ///
+    /// assert_eq!(Ok(1), rx.recv());
+    /// assert_eq!(Err(RecvError), rx.recv());
+    /// ```
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.inner.recv(None).map_err(|_| RecvError)
+    }
+
+    /// Blocks for at most `timeout` waiting for a value on this channel.
+    ///
+    /// Errors are returned as with [`recv`], or when the timeout elapses.
+    ///
+    /// [`recv`]: struct.Receiver.html#method.recv
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        self.inner.recv(Some(Instant::now() + timeout))
+    }
+
+    /// Returns an iterator that blocks waiting for messages, and stops once
+    /// the channel is disconnected.
+    pub fn iter(&self) -> Iter<T> {
+        Iter { rx: self }
+    }
+
+    /// Returns an iterator over the messages that are available right now,
+    /// without blocking.
+    pub fn try_iter(&self) -> TryIter<T> {
+        TryIter { rx: self }
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        self.inner.lock().receivers += 1;
+        Receiver { inner: self.inner.clone() }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.lock();
+        state.receivers -= 1;
+        if state.receivers == 0 {
+            state.send_waiters.notify_all();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Receiver").finish()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> { self.rx.recv().ok() }
+}
+
+impl<'a, T> Iterator for TryIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> { self.rx.try_recv().ok() }
+}
+
+impl<'a, T> IntoIterator for &'a Receiver<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> { self.iter() }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> { self.rx.recv().ok() }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { rx: self }
+    }
+}
+
+/// Waits on a set of channel operations at once.
+///
+/// Receive operations are added with [`recv`] and send operations with
+/// [`send`]; each returns the index that identifies the operation. Waiting
+/// with [`select`], [`try_select`] or [`select_timeout`] hands back a
+/// [`SelectedOperation`] that must then be completed by calling its `recv` or
+/// `send` method with the same channel. The operation is guaranteed not to
+/// block at that point: the message or buffer slot it needs has been set
+/// aside for it.
+///
+/// When several operations are ready, the one that was added first wins.
+///
+/// [`recv`]: #method.recv
+/// [`send`]: #method.send
+/// [`select`]: #method.select
+/// [`try_select`]: #method.try_select
+/// [`select_timeout`]: #method.select_timeout
+/// [`SelectedOperation`]: struct.SelectedOperation.html
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+/// use std::sync::mpsc::mpmc::{self, Select};
+///
+/// let (tx1, rx1) = mpmc::channel();
+/// let (tx2, rx2) = mpmc::sync_channel(1);
+/// tx1.send("hello").unwrap();
+///
+/// let mut sel = Select::new();
+/// let recv1 = sel.recv(&rx1);
+/// let send2 = sel.send(&tx2);
+///
+/// let oper = sel.select();
+/// match oper.index() {
+///     i if i == recv1 => assert_eq!(oper.recv(&rx1), Ok("hello")),
+///     i if i == send2 => oper.send(&tx2, "world").unwrap(),
+///     _ => unreachable!(),
+/// }
+/// # drop(rx2);
+/// ```
+pub struct Select<'a> {
+    handles: Vec<(&'a (Selectable + 'a), Operation)>,
+}
+
+/// An operation picked by a [`Select`], which must be completed with its
+/// [`recv`] or [`send`] method.
+///
+/// Dropping a `SelectedOperation` without completing it gives the message or
+/// buffer slot it had set aside back to the channel.
+///
+/// [`Select`]: struct.Select.html
+/// [`recv`]: #method.recv
+/// [`send`]: #method.send
+#[must_use = "a selected operation should be completed with `recv` or `send`"]
+pub struct SelectedOperation<'a> {
+    index: usize,
+    chan: &'a (Selectable + 'a),
+    op: Operation,
+    /// Whether a message or slot was set aside. If not, the channel was
+    /// disconnected.
+    reserved: bool,
+    completed: bool,
+}
+
+/// An error returned from [`Select::try_select`] when no operation is ready.
+///
+/// [`Select::try_select`]: struct.Select.html#method.try_select
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct TrySelectError;
+
+/// An error returned from [`Select::select_timeout`] when no operation became
+/// ready before the timeout elapsed.
+///
+/// [`Select::select_timeout`]: struct.Select.html#method.select_timeout
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct SelectTimeoutError;
+
+impl<'a> Select<'a> {
+    /// Creates an empty set of operations.
+    pub fn new() -> Select<'a> {
+        Select { handles: Vec::new() }
+    }
+
+    /// Adds a receive operation on `r`, returning its index.
+    pub fn recv<T>(&mut self, r: &'a Receiver<T>) -> usize {
+        self.handles.push((&*r.inner, Operation::Recv));
+        self.handles.len() - 1
+    }
+
+    /// Adds a send operation on `s`, returning its index.
+    pub fn send<T>(&mut self, s: &'a Sender<T>) -> usize {
+        self.handles.push((&*s.inner, Operation::Send));
+        self.handles.len() - 1
+    }
+
+    /// Blocks until one of the operations can proceed.
+    ///
+    /// An operation on a disconnected channel counts as ready; completing it
+    /// returns an error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no operations have been added, as this would block forever.
+    pub fn select(&mut self) -> SelectedOperation<'a> {
+        assert!(!self.handles.is_empty(), "no operations have been added to `Select`");
+        self.wait(None).unwrap()
+    }
+
+    /// Picks one of the operations if it can proceed right now.
+    pub fn try_select(&mut self) -> Result<SelectedOperation<'a>, TrySelectError> {
+        self.try_reserve().ok_or(TrySelectError)
+    }
+
+    /// Blocks for at most `timeout` until one of the operations can proceed.
+    pub fn select_timeout(&mut self, timeout: Duration)
+                          -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        self.wait(Some(Instant::now() + timeout)).ok_or(SelectTimeoutError)
+    }
+
+    fn try_reserve(&self) -> Option<SelectedOperation<'a>> {
+        for (index, &(chan, op)) in self.handles.iter().enumerate() {
+            if let Some(reserved) = chan.try_reserve(op) {
+                return Some(SelectedOperation { index, chan, op, reserved, completed: false });
+            }
+        }
+        None
+    }
+
+    fn wait(&self, deadline: Option<Instant>) -> Option<SelectedOperation<'a>> {
+        loop {
+            if let Some(oper) = self.try_reserve() {
+                return Some(oper);
+            }
+
+            // Register with every channel, checking each one again under its
+            // lock so that no wakeup is missed between the attempt above and
+            // going to sleep.
+            let (wait_token, signal_token) = blocking::tokens();
+            let mut registered = Vec::with_capacity(self.handles.len());
+            let mut selected = None;
+            for (index, &(chan, op)) in self.handles.iter().enumerate() {
+                match chan.register(op, signal_token.clone()) {
+                    Ok(id) => registered.push((chan, op, id)),
+                    Err(reserved) => {
+                        selected = Some(SelectedOperation {
+                            index, chan, op, reserved, completed: false,
+                        });
+                        break;
+                    }
+                }
+            }
+
+            let woken = match (&selected, deadline) {
+                (&Some(_), _) => true,
+                (&None, Some(deadline)) => wait_token.wait_max_until(deadline),
+                (&None, None) => { wait_token.wait(); true }
+            };
+
+            for (chan, op, id) in registered {
+                chan.unregister(op, id);
+            }
+
+            if selected.is_some() {
+                return selected;
+            }
+            if !woken {
+                return self.try_reserve();
+            }
+        }
+    }
+}
+
+impl<'a> fmt::Debug for Select<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Select").finish()
+    }
+}
+
+impl<'a> SelectedOperation<'a> {
+    /// Returns the index of the operation that was selected, as returned when
+    /// it was added to the `Select`.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Completes a selected receive operation.
+    ///
+    /// An error is returned if the channel was disconnected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r` is not the receiver that the selected operation was
+    /// added with.
+    pub fn recv<T>(mut self, r: &Receiver<T>) -> Result<T, RecvError> {
+        assert!(self.op == Operation::Recv && self.chan.addr() == r.inner.addr(),
+                "passed a receiver that wasn't selected");
+        self.completed = true;
+        if !self.reserved {
+            return Err(RecvError);
+        }
+        let mut state = r.inner.lock();
+        state.recv_reserved -= 1;
+        let t = state.buf.pop_front().unwrap();
+        state.send_waiters.notify();
+        Ok(t)
+    }
+
+    /// Completes a selected send operation.
+    ///
+    /// An error is returned, handing back the value, if the channel was
+    /// disconnected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not the sender that the selected operation was added
+    /// with.
+    pub fn send<T>(mut self, s: &Sender<T>, t: T) -> Result<(), SendError<T>> {
+        assert!(self.op == Operation::Send && self.chan.addr() == s.inner.addr(),
+                "passed a sender that wasn't selected");
+        self.completed = true;
+        if !self.reserved {
+            return Err(SendError(t));
+        }
+        let mut state = s.inner.lock();
+        state.send_reserved -= 1;
+        if state.receivers == 0 {
+            return Err(SendError(t));
+        }
+        state.buf.push_back(t);
+        state.recv_waiters.notify();
+        Ok(())
+    }
+}
+
+impl<'a> Drop for SelectedOperation<'a> {
+    fn drop(&mut self) {
+        if self.reserved && !self.completed {
+            self.chan.release(self.op);
+        }
+    }
+}
+
+impl<'a> fmt::Debug for SelectedOperation<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SelectedOperation")
+         .field("index", &self.index)
+         .finish()
+    }
+}
+
+impl fmt::Display for TrySelectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "all operations in select would block".fmt(f)
+    }
+}
+
+impl error::Error for TrySelectError {
+    fn description(&self) -> &str {
+        "all operations in select would block"
+    }
+}
+
+impl fmt::Display for SelectTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "timed out waiting on select".fmt(f)
+    }
+}
+
+impl error::Error for SelectTimeoutError {
+    fn description(&self) -> &str {
+        "timed out waiting on select"
+    }
+}
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests {
+    use sync::mpsc::mpmc::*;
+    use sync::mpsc::{RecvError, RecvTimeoutError, TryRecvError, TrySendError};
+    use thread;
+    use time::Duration;
+
+    #[test]
+    fn smoke() {
+        let (tx, rx) = channel::<i32>();
+        tx.send(1).unwrap();
+        assert_eq!(rx.recv().unwrap(), 1);
+    }
+
+    #[test]
+    fn drop_senders() {
+        let (tx, rx) = channel::<i32>();
+        let tx2 = tx.clone();
+        tx.send(1).unwrap();
+        drop(tx);
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+        drop(tx2);
+        assert_eq!(rx.recv(), Err(RecvError));
+    }
+
+    #[test]
+    fn drop_receivers() {
+        let (tx, rx) = sync_channel::<i32>(1);
+        let rx2 = rx.clone();
+        drop(rx);
+        tx.send(1).unwrap();
+        drop(rx2);
+        assert_eq!(tx.try_send(2), Err(TrySendError::Disconnected(2)));
+        assert!(tx.send(3).is_err());
+    }
+
+    #[test]
+    fn bounded_full() {
+        let (tx, rx) = sync_channel::<i32>(2);
+        tx.send(1).unwrap();
+        tx.try_send(2).unwrap();
+        assert_eq!(tx.try_send(3), Err(TrySendError::Full(3)));
+        assert_eq!(rx.recv(), Ok(1));
+        tx.try_send(3).unwrap();
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    fn blocked_sender_is_woken() {
+        let (tx, rx) = sync_channel::<i32>(1);
+        tx.send(1).unwrap();
+        let t = thread::spawn(move || {
+            tx.send(2).unwrap();
+        });
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.recv(), Ok(2));
+        t.join().unwrap();
+        assert_eq!(rx.recv(), Err(RecvError));
+    }
+
+    #[test]
+    fn multiple_consumers() {
+        const AMT: usize = 10000;
+        const NCONSUMERS: usize = 4;
+        let (tx, rx) = sync_channel::<usize>(10);
+        let consumers = (0..NCONSUMERS).map(|_| {
+            let rx = rx.clone();
+            thread::spawn(move || rx.iter().collect::<Vec<_>>())
+        }).collect::<Vec<_>>();
+        drop(rx);
+
+        let producers = (0..2).map(|i| {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for n in 0..AMT {
+                    if n % 2 == i {
+                        tx.send(n).unwrap();
+                    }
+                }
+            })
+        }).collect::<Vec<_>>();
+        drop(tx);
+        for p in producers {
+            p.join().unwrap();
+        }
+
+        let mut all = consumers.into_iter()
+                               .flat_map(|c| c.join().unwrap())
+                               .collect::<Vec<_>>();
+        all.sort();
+        assert_eq!(all, (0..AMT).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn recv_timeout() {
+        let (tx, rx) = channel::<i32>();
+        assert_eq!(rx.recv_timeout(Duration::from_millis(1)), Err(RecvTimeoutError::Timeout));
+        tx.send(1).unwrap();
+        assert_eq!(rx.recv_timeout(Duration::from_millis(1)), Ok(1));
+        drop(tx);
+        assert_eq!(rx.recv_timeout(Duration::from_millis(1)),
+                   Err(RecvTimeoutError::Disconnected));
+    }
+
+    #[test]
+    fn select_recv() {
+        let (tx1, rx1) = channel::<i32>();
+        let (tx2, rx2) = channel::<i32>();
+        let t = thread::spawn(move || {
+            tx2.send(2).unwrap();
+        });
+
+        let mut sel = Select::new();
+        let i1 = sel.recv(&rx1);
+        let i2 = sel.recv(&rx2);
+        let oper = sel.select();
+        assert_eq!(oper.index(), i2);
+        assert!(oper.index() != i1);
+        assert_eq!(oper.recv(&rx2), Ok(2));
+        t.join().unwrap();
+        drop(tx1);
+    }
+
+    #[test]
+    fn select_send() {
+        let (tx1, _rx1) = sync_channel::<i32>(1);
+        let (tx2, rx2) = sync_channel::<i32>(1);
+        tx1.send(1).unwrap();
+
+        let mut sel = Select::new();
+        sel.send(&tx1);
+        let i2 = sel.send(&tx2);
+        let oper = sel.try_select().unwrap();
+        assert_eq!(oper.index(), i2);
+        oper.send(&tx2, 2).unwrap();
+        assert_eq!(rx2.recv(), Ok(2));
+    }
+
+    #[test]
+    fn select_and_recv_share_messages() {
+        let (tx, rx) = channel::<i32>();
+        let rx2 = rx.clone();
+        let receiver = thread::spawn(move || rx2.recv().unwrap());
+        let selector = thread::spawn(move || {
+            let mut sel = Select::new();
+            sel.recv(&rx);
+            sel.select().recv(&rx).unwrap()
+        });
+
+        thread::sleep(Duration::from_millis(10));
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        let mut got = vec![receiver.join().unwrap(), selector.join().unwrap()];
+        got.sort();
+        assert_eq!(got, vec![1, 2]);
+    }
+
+    #[test]
+    fn select_timeout() {
+        let (_tx, rx) = channel::<i32>();
+        let (tx2, _rx2) = sync_channel::<i32>(1);
+        tx2.send(1).unwrap();
+
+        let mut sel = Select::new();
+        sel.recv(&rx);
+        sel.send(&tx2);
+        assert_eq!(sel.try_select().unwrap_err(), TrySelectError);
+        assert_eq!(sel.select_timeout(Duration::from_millis(1)).unwrap_err(),
+                   SelectTimeoutError);
+    }
+
+    #[test]
+    fn select_disconnected() {
+        let (tx, rx) = channel::<i32>();
+        drop(tx);
+        let mut sel = Select::new();
+        sel.recv(&rx);
+        assert_eq!(sel.select().recv(&rx), Err(RecvError));
+    }
+
+    #[test]
+    fn select_drop_releases() {
+        let (tx, rx) = sync_channel::<i32>(1);
+        {
+            let mut sel = Select::new();
+            sel.send(&tx);
+            let _oper = sel.select();
+            assert_eq!(tx.try_send(1), Err(TrySendError::Full(1)));
+        }
+        tx.try_send(1).unwrap();
+
+        {
+            let mut sel = Select::new();
+            sel.recv(&rx);
+            let _oper = sel.select();
+            assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+        }
+        assert_eq!(rx.try_recv(), Ok(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn select_wrong_channel() {
+        let (tx, rx1) = channel::<i32>();
+        let (_tx2, rx2) = channel::<i32>();
+        tx.send(1).unwrap();
+        let mut sel = Select::new();
+        sel.recv(&rx1);
+        let _ = sel.select().recv(&rx2);
+    }
+
+    #[test]
+    fn select_macro() {
+        let (tx1, rx1) = channel::<i32>();
+        let (tx2, rx2) = sync_channel::<i32>(1);
+
+        let got = select! {
+            recv(rx1) -> _ => 1,
+            send(tx2, 5) -> res => { res.unwrap(); 2 }
+        };
+        assert_eq!(got, 2);
+        assert_eq!(rx2.recv(), Ok(5));
+
+        tx1.send(3).unwrap();
+        let got = select! {
+            recv(rx1) -> msg => msg.unwrap(),
+            recv(rx2) -> _ => 0,
+        };
+        assert_eq!(got, 3);
+
+        let got = select! {
+            recv(rx1) -> _ => false,
+            default => true,
+        };
+        assert!(got);
+
+        let got = select! {
+            recv(rx1) -> _ => { false }
+            default(Duration::from_millis(1)) => true
+        };
+        assert!(got);
+    }
+}
@@ -13,6 +13,7 @@ use io;
 pub mod args;
 pub mod env;
 pub mod fs;
+pub use sys_common::iovec;
 pub mod net;
 #[path = "../../unix/path.rs"]
 pub mod path;
@@ -8,8 +8,9 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use fmt;
 use fs::File;
-use io::{Error, Result, Read};
+use io::{Error, ErrorKind, Result, Read};
 use iter::Iterator;
 use net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use str::FromStr;
@@ -29,6 +30,76 @@ mod dns;
 mod tcp;
 mod udp;
 
+enum Void {}
+
+// Redox opens sockets and binds or connects them in one step, so there's no
+// way to configure a socket beforehand.
+pub struct SocketBuilder(Void);
+
+impl SocketBuilder {
+    pub fn new_tcp(_: bool) -> Result<SocketBuilder> {
+        Err(Error::new(ErrorKind::Other, "SocketBuilder not implemented"))
+    }
+
+    pub fn new_udp(_: bool) -> Result<SocketBuilder> {
+        Err(Error::new(ErrorKind::Other, "SocketBuilder not implemented"))
+    }
+
+    pub fn set_reuse_address(&self, _: bool) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn set_reuse_port(&self, _: bool) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn set_keepalive(&self, _: Option<Duration>) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn set_ttl(&self, _: u32) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn set_only_v6(&self, _: bool) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn bind_device(&self, _: &str) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn bind(&self, _: &SocketAddr) -> Result<()> {
+        match self.0 {}
+    }
+
+    pub fn listen(self, _: i32) -> Result<TcpListener> {
+        match self.0 {}
+    }
+
+    pub fn connect(self, _: &SocketAddr) -> Result<TcpStream> {
+        match self.0 {}
+    }
+
+    pub fn into_udp(self) -> UdpSocket {
+        match self.0 {}
+    }
+}
+
+impl fmt::Debug for SocketBuilder {
+    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {}
+    }
+}
+
 pub struct LookupHost(IntoIter<SocketAddr>);
 
 impl Iterator for LookupHost {
@@ -18,7 +18,8 @@ use sys::fd::FileDesc;
 use sys::fs::{File, OpenOptions};
 use sys::pipe::{self, AnonPipe};
 use sys::{cvt, syscall};
-use sys_common::process::{CommandEnv, DefaultEnvKey};
+use sys_common::process::{self, CommandEnv, DefaultEnvKey};
+use time::Duration;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Command
@@ -527,4 +528,8 @@ impl Process {
             Ok(Some(ExitStatus(status as i32)))
         }
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        process::wait_timeout_by_polling(timeout, || self.try_wait())
+    }
 }
@@ -91,6 +91,65 @@ pub trait CommandExt {
     /// cross-platform `spawn` instead.
     #[stable(feature = "process_exec2", since = "1.9.0")]
     fn exec(&mut self) -> io::Error;
+
+    /// Runs the child process in a new session, translating to a `setsid`
+    /// call in the child process.
+    ///
+    /// The child becomes the leader of both a new session and a new process
+    /// group, and has no controlling terminal. When this is set, any process
+    /// group requested with [`pgroup`] is ignored.
+    ///
+    /// [`pgroup`]: #tymethod.pgroup
+    #[unstable(feature = "process_unix_ext", issue = "0")]
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command;
+
+    /// Moves the child process into the process group `pgroup`, translating
+    /// to a `setpgid(0, pgroup)` call in the child process.
+    ///
+    /// A `pgroup` of 0 creates a new process group whose id is the child's
+    /// process id. Failure in the `setpgid` call will cause the spawn to
+    /// fail.
+    #[unstable(feature = "process_unix_ext", issue = "0")]
+    fn pgroup(&mut self, pgroup: i32) -> &mut process::Command;
+
+    /// Sets a resource limit of the child process, translating to a
+    /// `setrlimit` call in the child process.
+    ///
+    /// `resource` is one of the `RLIMIT_*` constants, and `soft` and `hard`
+    /// are the soft and hard limits to set; `RLIM_INFINITY` removes a limit.
+    /// Setting the same resource more than once keeps the last limits.
+    /// Limits are set before the [`uid`] and [`gid`] are changed, so a
+    /// privileged parent may raise the hard limits of an unprivileged child.
+    /// Failure in the `setrlimit` call will cause the spawn to fail.
+    ///
+    /// [`uid`]: #tymethod.uid
+    /// [`gid`]: #tymethod.gid
+    #[unstable(feature = "process_unix_ext", issue = "0")]
+    fn rlimit(&mut self, resource: i32, soft: u64, hard: u64) -> &mut process::Command;
+
+    /// Asks for the child process to be sent `signal` when its parent dies,
+    /// translating to a `prctl(PR_SET_PDEATHSIG, signal)` call in the child
+    /// process.
+    ///
+    /// # Notes
+    ///
+    /// Linux considers the "parent" to be the thread that spawned the child,
+    /// so the signal is also sent if that thread exits while the rest of the
+    /// process keeps running.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[unstable(feature = "process_unix_ext", issue = "0")]
+    fn parent_death_signal(&mut self, signal: i32) -> &mut process::Command;
+
+    /// Passes the file descriptor `parent` of this process to the child
+    /// process as the descriptor numbered `child`.
+    ///
+    /// The descriptor is duplicated in the child process, so it is unaffected
+    /// by `CLOEXEC` and stays open in this process. It must remain open until
+    /// the child is spawned, or the spawn will fail. Mapping the same `child`
+    /// descriptor more than once keeps the last mapping, and mapping one of
+    /// the stdio descriptors overrides the corresponding stdio configuration.
+    #[unstable(feature = "process_unix_ext", issue = "0")]
+    fn inherit_fd(&mut self, parent: RawFd, child: RawFd) -> &mut process::Command;
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -115,6 +174,32 @@ impl CommandExt for process::Command {
     fn exec(&mut self) -> io::Error {
         self.as_inner_mut().exec(sys::process::Stdio::Inherit)
     }
+
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command {
+        self.as_inner_mut().setsid(setsid);
+        self
+    }
+
+    fn pgroup(&mut self, pgroup: i32) -> &mut process::Command {
+        self.as_inner_mut().pgroup(pgroup);
+        self
+    }
+
+    fn rlimit(&mut self, resource: i32, soft: u64, hard: u64) -> &mut process::Command {
+        self.as_inner_mut().rlimit(resource, soft, hard);
+        self
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn parent_death_signal(&mut self, signal: i32) -> &mut process::Command {
+        self.as_inner_mut().pdeathsig(signal);
+        self
+    }
+
+    fn inherit_fd(&mut self, parent: RawFd, child: RawFd) -> &mut process::Command {
+        self.as_inner_mut().inherit_fd(parent, child);
+        self
+    }
 }
 
 /// Unix-specific extensions to `std::process::ExitStatus`
@@ -12,7 +12,7 @@ use os::unix::prelude::*;
 
 use ffi::{CString, CStr, OsString, OsStr};
 use fmt;
-use io::{self, Error, ErrorKind, SeekFrom};
+use io::{self, Error, ErrorKind, IoSlice, IoSliceMut, SeekFrom};
 use libc::{self, c_int, mode_t};
 use mem;
 use path::{Path, PathBuf};
@@ -496,6 +496,10 @@ impl File {
         self.0.read(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     pub fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
         self.0.read_at(buf, offset)
     }
@@ -504,6 +508,10 @@ impl File {
         self.0.write(buf)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     pub fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
         self.0.write_at(buf, offset)
     }
@@ -762,15 +770,40 @@ pub fn canonicalize(p: &Path) -> io::Result<PathBuf> {
 }
 
 pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
-    use fs::{File, set_permissions};
+    use fs::{File, OpenOptions, set_permissions};
     if !from.is_file() {
         return Err(Error::new(ErrorKind::InvalidInput,
                               "the source path is not an existing regular file"))
     }
 
     let mut reader = File::open(from)?;
-    let mut writer = File::create(to)?;
-    let perm = reader.metadata()?.permissions();
+    // `to` is only truncated once it is known not to be `from` itself, whose
+    // contents would otherwise be gone before they are read.
+    let mut writer = OpenOptions::new().write(true).create(true).open(to)?;
+    let reader_attr = reader.as_inner().file_attr()?;
+    let writer_attr = writer.as_inner().file_attr()?;
+    if reader_attr.stat.st_dev == writer_attr.stat.st_dev &&
+       reader_attr.stat.st_ino == writer_attr.stat.st_ino {
+        return Err(Error::new(ErrorKind::InvalidInput,
+                              "the source and destination are the same file"))
+    }
+    writer.set_len(0)?;
+    let metadata = reader.metadata()?;
+    let perm = metadata.permissions();
+
+    // Where the filesystem supports it, share the data with a reflink rather
+    // than copying it. Otherwise `io::copy` copies between the two files
+    // inside the kernel where it can.
+    #[cfg(target_os = "linux")]
+    {
+        use sys::kernel_copy;
+
+        if kernel_copy::clone_file(reader.as_inner().fd().raw(),
+                                   writer.as_inner().fd().raw()) {
+            set_permissions(to, perm)?;
+            return Ok(metadata.len())
+        }
+    }
 
     let ret = io::copy(&mut reader, &mut writer)?;
     set_permissions(to, perm)?;
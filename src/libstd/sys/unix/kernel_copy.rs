@@ -0,0 +1,218 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Copying data between file descriptors without going through userspace,
+//! used by `fs::copy` and `io::copy` on Linux.
+//!
+//! Each of these functions either finishes the copy, fails after some data
+//! has been transferred, or reports that the kernel cannot do this copy, in
+//! which case the caller falls back to a `read`/`write` loop for the rest.
+
+use cmp;
+use io;
+use libc::{self, c_int, c_void};
+use ptr;
+use sync::atomic::{AtomicBool, Ordering};
+use sys::cvt;
+use sys::fd::FileDesc;
+use sys::pipe::anon_pipe;
+
+// The most Linux transfers in a single read-like or write-like syscall.
+const MAX_CHUNK: usize = 0x7fff_f000;
+
+// `FICLONE` from `linux/fs.h`, i.e. `_IOW(0x94, 9, int)`. The direction bits
+// of `_IOW` differ between architectures.
+#[cfg(not(any(target_arch = "powerpc",
+              target_arch = "powerpc64",
+              target_arch = "mips",
+              target_arch = "mips64",
+              target_arch = "sparc",
+              target_arch = "sparc64")))]
+const FICLONE: libc::c_ulong = 0x4004_9409;
+#[cfg(any(target_arch = "powerpc",
+          target_arch = "powerpc64",
+          target_arch = "mips",
+          target_arch = "mips64",
+          target_arch = "sparc",
+          target_arch = "sparc64"))]
+const FICLONE: libc::c_ulong = 0x8004_9409;
+
+pub enum CopyResult {
+    /// The copy finished, or failed after data was transferred.
+    Ended(io::Result<u64>),
+    /// The kernel cannot do this copy. The number of bytes transferred so
+    /// far is given and the caller should copy the rest itself.
+    Fallback(u64),
+}
+
+// Errors telling us that a syscall cannot be used for these descriptors,
+// rather than that the copy itself went wrong.
+fn is_unsupported(err: &io::Error) -> bool {
+    match err.raw_os_error() {
+        Some(libc::ENOSYS) |
+        Some(libc::EXDEV) |
+        Some(libc::EINVAL) |
+        Some(libc::EPERM) |
+        Some(libc::EBADF) |
+        Some(libc::EOPNOTSUPP) => true,
+        _ => false,
+    }
+}
+
+/// Makes `writer` share the data of `reader` with a reflink, on filesystems
+/// that support it (like Btrfs and XFS). Returns whether that worked; if it
+/// did not, `writer` is left untouched.
+pub fn clone_file(reader: c_int, writer: c_int) -> bool {
+    unsafe { libc::ioctl(writer, FICLONE as _, reader) == 0 }
+}
+
+/// Copies the rest of `reader` to `writer` with `copy_file_range`, or with
+/// `sendfile` where that is not available. Both use and advance the file
+/// offsets of the descriptors.
+pub fn copy_regular_files(reader: c_int, writer: c_int) -> CopyResult {
+    // `copy_file_range` only appeared in Linux 4.5, so remember whether the
+    // kernel knows about it at all.
+    static HAS_COPY_FILE_RANGE: AtomicBool = AtomicBool::new(true);
+
+    if !HAS_COPY_FILE_RANGE.load(Ordering::Relaxed) {
+        return sendfile(reader, writer);
+    }
+
+    let mut written = 0u64;
+    loop {
+        let ret = cvt(unsafe {
+            libc::syscall(libc::SYS_copy_file_range,
+                          reader,
+                          ptr::null_mut::<libc::off64_t>(),
+                          writer,
+                          ptr::null_mut::<libc::off64_t>(),
+                          MAX_CHUNK,
+                          0)
+        });
+        match ret {
+            // Some pseudo filesystems, like procfs, report a size of 0 and
+            // produce nothing through `copy_file_range`, so let a plain read
+            // have a look before concluding that the file is empty.
+            Ok(0) if written == 0 => return sendfile(reader, writer),
+            Ok(0) => return CopyResult::Ended(Ok(written)),
+            Ok(n) => written += n as u64,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(ref e) if written == 0 && is_unsupported(e) => {
+                if e.raw_os_error() == Some(libc::ENOSYS) {
+                    HAS_COPY_FILE_RANGE.store(false, Ordering::Relaxed);
+                }
+                return sendfile(reader, writer)
+            }
+            Err(e) => return CopyResult::Ended(Err(e)),
+        }
+    }
+}
+
+/// Copies the rest of `reader`, which must support `mmap`-like operations
+/// (a regular file, typically), to `writer` with `sendfile`.
+pub fn sendfile(reader: c_int, writer: c_int) -> CopyResult {
+    let mut written = 0u64;
+    loop {
+        let ret = cvt(unsafe {
+            libc::sendfile(writer, reader, ptr::null_mut(), MAX_CHUNK)
+        });
+        match ret {
+            Ok(0) if written == 0 => return CopyResult::Fallback(0),
+            Ok(0) => return CopyResult::Ended(Ok(written)),
+            Ok(n) => written += n as u64,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(ref e) if written == 0 && is_unsupported(e) => {
+                return CopyResult::Fallback(0)
+            }
+            Err(e) => return CopyResult::Ended(Err(e)),
+        }
+    }
+}
+
+/// Copies the rest of `reader`, typically a socket, to `writer` by splicing
+/// through a pipe.
+pub fn splice(reader: c_int, writer: c_int) -> CopyResult {
+    let (pipe_reader, pipe_writer) = match anon_pipe() {
+        Ok(pipe) => pipe,
+        Err(_) => return CopyResult::Fallback(0),
+    };
+    let (pipe_reader, pipe_writer) = (pipe_reader.into_fd(), pipe_writer.into_fd());
+
+    let mut written = 0u64;
+    loop {
+        let ret = cvt(unsafe {
+            libc::splice(reader, ptr::null_mut(), pipe_writer.raw(), ptr::null_mut(),
+                         MAX_CHUNK, libc::SPLICE_F_MOVE)
+        });
+        let mut in_pipe = match ret {
+            Ok(0) => return CopyResult::Ended(Ok(written)),
+            Ok(n) => n as usize,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(ref e) if written == 0 && is_unsupported(e) => {
+                return CopyResult::Fallback(0)
+            }
+            Err(e) => return CopyResult::Ended(Err(e)),
+        };
+        while in_pipe > 0 {
+            let ret = cvt(unsafe {
+                libc::splice(pipe_reader.raw(), ptr::null_mut(), writer, ptr::null_mut(),
+                             in_pipe, libc::SPLICE_F_MOVE)
+            });
+            match ret {
+                Ok(0) => {
+                    return CopyResult::Ended(Err(io::Error::new(io::ErrorKind::WriteZero,
+                                                                "failed to write whole buffer")))
+                }
+                Ok(n) => {
+                    in_pipe -= n as usize;
+                    written += n as u64;
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                // The writer cannot be spliced into, e.g. because it was
+                // opened with `O_APPEND`. Hand what is already in the pipe
+                // over the slow way before letting the caller take over.
+                Err(ref e) if is_unsupported(e) => {
+                    return match drain(&pipe_reader, writer, in_pipe) {
+                        Ok(()) => CopyResult::Fallback(written + in_pipe as u64),
+                        Err(e) => CopyResult::Ended(Err(e)),
+                    }
+                }
+                Err(e) => return CopyResult::Ended(Err(e)),
+            }
+        }
+    }
+}
+
+// Moves `len` bytes from `pipe` to `writer` with plain reads and writes.
+fn drain(pipe: &FileDesc, writer: c_int, mut len: usize) -> io::Result<()> {
+    let mut buf = [0; 8 * 1024];
+    while len > 0 {
+        let n = pipe.read(&mut buf[..cmp::min(len, buf.len())])?;
+        if n == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                      "pipe closed while data was pending"))
+        }
+        let mut data = &buf[..n];
+        while !data.is_empty() {
+            let ret = cvt(unsafe {
+                libc::write(writer, data.as_ptr() as *const c_void, data.len())
+            });
+            match ret {
+                Ok(0) => return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                                   "failed to write whole buffer")),
+                Ok(m) => data = &data[m as usize..],
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        len -= n;
+    }
+    Ok(())
+}
@@ -9,7 +9,7 @@
 // except according to those terms.
 
 use ffi::CStr;
-use io;
+use io::{self, IoSlice, IoSliceMut};
 use libc::{self, c_int, c_void, size_t, sockaddr, socklen_t, EAI_SYSTEM, MSG_PEEK};
 use mem;
 use net::{SocketAddr, Shutdown};
@@ -43,6 +43,18 @@ use libc::SO_NOSIGPIPE;
 #[cfg(not(target_vendor = "apple"))]
 const SO_NOSIGPIPE: c_int = 0;
 
+// The TCP option for the idle time before keepalive probes are sent, on the
+// platforms where it can be set per socket.
+#[cfg(any(target_os = "linux", target_os = "android",
+          target_os = "freebsd", target_os = "netbsd", target_os = "dragonfly"))]
+const KEEPALIVE_IDLE: Option<c_int> = Some(libc::TCP_KEEPIDLE);
+#[cfg(target_vendor = "apple")]
+const KEEPALIVE_IDLE: Option<c_int> = Some(libc::TCP_KEEPALIVE);
+#[cfg(not(any(target_os = "linux", target_os = "android",
+              target_os = "freebsd", target_os = "netbsd", target_os = "dragonfly",
+              target_vendor = "apple")))]
+const KEEPALIVE_IDLE: Option<c_int> = None;
+
 pub struct Socket(FileDesc);
 
 pub fn init() {}
@@ -244,6 +256,10 @@ impl Socket {
         self.recv_with_flags(buf, 0)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.recv_with_flags(buf, MSG_PEEK)
     }
@@ -276,6 +292,10 @@ impl Socket {
         self.0.write(buf)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     pub fn set_timeout(&self, dur: Option<Duration>, kind: libc::c_int) -> io::Result<()> {
         let timeout = match dur {
             Some(dur) => {
@@ -338,6 +358,57 @@ impl Socket {
         Ok(raw != 0)
     }
 
+    pub fn set_keepalive(&self, keepalive: Option<Duration>) -> io::Result<()> {
+        // Set the idle time first, so that a failure leaves keepalive as it
+        // was.
+        if let Some(dur) = keepalive {
+            let idle = match KEEPALIVE_IDLE {
+                Some(idle) => idle,
+                None => return Err(io::Error::new(io::ErrorKind::Other,
+                                                  "setting the keepalive idle time is not \
+                                                   supported on this platform")),
+            };
+            // The idle time is in whole seconds, so round up.
+            let secs = dur.as_secs() + if dur.subsec_nanos() > 0 { 1 } else { 0 };
+            let secs = cmp::max(1, cmp::min(secs, c_int::max_value() as u64)) as c_int;
+            setsockopt(self, libc::IPPROTO_TCP, idle, secs)?;
+        }
+        setsockopt(self, libc::SOL_SOCKET, libc::SO_KEEPALIVE, keepalive.is_some() as c_int)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android",
+              target_os = "macos", target_os = "ios",
+              target_os = "freebsd", target_os = "dragonfly",
+              target_os = "netbsd", target_os = "openbsd"))]
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        setsockopt(self, libc::SOL_SOCKET, libc::SO_REUSEPORT, reuse as c_int)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android",
+                  target_os = "macos", target_os = "ios",
+                  target_os = "freebsd", target_os = "dragonfly",
+                  target_os = "netbsd", target_os = "openbsd")))]
+    pub fn set_reuse_port(&self, _reuse: bool) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Other,
+                           "SO_REUSEPORT is not supported on this platform"))
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn bind_device(&self, interface: &str) -> io::Result<()> {
+        cvt(unsafe {
+            libc::setsockopt(*self.as_inner(), libc::SOL_SOCKET, libc::SO_BINDTODEVICE,
+                             interface.as_ptr() as *const c_void,
+                             interface.len() as socklen_t)
+        })?;
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    pub fn bind_device(&self, _interface: &str) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Other,
+                           "binding to a device is not supported on this platform"))
+    }
+
     pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         let mut nonblocking = nonblocking as libc::c_int;
         cvt(unsafe { libc::ioctl(*self.as_inner(), libc::FIONBIO, &mut nonblocking) }).map(|_| ())
@@ -13,7 +13,7 @@ use os::unix::prelude::*;
 use ffi::{OsString, OsStr, CString, CStr};
 use fmt;
 use io;
-use libc::{self, c_int, gid_t, uid_t, c_char};
+use libc::{self, c_int, gid_t, pid_t, uid_t, c_char};
 use ptr;
 use sys::fd::FileDesc;
 use sys::fs::{File, OpenOptions};
@@ -51,6 +51,12 @@ pub struct Command {
     cwd: Option<CString>,
     uid: Option<uid_t>,
     gid: Option<gid_t>,
+    setsid: bool,
+    pgroup: Option<pid_t>,
+    rlimits: Vec<(c_int, libc::rlimit)>,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pdeathsig: Option<c_int>,
+    extra_fds: Vec<ExtraFd>,
     saw_nul: bool,
     closures: Vec<Box<FnMut() -> io::Result<()> + Send + Sync>>,
     stdin: Option<Stdio>,
@@ -80,6 +86,17 @@ pub struct ChildPipes {
     pub stderr: ChildStdio,
 }
 
+// an additional file descriptor which the child should receive at a chosen
+// number
+pub struct ExtraFd {
+    pub parent: c_int,
+    pub child: c_int,
+    // scratch space for the child to park a copy of `parent` in while the
+    // descriptors are shuffled into place, so that no allocation is needed
+    // after the fork
+    pub tmp: c_int,
+}
+
 pub enum ChildStdio {
     Inherit,
     Explicit(c_int),
@@ -105,6 +122,12 @@ impl Command {
             cwd: None,
             uid: None,
             gid: None,
+            setsid: false,
+            pgroup: None,
+            rlimits: Vec::new(),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            pdeathsig: None,
+            extra_fds: Vec::new(),
             saw_nul,
             closures: Vec::new(),
             stdin: None,
@@ -134,6 +157,31 @@ impl Command {
     pub fn gid(&mut self, id: gid_t) {
         self.gid = Some(id);
     }
+    pub fn setsid(&mut self, setsid: bool) {
+        self.setsid = setsid;
+    }
+    pub fn pgroup(&mut self, pgroup: pid_t) {
+        self.pgroup = Some(pgroup);
+    }
+    pub fn rlimit(&mut self, resource: c_int, soft: u64, hard: u64) {
+        let limit = libc::rlimit {
+            rlim_cur: soft as libc::rlim_t,
+            rlim_max: hard as libc::rlim_t,
+        };
+        if let Some(entry) = self.rlimits.iter_mut().find(|e| e.0 == resource) {
+            entry.1 = limit;
+            return;
+        }
+        self.rlimits.push((resource, limit));
+    }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn pdeathsig(&mut self, signal: c_int) {
+        self.pdeathsig = Some(signal);
+    }
+    pub fn inherit_fd(&mut self, parent: c_int, child: c_int) {
+        self.extra_fds.retain(|fd| fd.child != child);
+        self.extra_fds.push(ExtraFd { parent, child, tmp: -1 });
+    }
 
     pub fn saw_nul(&self) -> bool {
         self.saw_nul
@@ -154,6 +202,22 @@ impl Command {
     pub fn get_gid(&self) -> Option<gid_t> {
         self.gid
     }
+    pub fn get_setsid(&self) -> bool {
+        self.setsid
+    }
+    pub fn get_pgroup(&self) -> Option<pid_t> {
+        self.pgroup
+    }
+    pub fn get_rlimits(&self) -> &[(c_int, libc::rlimit)] {
+        &self.rlimits
+    }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn get_pdeathsig(&self) -> Option<c_int> {
+        self.pdeathsig
+    }
+    pub fn get_extra_fds(&mut self) -> &mut [ExtraFd] {
+        &mut self.extra_fds
+    }
 
     pub fn get_closures(&mut self) -> &mut Vec<Box<FnMut() -> io::Result<()> + Send + Sync>> {
         &mut self.closures
@@ -15,6 +15,8 @@ use ptr;
 
 use sys::process::zircon::{Handle, zx_handle_t};
 use sys::process::process_common::*;
+use sys_common::process;
+use time::Duration;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Command
@@ -30,6 +32,15 @@ impl Command {
                                       "nul byte found in provided data"));
         }
 
+        // launchpad has no notion of sessions, process groups or resource
+        // limits, and only hands over the stdio descriptors.
+        if self.get_setsid() || self.get_pgroup().is_some() ||
+           !self.get_rlimits().is_empty() || !self.get_extra_fds().is_empty() {
+            return Err(io::Error::new(io::ErrorKind::Other,
+                                      "setsid, pgroup, rlimit and inherit_fd are not \
+                                       supported on Fuchsia"));
+        }
+
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
         let process_handle = unsafe { self.do_exec(theirs, envp.as_ref())? };
@@ -197,4 +208,8 @@ impl Process {
         }
         Ok(Some(ExitStatus::new(proc_info.rec.return_code)))
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        process::wait_timeout_by_polling(timeout, || self.try_wait())
+    }
 }
@@ -11,6 +11,7 @@
 use io::{self, Error, ErrorKind};
 use libc::{self, c_int, gid_t, pid_t, uid_t};
 use ptr;
+use time::Duration;
 
 use sys::cvt;
 use sys::process::process_common::*;
@@ -36,11 +37,12 @@ impl Command {
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
         let (input, output) = sys::pipe::anon_pipe()?;
 
+        let parent_pid = unsafe { libc::getpid() };
         let pid = unsafe {
             match cvt(libc::fork())? {
                 0 => {
                     drop(input);
-                    let err = self.do_exec(theirs, envp.as_ref());
+                    let err = self.do_exec(theirs, envp.as_ref(), parent_pid);
                     let errno = err.raw_os_error().unwrap_or(libc::EINVAL) as u32;
                     let bytes = [
                         (errno >> 24) as u8,
@@ -109,7 +111,10 @@ impl Command {
         }
 
         match self.setup_io(default, true) {
-            Ok((_, theirs)) => unsafe { self.do_exec(theirs, envp.as_ref()) },
+            Ok((_, theirs)) => unsafe {
+                let parent_pid = libc::getppid();
+                self.do_exec(theirs, envp.as_ref(), parent_pid)
+            },
             Err(e) => e,
         }
     }
@@ -144,10 +149,16 @@ impl Command {
     // allocation). Instead we just close it manually. This will never
     // have the drop glue anyway because this code never returns (the
     // child will either exec() or invoke libc::exit)
+    //
+    // `parent_pid` is the process that the parent-death signal refers to,
+    // looked up before forking.
+    #[cfg_attr(not(any(target_os = "linux", target_os = "android")),
+               allow(unused_variables))]
     unsafe fn do_exec(
         &mut self,
         stdio: ChildPipes,
-        maybe_envp: Option<&CStringArray>
+        maybe_envp: Option<&CStringArray>,
+        parent_pid: pid_t,
     ) -> io::Error {
         use sys::{self, cvt_r};
 
@@ -158,6 +169,20 @@ impl Command {
             })
         }
 
+        // Extra descriptors are first parked above every number they'll end
+        // up at (and above stdio), so that neither the stdio setup nor the
+        // descriptors moved into place below can clobber one that hasn't
+        // been moved yet. The parked copies are closed by `execvp`.
+        if !self.get_extra_fds().is_empty() {
+            let min = self.get_extra_fds().iter()
+                          .map(|fd| fd.child)
+                          .fold(libc::STDERR_FILENO, |a, b| if a > b { a } else { b }) + 1;
+            for fd in self.get_extra_fds().iter_mut() {
+                fd.tmp = t!(cvt(libc::fcntl(fd.parent, libc::F_DUPFD, min)));
+                t!(cvt(libc::fcntl(fd.tmp, libc::F_SETFD, libc::FD_CLOEXEC)));
+            }
+        }
+
         if let Some(fd) = stdio.stdin.fd() {
             t!(cvt_r(|| libc::dup2(fd, libc::STDIN_FILENO)));
         }
@@ -167,6 +192,21 @@ impl Command {
         if let Some(fd) = stdio.stderr.fd() {
             t!(cvt_r(|| libc::dup2(fd, libc::STDERR_FILENO)));
         }
+        for fd in self.get_extra_fds().iter() {
+            t!(cvt_r(|| libc::dup2(fd.tmp, fd.child)));
+        }
+
+        if self.get_setsid() {
+            t!(cvt(libc::setsid()));
+        } else if let Some(pgroup) = self.get_pgroup() {
+            t!(cvt(libc::setpgid(0, pgroup)));
+        }
+
+        // Resource limits go before dropping privileges, as raising a hard
+        // limit requires them.
+        for &(resource, ref limit) in self.get_rlimits() {
+            t!(cvt(libc::setrlimit(resource as _, limit)));
+        }
 
         if cfg!(not(any(target_os = "l4re"))) {
             if let Some(u) = self.get_gid() {
@@ -185,6 +225,20 @@ impl Command {
                 t!(cvt(libc::setuid(u as uid_t)));
             }
         }
+
+        // This comes after `setuid` and `setgid`, as changing credentials
+        // resets the parent-death signal.
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            if let Some(signal) = self.get_pdeathsig() {
+                t!(cvt(libc::prctl(libc::PR_SET_PDEATHSIG, signal as libc::c_ulong)));
+                // If the parent already exited we have been reparented and
+                // the signal will never come, so deliver it ourselves.
+                if libc::getppid() != parent_pid {
+                    t!(cvt(libc::raise(signal)));
+                }
+            }
+        }
         if let Some(ref cwd) = *self.get_cwd() {
             t!(cvt(libc::chdir(cwd.as_ptr())));
         }
@@ -284,4 +338,145 @@ impl Process {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        if let Some(status) = self.try_wait()? {
+            return Ok(Some(status))
+        }
+        self.wait_timeout_blocking(timeout)
+    }
+
+    // Waits for the child with a kqueue, which reports the exit of a
+    // process as an event.
+    #[cfg(any(target_os = "macos",
+              target_os = "ios",
+              target_os = "freebsd",
+              target_os = "dragonfly",
+              target_os = "netbsd",
+              target_os = "openbsd"))]
+    fn wait_timeout_blocking(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        use cmp;
+        use mem;
+        use sys::fd::FileDesc;
+        use time::Instant;
+
+        let kq = FileDesc::new(cvt(unsafe { libc::kqueue() })?);
+        kq.set_cloexec()?;
+
+        let mut change: libc::kevent = unsafe { mem::zeroed() };
+        change.ident = self.pid as _;
+        change.filter = libc::EVFILT_PROC as _;
+        change.flags = (libc::EV_ADD | libc::EV_ONESHOT) as _;
+        change.fflags = libc::NOTE_EXIT as _;
+        let ret = cvt(unsafe {
+            libc::kevent(kq.raw(), &change, 1, ptr::null_mut(), 0, ptr::null())
+        });
+        match ret {
+            Ok(_) => {}
+            // The child has already exited and is waiting to be reaped.
+            Err(ref e) if e.raw_os_error() == Some(libc::ESRCH) => return self.wait().map(Some),
+            Err(e) => return Err(e),
+        }
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let now = Instant::now();
+            let remaining = if deadline > now { deadline - now } else { Duration::new(0, 0) };
+            let ts = libc::timespec {
+                tv_sec: cmp::min(remaining.as_secs(),
+                                 libc::time_t::max_value() as u64) as libc::time_t,
+                tv_nsec: remaining.subsec_nanos() as _,
+            };
+            let mut event: libc::kevent = unsafe { mem::zeroed() };
+            match cvt(unsafe { libc::kevent(kq.raw(), ptr::null(), 0, &mut event, 1, &ts) }) {
+                Ok(0) => {
+                    if Instant::now() >= deadline {
+                        return Ok(None)
+                    }
+                }
+                // The child has exited, so this doesn't block.
+                Ok(_) => return self.wait().map(Some),
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos",
+                  target_os = "ios",
+                  target_os = "freebsd",
+                  target_os = "dragonfly",
+                  target_os = "netbsd",
+                  target_os = "openbsd")))]
+    fn wait_timeout_blocking(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        use sys_common::process;
+
+        #[cfg(target_os = "linux")]
+        {
+            if let Some(res) = self.wait_timeout_pidfd(timeout) {
+                return res
+            }
+        }
+        process::wait_timeout_by_polling(timeout, || self.try_wait())
+    }
+
+    // Waits on a pidfd for the child, which becomes readable once the child
+    // exits. Returns `None` if the kernel doesn't support pidfds, or if a
+    // seccomp filter forbids them.
+    #[cfg(target_os = "linux")]
+    fn wait_timeout_pidfd(&mut self, timeout: Duration)
+                          -> Option<io::Result<Option<ExitStatus>>> {
+        use cmp;
+        use sync::atomic::{AtomicBool, Ordering};
+        use sys::fd::FileDesc;
+        use time::Instant;
+
+        // `pidfd_open` is 434 everywhere except MIPS, which offsets the
+        // syscall numbers of each ABI.
+        #[cfg(target_arch = "mips")]
+        const SYS_PIDFD_OPEN: libc::c_long = 4000 + 434;
+        #[cfg(target_arch = "mips64")]
+        const SYS_PIDFD_OPEN: libc::c_long = 5000 + 434;
+        #[cfg(not(any(target_arch = "mips", target_arch = "mips64")))]
+        const SYS_PIDFD_OPEN: libc::c_long = 434;
+        static HAS_PIDFD: AtomicBool = AtomicBool::new(true);
+
+        if !HAS_PIDFD.load(Ordering::Relaxed) {
+            return None
+        }
+        // The child hasn't been reaped yet, so its pid can't have been
+        // recycled.
+        let fd = match cvt(unsafe { libc::syscall(SYS_PIDFD_OPEN, self.pid, 0) }) {
+            Ok(fd) => FileDesc::new(fd as c_int),
+            Err(ref e) if e.raw_os_error() == Some(libc::ENOSYS) ||
+                          e.raw_os_error() == Some(libc::EPERM) => {
+                HAS_PIDFD.store(false, Ordering::Relaxed);
+                return None
+            }
+            Err(e) => return Some(Err(e)),
+        };
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let now = Instant::now();
+            let remaining = if deadline > now { deadline - now } else { Duration::new(0, 0) };
+            // Round up, so that we don't wake up just before the deadline.
+            let ms = remaining.as_secs()
+                              .saturating_mul(1000)
+                              .saturating_add((remaining.subsec_nanos() as u64 + 999_999)
+                                              / 1_000_000);
+            let ms = cmp::min(ms, c_int::max_value() as u64) as c_int;
+            let mut pfd = libc::pollfd { fd: fd.raw(), events: libc::POLLIN, revents: 0 };
+            match cvt(unsafe { libc::poll(&mut pfd, 1, ms) }) {
+                Ok(0) => {
+                    if Instant::now() >= deadline {
+                        return Some(Ok(None))
+                    }
+                }
+                Ok(_) => return Some(self.try_wait()),
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
 }
@@ -39,6 +39,7 @@ pub mod cmath;
 pub mod condvar;
 pub mod env;
 pub mod fs;
+pub use sys_common::iovec;
 pub mod memchr;
 pub mod mutex;
 pub mod net;
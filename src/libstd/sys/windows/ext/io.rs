@@ -165,6 +165,34 @@ impl AsRawSocket for net::UdpSocket {
     }
 }
 
+#[unstable(feature = "net_socket_builder", issue = "0")]
+impl AsRawSocket for net::TcpBuilder {
+    fn as_raw_socket(&self) -> RawSocket {
+        *self.as_inner().socket().as_inner()
+    }
+}
+#[unstable(feature = "net_socket_builder", issue = "0")]
+impl AsRawSocket for net::UdpBuilder {
+    fn as_raw_socket(&self) -> RawSocket {
+        *self.as_inner().socket().as_inner()
+    }
+}
+
+#[unstable(feature = "net_socket_builder", issue = "0")]
+impl FromRawSocket for net::TcpBuilder {
+    unsafe fn from_raw_socket(sock: RawSocket) -> net::TcpBuilder {
+        let sock = sys::net::Socket::from_inner(sock);
+        net::TcpBuilder::from_inner(sys_common::net::SocketBuilder::from_inner(sock))
+    }
+}
+#[unstable(feature = "net_socket_builder", issue = "0")]
+impl FromRawSocket for net::UdpBuilder {
+    unsafe fn from_raw_socket(sock: RawSocket) -> net::UdpBuilder {
+        let sock = sys::net::Socket::from_inner(sock);
+        net::UdpBuilder::from_inner(sys_common::net::SocketBuilder::from_inner(sock))
+    }
+}
+
 #[stable(feature = "from_raw_os", since = "1.1.0")]
 impl FromRawSocket for net::TcpStream {
     unsafe fn from_raw_socket(sock: RawSocket) -> net::TcpStream {
@@ -12,7 +12,7 @@ use os::windows::prelude::*;
 
 use ffi::OsString;
 use fmt;
-use io::{self, Error, SeekFrom};
+use io::{self, Error, IoSlice, IoSliceMut, SeekFrom};
 use mem;
 use path::{Path, PathBuf};
 use ptr;
@@ -321,6 +321,10 @@ impl File {
         self.handle.read(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        io::default_read_vectored(|b| self.read(b), bufs)
+    }
+
     pub fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
         self.handle.read_at(buf, offset)
     }
@@ -329,6 +333,10 @@ impl File {
         self.handle.write(buf)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        io::default_write_vectored(|b| self.write(b), bufs)
+    }
+
     pub fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
         self.handle.write_at(buf, offset)
     }
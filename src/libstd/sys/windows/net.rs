@@ -11,7 +11,7 @@
 #![unstable(issue = "0", feature = "windows_net")]
 
 use cmp;
-use io::{self, Read};
+use io::{self, IoSlice, IoSliceMut, Read};
 use libc::{c_int, c_void, c_ulong, c_long};
 use mem;
 use net::{SocketAddr, Shutdown};
@@ -217,6 +217,22 @@ impl Socket {
         self.recv_with_flags(buf, 0)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        io::default_read_vectored(|b| self.read(b), bufs)
+    }
+
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        io::default_write_vectored(|buf| {
+            let len = cmp::min(buf.len(), i32::max_value() as usize) as i32;
+            unsafe {
+                match c::send(self.0, buf.as_ptr() as *const c_void, len, 0) {
+                    -1 => Err(last_error()),
+                    n => Ok(n as usize)
+                }
+            }
+        }, bufs)
+    }
+
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.recv_with_flags(buf, c::MSG_PEEK)
     }
@@ -316,6 +332,43 @@ impl Socket {
         Ok(raw != 0)
     }
 
+    pub fn set_keepalive(&self, keepalive: Option<Duration>) -> io::Result<()> {
+        let dur = match keepalive {
+            Some(dur) => dur,
+            None => return net::setsockopt(self, c::SOL_SOCKET, c::SO_KEEPALIVE, 0 as c::BOOL),
+        };
+        // Setting the idle time also turns keepalive on. The interval between
+        // probes is left at the system default of one second.
+        let mut vals = c::tcp_keepalive {
+            onoff: 1,
+            keepalivetime: cmp::max(sys::dur2timeout(dur), 1),
+            keepaliveinterval: 1000,
+        };
+        let mut out = 0;
+        let r = unsafe {
+            c::WSAIoctl(self.0, c::SIO_KEEPALIVE_VALS,
+                        &mut vals as *mut _ as c::LPVOID,
+                        mem::size_of::<c::tcp_keepalive>() as c::DWORD,
+                        ptr::null_mut(), 0, &mut out,
+                        ptr::null_mut(), ptr::null_mut())
+        };
+        if r == 0 {
+            Ok(())
+        } else {
+            Err(last_error())
+        }
+    }
+
+    pub fn set_reuse_port(&self, _reuse: bool) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Other,
+                           "SO_REUSEPORT is not supported on Windows"))
+    }
+
+    pub fn bind_device(&self, _interface: &str) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Other,
+                           "binding to a device is not supported on Windows"))
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         let raw: c_int = net::getsockopt(self, c::SOL_SOCKET, c::SO_ERROR)?;
         if raw == 0 {
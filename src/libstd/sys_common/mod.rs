@@ -44,6 +44,8 @@ pub mod util;
 pub mod wtf8;
 pub mod bytestring;
 pub mod process;
+#[cfg(not(unix))]
+pub mod iovec;
 
 cfg_if! {
     if #[cfg(any(target_os = "cloudabi", target_os = "l4re", target_os = "redox"))] {
@@ -11,10 +11,10 @@
 use cmp;
 use ffi::CString;
 use fmt;
-use io::{self, Error, ErrorKind};
+use io::{self, Error, ErrorKind, IoSlice, IoSliceMut};
 use libc::{c_int, c_void};
 use mem;
-use net::{SocketAddr, Shutdown, Ipv4Addr, Ipv6Addr};
+use net::{SocketAddr, SocketAddrV4, SocketAddrV6, Shutdown, Ipv4Addr, Ipv6Addr};
 use ptr;
 use sys::net::{cvt, cvt_r, cvt_gai, Socket, init, wrlen_t};
 use sys::net::netc as c;
@@ -227,6 +227,10 @@ impl TcpStream {
         self.inner.read(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
     pub fn write(&self, buf: &[u8]) -> io::Result<usize> {
         let len = cmp::min(buf.len(), <wrlen_t>::max_value() as usize) as wrlen_t;
         let ret = cvt(unsafe {
@@ -238,6 +242,10 @@ impl TcpStream {
         Ok(ret as usize)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
+
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         sockname(|buf, len| unsafe {
             c::getpeername(*self.inner.as_inner(), buf, len)
@@ -610,6 +618,109 @@ impl fmt::Debug for UdpSocket {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Socket builders
+////////////////////////////////////////////////////////////////////////////////
+
+pub struct SocketBuilder {
+    inner: Socket,
+}
+
+impl SocketBuilder {
+    pub fn new_tcp(v6: bool) -> io::Result<SocketBuilder> {
+        SocketBuilder::new(v6, c::SOCK_STREAM)
+    }
+
+    pub fn new_udp(v6: bool) -> io::Result<SocketBuilder> {
+        SocketBuilder::new(v6, c::SOCK_DGRAM)
+    }
+
+    fn new(v6: bool, ty: c_int) -> io::Result<SocketBuilder> {
+        init();
+
+        // `Socket::new` only looks at the family of the address.
+        let addr = if v6 {
+            SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0), 0, 0, 0))
+        } else {
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(0, 0, 0, 0), 0))
+        };
+        let sock = Socket::new(&addr, ty)?;
+        Ok(SocketBuilder { inner: sock })
+    }
+
+    pub fn socket(&self) -> &Socket { &self.inner }
+
+    pub fn set_reuse_address(&self, reuse: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR, reuse as c_int)
+    }
+
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        self.inner.set_reuse_port(reuse)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::max_value() as usize) as c_int;
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_SNDBUF, size)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::max_value() as usize) as c_int;
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_RCVBUF, size)
+    }
+
+    pub fn set_keepalive(&self, keepalive: Option<Duration>) -> io::Result<()> {
+        self.inner.set_keepalive(keepalive)
+    }
+
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        setsockopt(&self.inner, c::IPPROTO_IP, c::IP_TTL, ttl as c_int)
+    }
+
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_V6ONLY, only_v6 as c_int)
+    }
+
+    pub fn bind_device(&self, interface: &str) -> io::Result<()> {
+        self.inner.bind_device(interface)
+    }
+
+    pub fn bind(&self, addr: &SocketAddr) -> io::Result<()> {
+        let (addrp, len) = addr.into_inner();
+        cvt(unsafe { c::bind(*self.inner.as_inner(), addrp, len as _) })?;
+        Ok(())
+    }
+
+    pub fn listen(self, backlog: i32) -> io::Result<TcpListener> {
+        cvt(unsafe { c::listen(*self.inner.as_inner(), backlog) })?;
+        Ok(TcpListener { inner: self.inner })
+    }
+
+    pub fn connect(self, addr: &SocketAddr) -> io::Result<TcpStream> {
+        let (addrp, len) = addr.into_inner();
+        cvt_r(|| unsafe { c::connect(*self.inner.as_inner(), addrp, len) })?;
+        Ok(TcpStream { inner: self.inner })
+    }
+
+    pub fn into_udp(self) -> UdpSocket {
+        UdpSocket { inner: self.inner }
+    }
+}
+
+impl FromInner<Socket> for SocketBuilder {
+    fn from_inner(socket: Socket) -> SocketBuilder {
+        SocketBuilder { inner: socket }
+    }
+}
+
+impl fmt::Debug for SocketBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = if cfg!(windows) {"socket"} else {"fd"};
+        f.debug_struct("SocketBuilder")
+            .field(name, &self.inner.as_inner())
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
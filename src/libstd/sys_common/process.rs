@@ -13,8 +13,12 @@
 
 use ffi::{OsStr, OsString};
 use env;
+use cmp;
 use collections::BTreeMap;
 use alloc::borrow::Borrow;
+use io;
+use thread;
+use time::{Duration, Instant};
 
 pub trait EnvKey:
     From<OsString> + Into<OsString> +
@@ -122,3 +126,25 @@ impl<K: EnvKey> CommandEnv<K> {
         self.vars.clear();
     }
 }
+
+// Waits for at most `timeout` for `try_wait` to report that a child has
+// exited, sleeping in between attempts. This is how `wait_timeout` is done on
+// platforms that have no way to block on a child until a deadline.
+pub fn wait_timeout_by_polling<T, F>(timeout: Duration, mut try_wait: F)
+                                     -> io::Result<Option<T>>
+    where F: FnMut() -> io::Result<Option<T>>
+{
+    let deadline = Instant::now() + timeout;
+    let mut delay = Duration::from_millis(1);
+    loop {
+        if let Some(status) = try_wait()? {
+            return Ok(Some(status))
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None)
+        }
+        thread::sleep(cmp::min(delay, deadline - now));
+        delay = cmp::min(delay * 2, Duration::from_millis(100));
+    }
+}
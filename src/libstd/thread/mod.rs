@@ -208,6 +208,15 @@ pub use self::local::{LocalKey, LocalKeyState, AccessError};
 #[unstable(feature = "libstd_thread_internals", issue = "0")]
 #[doc(hidden)] pub use self::local::os::Key as __OsLocalKeyInner;
 
+////////////////////////////////////////////////////////////////////////////////
+// Scoped threads
+////////////////////////////////////////////////////////////////////////////////
+
+mod scoped;
+
+#[unstable(feature = "thread_scope", issue = "0")]
+pub use self::scoped::{scope, Scope, ScopedJoinHandle};
+
 ////////////////////////////////////////////////////////////////////////////////
 // Builder
 ////////////////////////////////////////////////////////////////////////////////
@@ -383,6 +392,23 @@ impl Builder {
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn spawn<F, T>(self, f: F) -> io::Result<JoinHandle<T>> where
         F: FnOnce() -> T, F: Send + 'static, T: Send + 'static
+    {
+        unsafe { self.spawn_unchecked(f, None).map(JoinHandle) }
+    }
+
+    /// Spawns a new thread without requiring `F` and `T` to be `'static`.
+    ///
+    /// If `scope_data` is given, the thread is accounted for in it: the
+    /// running count is incremented here, before the thread is started, so
+    /// that the scope can't see it drop to zero while the thread is still to
+    /// run. The thread decrements it as the very last thing it does, after
+    /// which it no longer touches anything borrowed by `f` or `T`.
+    ///
+    /// This is unsafe because the caller must guarantee that the thread is
+    /// finished before anything borrowed by `f` or `T` goes away.
+    unsafe fn spawn_unchecked<'a, F, T>(self, f: F, scope_data: Option<Arc<scoped::ScopeData>>)
+                                        -> io::Result<JoinInner<T>> where
+        F: FnOnce() -> T, F: Send + 'a, T: Send + 'a
     {
         let Builder { name, stack_size } = self;
 
@@ -394,6 +420,7 @@ impl Builder {
         let my_packet : Arc<UnsafeCell<Option<Result<T>>>>
             = Arc::new(UnsafeCell::new(None));
         let their_packet = my_packet.clone();
+        let their_scope_data = scope_data.clone();
 
         let main = move || {
             if let Some(name) = their_thread.cname() {
@@ -407,17 +434,40 @@ impl Builder {
                 }));
                 #[cfg(not(feature = "backtrace"))]
                 let try_result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+                if let Some(ref scope_data) = their_scope_data {
+                    if try_result.is_err() {
+                        scope_data.record_panic();
+                    }
+                }
                 *their_packet.get() = Some(try_result);
             }
+            // The packet may hold the last reference to the result, which can
+            // borrow from the scope, so it has to be dropped before the scope
+            // is told that this thread is done.
+            drop(their_packet);
+            if let Some(scope_data) = their_scope_data {
+                scope_data.decrement_num_running_threads();
+            }
         };
 
-        Ok(JoinHandle(JoinInner {
-            native: unsafe {
-                Some(imp::Thread::new(stack_size, Box::new(main))?)
-            },
+        if let Some(ref scope_data) = scope_data {
+            scope_data.increment_num_running_threads();
+        }
+        let native = match imp::Thread::new(stack_size, Box::new(main)) {
+            Ok(native) => native,
+            Err(e) => {
+                if let Some(ref scope_data) = scope_data {
+                    scope_data.decrement_num_running_threads();
+                }
+                return Err(e)
+            }
+        };
+
+        Ok(JoinInner {
+            native: Some(native),
             thread: my_thread,
             packet: Packet(my_packet),
-        }))
+        })
     }
 }
 
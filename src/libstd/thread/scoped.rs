@@ -0,0 +1,394 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Scoped threads, which may borrow from the stack of the thread spawning
+//! them.
+
+use fmt;
+use io;
+use marker::PhantomData;
+use panic;
+use sync::Arc;
+use sync::atomic::AtomicUsize;
+use sync::atomic::Ordering::SeqCst;
+
+use super::{current, park, Builder, JoinInner, Result, Thread};
+
+/// A scope to spawn scoped threads in.
+///
+/// See [`scope`] for details.
+///
+/// [`scope`]: fn.scope.html
+#[unstable(feature = "thread_scope", issue = "0")]
+pub struct Scope<'env> {
+    data: Arc<ScopeData>,
+    // `'env` must be invariant, so that it can be neither shortened nor
+    // lengthened to let a scoped thread outlive what it borrows.
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+/// The bookkeeping shared between a [`Scope`] and the threads spawned in it.
+///
+/// [`Scope`]: struct.Scope.html
+pub struct ScopeData {
+    num_running_threads: AtomicUsize,
+    num_unjoined_panics: AtomicUsize,
+    main_thread: Thread,
+}
+
+impl ScopeData {
+    pub fn increment_num_running_threads(&self) {
+        // Overflowing the count would let `scope` return while threads
+        // borrowing from it are still running, so abort well before that.
+        if self.num_running_threads.fetch_add(1, SeqCst) > usize::max_value() / 2 {
+            ::process::abort();
+        }
+    }
+
+    pub fn decrement_num_running_threads(&self) {
+        if self.num_running_threads.fetch_sub(1, SeqCst) == 1 {
+            self.main_thread.unpark();
+        }
+    }
+
+    pub fn record_panic(&self) {
+        self.num_unjoined_panics.fetch_add(1, SeqCst);
+    }
+}
+
+/// An owned permission to join on a scoped thread (block on its termination).
+///
+/// Unlike a [`JoinHandle`], a `ScopedJoinHandle` cannot outlive the [`Scope`]
+/// it was spawned in. Dropping it does not detach the thread for good: the
+/// thread is still joined when the scope ends.
+///
+/// This `struct` is created by the [`Scope::spawn`] and
+/// [`Builder::spawn_scoped`] methods.
+///
+/// [`JoinHandle`]: struct.JoinHandle.html
+/// [`Scope`]: struct.Scope.html
+/// [`Scope::spawn`]: struct.Scope.html#method.spawn
+/// [`Builder::spawn_scoped`]: struct.Builder.html#method.spawn_scoped
+#[unstable(feature = "thread_scope", issue = "0")]
+pub struct ScopedJoinHandle<'scope, T> {
+    inner: JoinInner<T>,
+    scope_data: Arc<ScopeData>,
+    scope: PhantomData<&'scope ()>,
+}
+
+/// Creates a scope for spawning threads that borrow non-`'static` data.
+///
+/// The closure `f` is given a [`Scope`], whose [`spawn`] method works like
+/// [`thread::spawn`], except that the spawned closure only needs to live
+/// as long as the scope rather than being `'static`. All threads spawned in
+/// the scope that have not been joined manually are joined before this
+/// function returns, even if `f` panics.
+///
+/// # Panics
+///
+/// If `f` panics, the panic is resumed once all the threads have been
+/// joined. Otherwise, if any of the threads panicked and its panic was not
+/// observed through [`ScopedJoinHandle::join`], this function panics too.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(thread_scope)]
+/// use std::thread;
+///
+/// let mut a = vec![1, 2, 3];
+/// let mut x = 0;
+///
+/// thread::scope(|s| {
+///     s.spawn(|| {
+///         println!("hello from the first scoped thread");
+///         // We can borrow `a` here.
+///         println!("{:?}", a);
+///     });
+///     s.spawn(|| {
+///         println!("hello from the second scoped thread");
+///         // We can even mutably borrow `x` here,
+///         // because no other threads are using it.
+///         x += a[0] + a[2];
+///     });
+///     println!("hello from the main thread");
+/// });
+///
+/// // After the scope, we can modify and access our variables again:
+/// a.push(4);
+/// assert_eq!(x, a.len());
+/// ```
+///
+/// [`Scope`]: struct.Scope.html
+/// [`spawn`]: struct.Scope.html#method.spawn
+/// [`thread::spawn`]: fn.spawn.html
+/// [`ScopedJoinHandle::join`]: struct.ScopedJoinHandle.html#method.join
+#[unstable(feature = "thread_scope", issue = "0")]
+pub fn scope<'env, F, T>(f: F) -> T
+    where F: FnOnce(&Scope<'env>) -> T
+{
+    let scope = Scope {
+        data: Arc::new(ScopeData {
+            num_running_threads: AtomicUsize::new(0),
+            num_unjoined_panics: AtomicUsize::new(0),
+            main_thread: current(),
+        }),
+        env: PhantomData,
+    };
+
+    // Run `f`, catching a panic so that the threads are still waited for.
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| f(&scope)));
+
+    while scope.data.num_running_threads.load(SeqCst) != 0 {
+        park();
+    }
+
+    match result {
+        Err(e) => panic::resume_unwind(e),
+        Ok(_) if scope.data.num_unjoined_panics.load(SeqCst) != 0 => {
+            panic!("a scoped thread panicked")
+        }
+        Ok(result) => result,
+    }
+}
+
+impl<'env> Scope<'env> {
+    /// Spawns a new thread within the scope, returning a
+    /// [`ScopedJoinHandle`] for it.
+    ///
+    /// Unlike [`thread::spawn`], the closure may borrow anything that
+    /// outlives the scope. The thread is joined at the end of the scope at
+    /// the latest.
+    ///
+    /// This uses the default parameters of [`Builder`]; use
+    /// [`Builder::spawn_scoped`] to name the thread or set its stack size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS fails to create a thread; use
+    /// [`Builder::spawn_scoped`] to recover from such errors.
+    ///
+    /// [`ScopedJoinHandle`]: struct.ScopedJoinHandle.html
+    /// [`thread::spawn`]: fn.spawn.html
+    /// [`Builder`]: struct.Builder.html
+    /// [`Builder::spawn_scoped`]: struct.Builder.html#method.spawn_scoped
+    #[unstable(feature = "thread_scope", issue = "0")]
+    pub fn spawn<'scope, F, T>(&'scope self, f: F) -> ScopedJoinHandle<'scope, T>
+        where F: FnOnce() -> T, F: Send + 'env, T: Send + 'env
+    {
+        Builder::new().spawn_scoped(self, f).unwrap()
+    }
+}
+
+impl Builder {
+    /// Spawns a new scoped thread using the settings of this `Builder`.
+    ///
+    /// This is the scoped equivalent of [`Builder::spawn`]; see
+    /// [`Scope::spawn`] and [`thread::scope`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Like [`Builder::spawn`], this yields an [`io::Result`] to capture any
+    /// failure to create the thread at the OS level.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a thread name was set and it contained null bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(thread_scope)]
+    /// use std::thread;
+    ///
+    /// let mut a = vec![1, 2, 3];
+    ///
+    /// thread::scope(|s| {
+    ///     thread::Builder::new()
+    ///         .name("first".to_string())
+    ///         .stack_size(64 * 1024)
+    ///         .spawn_scoped(s, || {
+    ///             assert_eq!(thread::current().name(), Some("first"));
+    ///             a.push(4);
+    ///         })
+    ///         .unwrap();
+    /// });
+    ///
+    /// assert_eq!(a, [1, 2, 3, 4]);
+    /// ```
+    ///
+    /// [`Builder::spawn`]: struct.Builder.html#method.spawn
+    /// [`Scope::spawn`]: struct.Scope.html#method.spawn
+    /// [`thread::scope`]: fn.scope.html
+    /// [`io::Result`]: ../../std/io/type.Result.html
+    #[unstable(feature = "thread_scope", issue = "0")]
+    pub fn spawn_scoped<'scope, 'env, F, T>(self, scope: &'scope Scope<'env>, f: F)
+                                            -> io::Result<ScopedJoinHandle<'scope, T>>
+        where F: FnOnce() -> T, F: Send + 'env, T: Send + 'env
+    {
+        // This is safe because `scope` waits for every thread registered with
+        // its data before returning, and `'env` outlives the call to `scope`.
+        let inner = unsafe { self.spawn_unchecked(f, Some(scope.data.clone()))? };
+        Ok(ScopedJoinHandle {
+            inner: inner,
+            scope_data: scope.data.clone(),
+            scope: PhantomData,
+        })
+    }
+}
+
+impl<'scope, T> ScopedJoinHandle<'scope, T> {
+    /// Extracts a handle to the underlying thread.
+    #[unstable(feature = "thread_scope", issue = "0")]
+    pub fn thread(&self) -> &Thread {
+        &self.inner.thread
+    }
+
+    /// Waits for the associated thread to finish.
+    ///
+    /// If the child thread panics, [`Err`] is returned with the parameter
+    /// given to [`panic`], and the panic is considered handled: it will not
+    /// make the enclosing [`scope`] panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(thread_scope)]
+    /// use std::thread;
+    ///
+    /// thread::scope(|s| {
+    ///     let t = s.spawn(|| {
+    ///         panic!("oh no");
+    ///     });
+    ///     assert!(t.join().is_err());
+    /// });
+    /// ```
+    ///
+    /// [`Err`]: ../../std/result/enum.Result.html#variant.Err
+    /// [`panic`]: ../../std/macro.panic.html
+    /// [`scope`]: fn.scope.html
+    #[unstable(feature = "thread_scope", issue = "0")]
+    pub fn join(mut self) -> Result<T> {
+        let result = self.inner.join();
+        if result.is_err() {
+            self.scope_data.num_unjoined_panics.fetch_sub(1, SeqCst);
+        }
+        result
+    }
+}
+
+#[unstable(feature = "thread_scope", issue = "0")]
+impl<'env> fmt::Debug for Scope<'env> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("Scope { .. }")
+    }
+}
+
+#[unstable(feature = "thread_scope", issue = "0")]
+impl<'scope, T> fmt::Debug for ScopedJoinHandle<'scope, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("ScopedJoinHandle { .. }")
+    }
+}
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests {
+    use sync::Mutex;
+    use sync::atomic::AtomicUsize;
+    use sync::atomic::Ordering::SeqCst;
+    use thread::{self, Builder};
+    use panic;
+
+    #[test]
+    fn test_borrow_from_stack() {
+        let v = vec![1, 2, 3, 4];
+        let sum = AtomicUsize::new(0);
+        thread::scope(|s| {
+            for x in &v {
+                let sum = &sum;
+                s.spawn(move || {
+                    sum.fetch_add(*x, SeqCst);
+                });
+            }
+        });
+        assert_eq!(sum.load(SeqCst), 10);
+    }
+
+    #[test]
+    fn test_join_returns_result() {
+        let mut v = vec![1, 2, 3];
+        let len = thread::scope(|s| {
+            s.spawn(|| {
+                v.push(4);
+                v.len()
+            }).join().unwrap()
+        });
+        assert_eq!(len, 4);
+        assert_eq!(v, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_all_threads_joined_at_scope_end() {
+        let done = Mutex::new(0);
+        thread::scope(|s| {
+            for _ in 0..10 {
+                s.spawn(|| {
+                    thread::yield_now();
+                    *done.lock().unwrap() += 1;
+                });
+            }
+        });
+        assert_eq!(*done.lock().unwrap(), 10);
+    }
+
+    #[test]
+    fn test_unjoined_panic_propagates() {
+        let result = panic::catch_unwind(|| {
+            thread::scope(|s| {
+                s.spawn(|| panic!());
+            });
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_joined_panic_is_handled() {
+        thread::scope(|s| {
+            assert!(s.spawn(|| panic!()).join().is_err());
+        });
+    }
+
+    #[test]
+    fn test_scope_panic_waits_for_threads() {
+        let done = AtomicUsize::new(0);
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            thread::scope(|s| {
+                s.spawn(|| {
+                    thread::yield_now();
+                    done.store(1, SeqCst);
+                });
+                panic!("in scope");
+            });
+        }));
+        let payload = result.unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"in scope"));
+        assert_eq!(done.load(SeqCst), 1);
+    }
+
+    #[test]
+    fn test_named_scoped_thread() {
+        let name = "ada lovelace";
+        thread::scope(|s| {
+            Builder::new().name(name.to_string()).stack_size(64 * 1024).spawn_scoped(s, || {
+                assert_eq!(thread::current().name(), Some(name));
+            }).unwrap().join().unwrap();
+        });
+    }
+}
@@ -830,6 +830,12 @@ impl<'a> ExtCtxt<'a> {
     pub fn set_trace_macros(&mut self, x: bool) {
         self.ecfg.trace_mac = x
     }
+    pub fn explain_macros(&self) -> bool {
+        self.ecfg.explain_mac
+    }
+    pub fn set_explain_macros(&mut self, x: bool) {
+        self.ecfg.explain_mac = x
+    }
     pub fn ident_of(&self, st: &str) -> ast::Ident {
         ast::Ident::from_str(st)
     }
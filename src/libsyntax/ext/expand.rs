@@ -1190,6 +1190,7 @@ pub struct ExpansionConfig<'feat> {
     pub features: Option<&'feat Features>,
     pub recursion_limit: usize,
     pub trace_mac: bool,
+    pub explain_mac: bool,
     pub should_test: bool, // If false, strip `#[test]` nodes
     pub single_step: bool,
     pub keep_macs: bool,
@@ -1215,6 +1216,7 @@ impl<'feat> ExpansionConfig<'feat> {
             features: None,
             recursion_limit: 1024,
             trace_mac: false,
+            explain_mac: false,
             should_test: false,
             single_step: false,
             keep_macs: false,
@@ -1225,6 +1227,7 @@ impl<'feat> ExpansionConfig<'feat> {
         fn enable_quotes = quote,
         fn enable_asm = asm,
         fn enable_global_asm = global_asm,
+        fn enable_static_assert = static_assert,
         fn enable_log_syntax = log_syntax,
         fn enable_concat_idents = concat_idents,
         fn enable_trace_macros = trace_macros,
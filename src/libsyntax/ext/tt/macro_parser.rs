@@ -222,6 +222,19 @@ pub enum ParseResult<T> {
 /// This represents the mapping of metavars to the token trees they bind to.
 pub type NamedParseResult = ParseResult<HashMap<Ident, Rc<NamedMatch>>>;
 
+/// How far a matcher got before it failed to match, as recorded by `parse_traced`. This is used
+/// to explain each arm's failure with `-Z macro-backtrace-verbose`.
+#[derive(Default)]
+pub struct MatchTrace {
+    /// The part of the input that was matched before the failure, if any.
+    pub matched: Option<Span>,
+    /// The metavariables bound by the black-box parser along the way, as `$name:fragment`, and
+    /// the part of the input each of them matched.
+    pub fragments: Vec<(String, Span)>,
+    /// What the matcher would have accepted instead of the token it failed at.
+    pub expected: Vec<String>,
+}
+
 /// Count how many metavars are named in the given matcher `ms`.
 pub fn count_names(ms: &[TokenTree]) -> usize {
     ms.iter().fold(0, |count, elt| {
@@ -362,6 +375,16 @@ pub fn parse_failure_msg(tok: Token) -> String {
     }
 }
 
+/// Record `what()` as something a matcher position would have accepted, if we're tracing.
+fn note_expected<F: FnOnce() -> String>(expected: &mut Option<&mut Vec<String>>, what: F) {
+    if let Some(ref mut expected) = *expected {
+        let what = what();
+        if !expected.contains(&what) {
+            expected.push(what);
+        }
+    }
+}
+
 /// Perform a token equality check, ignoring syntax context (that is, an unhygienic comparison)
 fn token_name_eq(t1: &Token, t2: &Token) -> bool {
     if let (Some(id1), Some(id2)) = (t1.ident(), t2.ident()) {
@@ -391,6 +414,8 @@ fn token_name_eq(t1: &Token, t2: &Token) -> bool {
 /// - `token`: the current token of the parser.
 /// - `span`: the `Span` in the source code corresponding to the token trees we are trying to match
 ///   against the matcher positions in `cur_items`.
+/// - `expected`: if we are tracing the match, collects what the matcher positions that could not
+///   advance past `token` were expecting instead.
 ///
 /// # Returns
 ///
@@ -403,6 +428,7 @@ fn inner_parse_loop(
     bb_items: &mut SmallVector<Box<MatcherPos>>,
     token: &Token,
     span: syntax_pos::Span,
+    mut expected: Option<&mut Vec<String>>,
 ) -> ParseResult<()> {
     // Pop items from `cur_items` until it is empty.
     while let Some(mut item) = cur_items.pop() {
@@ -466,6 +492,11 @@ fn inner_parse_loop(
                     {
                         item.idx += 1;
                         next_items.push(item);
+                    } else {
+                        let sep = item.sep.as_ref().unwrap();
+                        note_expected(&mut expected, || {
+                            format!("`{}`", pprust::token_to_string(sep))
+                        });
                     }
                 }
                 // We don't need a separator. Move the "dot" back to the beginning of the matcher
@@ -479,6 +510,7 @@ fn inner_parse_loop(
             // If we are not in a repetition, then being at the end of a matcher means that we have
             // reached the potential end of the input.
             else {
+                note_expected(&mut expected, || "the end of the macro invocation".to_string());
                 eof_items.push(item);
             }
         }
@@ -527,11 +559,13 @@ fn inner_parse_loop(
 
                 // We need to match a metavar with a valid ident... call out to the black-box
                 // parser by adding an item to `bb_items`.
-                TokenTree::MetaVarDecl(_, _, id) => {
+                TokenTree::MetaVarDecl(_, bind, id) => {
                     // Built-in nonterminals never start with these tokens,
                     // so we can eliminate them from consideration.
                     if may_begin_with(&*id.name.as_str(), token) {
                         bb_items.push(item);
+                    } else {
+                        note_expected(&mut expected, || format!("`${}:{}`", bind, id));
                     }
                 }
 
@@ -562,7 +596,10 @@ fn inner_parse_loop(
                 // rules. NOTE that this is not necessarily an error unless _all_ items in
                 // `cur_items` end up doing this. There may still be some other matchers that do
                 // end up working out.
-                TokenTree::Token(..) | TokenTree::MetaVar(..) => {}
+                TokenTree::Token(_, ref t) => {
+                    note_expected(&mut expected, || format!("`{}`", pprust::token_to_string(t)));
+                }
+                TokenTree::MetaVar(..) => {}
             }
         }
     }
@@ -588,6 +625,30 @@ pub fn parse(
     ms: &[TokenTree],
     directory: Option<Directory>,
     recurse_into_modules: bool,
+) -> NamedParseResult {
+    parse_inner(sess, tts, ms, directory, recurse_into_modules, None)
+}
+
+/// Like `parse`, but also records in `trace` how far the match got, so that a failure can be
+/// explained.
+pub fn parse_traced(
+    sess: &ParseSess,
+    tts: TokenStream,
+    ms: &[TokenTree],
+    directory: Option<Directory>,
+    recurse_into_modules: bool,
+    trace: &mut MatchTrace,
+) -> NamedParseResult {
+    parse_inner(sess, tts, ms, directory, recurse_into_modules, Some(trace))
+}
+
+fn parse_inner(
+    sess: &ParseSess,
+    tts: TokenStream,
+    ms: &[TokenTree],
+    directory: Option<Directory>,
+    recurse_into_modules: bool,
+    mut trace: Option<&mut MatchTrace>,
 ) -> NamedParseResult {
     // Create a parser that can be used for the "black box" parts.
     let mut parser = Parser::new(sess, tts, directory, recurse_into_modules, true);
@@ -600,7 +661,14 @@ pub fn parse(
     let mut cur_items = SmallVector::one(initial_matcher_pos(ms.to_owned(), parser.span.lo()));
     let mut next_items = Vec::new();
 
+    // The span of the first token, for tracing how much of the input has been matched
+    let first_span = parser.span;
+
     loop {
+        if let Some(ref mut trace) = trace {
+            trace.expected.clear();
+        }
+
         // Matcher positions black-box parsed by parser.rs (`parser`)
         let mut bb_items = SmallVector::new();
 
@@ -619,6 +687,7 @@ pub fn parse(
             &mut bb_items,
             &parser.token,
             parser.span,
+            trace.as_mut().map(|trace| &mut trace.expected),
         ) {
             Success(_) => {}
             Failure(sp, tok) => return Failure(sp, tok),
@@ -683,6 +752,9 @@ pub fn parse(
         else if !next_items.is_empty() {
             // Now process the next token
             cur_items.extend(next_items.drain(..));
+            if let Some(ref mut trace) = trace {
+                trace.matched = Some(first_span.to(parser.span));
+            }
             parser.bump();
         }
         // Finally, we have the case where we need to call the black-box parser to get some
@@ -691,12 +763,18 @@ pub fn parse(
             assert_eq!(bb_items.len(), 1);
 
             let mut item = bb_items.pop().unwrap();
-            if let TokenTree::MetaVarDecl(span, _, ident) = item.top_elts.get_tt(item.idx) {
+            if let TokenTree::MetaVarDecl(span, bind, ident) = item.top_elts.get_tt(item.idx) {
                 let match_cur = item.match_cur;
+                let nt_lo = parser.span;
                 item.push_match(
                     match_cur,
                     MatchedNonterminal(Rc::new(parse_nt(&mut parser, span, &ident.name.as_str()))),
                 );
+                if let Some(ref mut trace) = trace {
+                    trace.matched = Some(first_span.to(parser.prev_span));
+                    trace.fragments.push((format!("${}:{}", bind, ident),
+                                          nt_lo.to(parser.prev_span)));
+                }
                 item.idx += 1;
                 item.match_cur += 1;
             } else {
@@ -9,20 +9,22 @@
 // except according to those terms.
 
 use {ast, attr};
-use syntax_pos::{Span, DUMMY_SP};
+use syntax_pos::{MultiSpan, Span, DUMMY_SP};
 use ext::base::{DummyResult, ExtCtxt, MacResult, SyntaxExtension};
 use ext::base::{NormalTT, TTMacroExpander};
 use ext::expand::{Expansion, ExpansionKind};
 use ext::tt::macro_parser::{Success, Error, Failure};
-use ext::tt::macro_parser::{MatchedSeq, MatchedNonterminal};
+use ext::tt::macro_parser::{MatchedSeq, MatchedNonterminal, MatchTrace};
 use ext::tt::macro_parser::{parse, parse_failure_msg};
 use ext::tt::quoted;
 use ext::tt::transcribe::transcribe;
+use errors::DiagnosticBuilder;
 use feature_gate::{self, emit_feature_err, Features, GateIssue};
 use parse::{Directory, ParseSess};
 use parse::parser::Parser;
-use parse::token::{self, NtTT};
+use parse::token::{self, NtTT, Token};
 use parse::token::Token::*;
+use print::pprust;
 use symbol::Symbol;
 use tokenstream::{TokenStream, TokenTree};
 
@@ -85,6 +87,12 @@ impl TTMacroExpander for MacroRulesMacroExpander {
 }
 
 fn trace_macros_note(cx: &mut ExtCtxt, sp: Span, message: String) {
+    // When explaining macros, indent the notes by expansion depth so that they form a tree.
+    let message = if cx.explain_macros() {
+        format!("{}{}", "  ".repeat(cx.current_expansion.depth), message)
+    } else {
+        message
+    };
     let sp = sp.macro_backtrace().last().map(|trace| trace.call_site).unwrap_or(sp);
     let values: &mut Vec<String> = cx.expansions.entry(sp).or_insert_with(Vec::new);
     values.push(message);
@@ -99,20 +107,34 @@ fn generic_extension<'cx>(cx: &'cx mut ExtCtxt,
                           rhses: &[quoted::TokenTree])
                           -> Box<MacResult+'cx> {
     if cx.trace_macros() {
-        trace_macros_note(cx, sp, format!("expanding `{}! {{ {} }}`", name, arg));
+        let mut message = format!("expanding `{}! {{ {} }}`", name, arg);
+        if cx.explain_macros() {
+            message.push_str(&format!(" at {}", cx.codemap().span_to_string(sp)));
+        }
+        trace_macros_note(cx, sp, message);
     }
 
     // Which arm's failure should we report? (the one furthest along)
     let mut best_fail_spot = DUMMY_SP;
     let mut best_fail_tok = None;
 
+    // How each arm failed, if we're explaining failures
+    let mut arm_failures = Vec::new();
+
     for (i, lhs) in lhses.iter().enumerate() { // try each arm's matchers
         let lhs_tt = match *lhs {
             quoted::TokenTree::Delimited(_, ref delim) => &delim.tts[..],
             _ => cx.span_bug(sp, "malformed macro lhs")
         };
 
-        match TokenTree::parse(cx, lhs_tt, arg.clone()) {
+        let mut trace = MatchTrace::default();
+        let result = if cx.explain_macros() {
+            TokenTree::parse_traced(cx, lhs_tt, arg.clone(), &mut trace)
+        } else {
+            TokenTree::parse(cx, lhs_tt, arg.clone())
+        };
+
+        match result {
             Success(named_matches) => {
                 let rhs = match rhses[i] {
                     // ignore delimiters
@@ -161,10 +183,20 @@ fn generic_extension<'cx>(cx: &'cx mut ExtCtxt,
                     macro_ident: name
                 })
             }
-            Failure(sp, tok) => if sp.lo() >= best_fail_spot.lo() {
-                best_fail_spot = sp;
-                best_fail_tok = Some(tok);
-            },
+            Failure(sp, tok) => {
+                if cx.explain_macros() {
+                    arm_failures.push(ArmFailure {
+                        lhs_span: lhs.span(),
+                        fail_span: sp,
+                        fail_tok: tok.clone(),
+                        trace,
+                    });
+                }
+                if sp.lo() >= best_fail_spot.lo() {
+                    best_fail_spot = sp;
+                    best_fail_tok = Some(tok);
+                }
+            }
             Error(err_sp, ref msg) => {
                 cx.span_fatal(err_sp.substitute_dummy(sp), &msg[..])
             }
@@ -172,11 +204,67 @@ fn generic_extension<'cx>(cx: &'cx mut ExtCtxt,
     }
 
     let best_fail_msg = parse_failure_msg(best_fail_tok.expect("ran no matchers"));
-    cx.span_err(best_fail_spot.substitute_dummy(sp), &best_fail_msg);
+    let mut err = cx.struct_span_err(best_fail_spot.substitute_dummy(sp), &best_fail_msg);
+    if cx.explain_macros() {
+        explain_match_failure(cx, &mut err, sp, &arm_failures);
+    }
+    err.emit();
     cx.trace_macros_diag();
     DummyResult::any(sp)
 }
 
+/// How one arm of a macro failed to match an invocation.
+struct ArmFailure {
+    lhs_span: Span,
+    fail_span: Span,
+    fail_tok: Token,
+    trace: MatchTrace,
+}
+
+/// Adds notes to `err` describing how far each arm got before failing, followed by the chain
+/// of expansions that led to the failing invocation at `sp`.
+fn explain_match_failure(cx: &ExtCtxt,
+                         err: &mut DiagnosticBuilder,
+                         sp: Span,
+                         arm_failures: &[ArmFailure]) {
+    let describe = |span: Span| {
+        match cx.codemap().span_to_snippet(span) {
+            Ok(ref snippet) if !snippet.is_empty() => format!("`{}`", snippet),
+            _ => "nothing".to_string(),
+        }
+    };
+
+    for (i, failure) in arm_failures.iter().enumerate() {
+        let fail_at = match failure.fail_tok {
+            token::Eof => "the end of the macro invocation".to_string(),
+            ref tok => format!("`{}`", pprust::token_to_string(tok)),
+        };
+
+        let mut spans = MultiSpan::from_span(failure.lhs_span);
+        spans.push_span_label(failure.fail_span.substitute_dummy(sp),
+                              format!("arm {} failed at {}", i + 1, fail_at));
+        err.span_note(spans, &format!("arm {} did not match", i + 1));
+
+        match failure.trace.matched {
+            Some(matched) => err.note(&format!("arm {} matched {} before failing",
+                                               i + 1, describe(matched))),
+            None => err.note(&format!("arm {} failed at the first token", i + 1)),
+        };
+        for &(ref fragment, span) in &failure.trace.fragments {
+            err.note(&format!("`{}` matched {}", fragment, describe(span)));
+        }
+        if !failure.trace.expected.is_empty() {
+            err.note(&format!("arm {} expected {} instead of {}",
+                              i + 1, failure.trace.expected.join(" or "), fail_at));
+        }
+    }
+
+    for trace in sp.macro_backtrace() {
+        err.span_note(trace.call_site, &format!("in this expansion of `{}`",
+                                                trace.macro_decl_name));
+    }
+}
+
 // Note that macro-by-example's input is also matched against a token tree:
 //                   $( $lhs:tt => $rhs:tt );+
 //
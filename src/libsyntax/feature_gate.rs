@@ -449,6 +449,12 @@ declare_features! (
 
     // Use `?` as the Kleene "at most one" operator
     (active, macro_at_most_once_rep, "1.25.0", Some(48075)),
+
+    // Allows `if`, `match`, `while`, `loop` and `let` statements in `const fn`
+    (active, const_control_flow, "1.25.0", None),
+
+    // Allows the `static_assert!` macro
+    (active, static_assert, "1.25.0", None),
 );
 
 declare_features! (
@@ -747,6 +753,12 @@ pub const BUILTIN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeG
                                      is just used for rustc unit tests \
                                      and will never be stable",
                                     cfg_fn!(rustc_attrs))),
+    ("rustc_static_assert", Whitelisted, Gated(Stability::Unstable,
+                                               "rustc_attrs",
+                                               "the `#[rustc_static_assert]` attribute \
+                                                is an implementation detail of \
+                                                `static_assert!`",
+                                               cfg_fn!(rustc_attrs))),
     ("rustc_error", Whitelisted, Gated(Stability::Unstable,
                                        "rustc_attrs",
                                        "the `#[rustc_error]` attribute \
@@ -1226,6 +1238,9 @@ pub const EXPLAIN_ASM: &'static str =
 pub const EXPLAIN_GLOBAL_ASM: &'static str =
     "`global_asm!` is not stable enough for use and is subject to change";
 
+pub const EXPLAIN_STATIC_ASSERT: &'static str =
+    "`static_assert!` is not stable enough for use and is subject to change";
+
 pub const EXPLAIN_LOG_SYNTAX: &'static str =
     "`log_syntax!` is not stable enough for use and is subject to change";
 
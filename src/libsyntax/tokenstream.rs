@@ -112,6 +112,19 @@ impl TokenTree {
         macro_parser::parse(cx.parse_sess(), tts, mtch, Some(directory), true)
     }
 
+    /// Like `parse`, but records how far the match got in `trace`.
+    pub fn parse_traced(cx: &base::ExtCtxt,
+                        mtch: &[quoted::TokenTree],
+                        tts: TokenStream,
+                        trace: &mut macro_parser::MatchTrace)
+                        -> macro_parser::NamedParseResult {
+        let directory = Directory {
+            path: cx.current_expansion.module.directory.clone(),
+            ownership: cx.current_expansion.directory_ownership,
+        };
+        macro_parser::parse_traced(cx.parse_sess(), tts, mtch, Some(directory), true, trace)
+    }
+
     /// Check if this TokenTree is equal to the other, regardless of span information.
     pub fn eq_unspanned(&self, other: &TokenTree) -> bool {
         match (self, other) {
@@ -35,6 +35,7 @@ mod format;
 mod format_foreign;
 mod global_asm;
 mod log_syntax;
+mod static_assert;
 mod trace_macros;
 
 pub mod proc_macro_registrar;
@@ -112,6 +113,15 @@ pub fn register_builtins(resolver: &mut syntax::ext::base::Resolver,
         compile_error: compile_error::expand_compile_error,
     }
 
+    // static_assert emits the unstable `rustc_static_assert` attribute.
+    register(Symbol::intern("static_assert"),
+             NormalTT {
+                expander: Box::new(static_assert::expand_static_assert),
+                def_info: None,
+                allow_internal_unstable: true,
+                allow_internal_unsafe: false,
+            });
+
     // format_args uses `unstable` things internally.
     register(Symbol::intern("format_args"),
              NormalTT {
@@ -0,0 +1,127 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Compile-time assertions.
+//!
+//! `static_assert!(EXPR)` and `static_assert!(EXPR, "message")` expand to
+//!
+//! ```ignore
+//! #[allow(dead_code)]
+//! #[rustc_static_assert = "message"]
+//! const __STATIC_ASSERT: $crate::primitive::Bool = EXPR;
+//! ```
+//!
+//! The constant is evaluated by the compiler after type checking, and a
+//! `false` value is reported as an error, together with the values of the
+//! operands if `EXPR` is a comparison.
+
+use syntax::ast;
+use syntax::ext::base;
+use syntax::ext::base::*;
+use syntax::ext::build::AstBuilder;
+use syntax::feature_gate;
+use syntax::parse::token;
+use syntax::print::pprust;
+use syntax::symbol::Symbol;
+use syntax_pos::Span;
+use syntax::tokenstream;
+
+use syntax::util::small_vector::SmallVector;
+
+pub const MACRO: &'static str = "static_assert";
+
+pub fn expand_static_assert<'cx>(cx: &'cx mut ExtCtxt,
+                                 sp: Span,
+                                 tts: &[tokenstream::TokenTree]) -> Box<base::MacResult + 'cx> {
+    if !cx.ecfg.enable_static_assert() {
+        feature_gate::emit_feature_err(&cx.parse_sess,
+                                       MACRO,
+                                       sp,
+                                       feature_gate::GateIssue::Language,
+                                       feature_gate::EXPLAIN_STATIC_ASSERT);
+        return DummyResult::any(sp);
+    }
+
+    if tts.is_empty() {
+        cx.span_err(sp, "static_assert! takes 1 or 2 arguments");
+        return DummyResult::any(sp);
+    }
+
+    let mut p = cx.new_parser_from_tts(tts);
+    let cond = panictry!(p.parse_expr());
+    let message = if p.eat(&token::Comma) && p.token != token::Eof {
+        match expr_to_string(cx,
+                             panictry!(p.parse_expr()),
+                             "static assertion message must be a string literal") {
+            Some((s, _)) => s,
+            None => return DummyResult::any(sp),
+        }
+    } else {
+        Symbol::intern(&format!("static assertion failed: `{}`", pprust::expr_to_string(&cond)))
+    };
+    if p.token != token::Eof {
+        cx.span_err(sp, "static_assert! takes 1 or 2 arguments");
+        return DummyResult::any(sp);
+    }
+
+    // The `rustc_static_assert` attribute is only allowed in the expansion.
+    let sp = sp.with_ctxt(sp.ctxt().apply_mark(cx.current_expansion.mark));
+    let attrs = vec![
+        cx.attribute(sp, cx.meta_list(sp,
+                                      Symbol::intern("allow"),
+                                      vec![cx.meta_list_item_word(sp,
+                                                                  Symbol::intern("dead_code"))])),
+        cx.attribute(sp, cx.meta_name_value(sp,
+                                            Symbol::intern("rustc_static_assert"),
+                                            ast::LitKind::Str(message, ast::StrStyle::Cooked))),
+    ];
+    let ident = ast::Ident::with_empty_ctxt(Symbol::gensym("__STATIC_ASSERT"));
+    // `bool` itself could name some other type at the call site.
+    let ty = cx.ty_path(cx.path_global(sp, cx.std_path(&["primitive", "Bool"])));
+
+    let item = cx.item_const(sp, ident, ty.clone(), cond.clone()).map(|item| ast::Item {
+        attrs: attrs.clone(),
+        ..item
+    });
+    let stmt = ast::Stmt {
+        id: ast::DUMMY_NODE_ID,
+        node: ast::StmtKind::Item(item.clone()),
+        span: sp,
+    };
+    let impl_item = ast::ImplItem {
+        id: ast::DUMMY_NODE_ID,
+        ident,
+        vis: ast::Visibility::Inherited,
+        defaultness: ast::Defaultness::Final,
+        attrs: attrs.clone(),
+        generics: ast::Generics::default(),
+        node: ast::ImplItemKind::Const(ty.clone(), cond.clone()),
+        span: sp,
+        tokens: None,
+    };
+    // Rejected after type checking, with a better error than a failed expansion.
+    let trait_item = ast::TraitItem {
+        id: ast::DUMMY_NODE_ID,
+        ident,
+        attrs,
+        generics: ast::Generics::default(),
+        node: ast::TraitItemKind::Const(ty, Some(cond)),
+        span: sp,
+        tokens: None,
+    };
+
+    Box::new(MacEager {
+        items: Some(SmallVector::one(item)),
+        stmts: Some(SmallVector::one(stmt)),
+        impl_items: Some(SmallVector::one(impl_item)),
+        trait_items: Some(SmallVector::one(trait_item)),
+        ..Default::default()
+    })
+}
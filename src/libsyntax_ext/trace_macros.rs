@@ -34,8 +34,14 @@ pub fn expand_trace_macros(cx: &mut ExtCtxt,
         }
         (1, Some(&TokenTree::Token(_, ref tok))) if tok.is_keyword(keywords::False) => {
             cx.set_trace_macros(false);
+            cx.set_explain_macros(false);
         }
-        _ => cx.span_err(sp, "trace_macros! accepts only `true` or `false`"),
+        (1, Some(&TokenTree::Token(_, ref tok)))
+                if tok.ident().map_or(false, |ident| ident.name == "explain") => {
+            cx.set_trace_macros(true);
+            cx.set_explain_macros(true);
+        }
+        _ => cx.span_err(sp, "trace_macros! accepts only `true`, `false` or `explain`"),
     }
 
     base::DummyResult::any(sp)
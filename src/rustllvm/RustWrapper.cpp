@@ -15,6 +15,7 @@
 #include "llvm/IR/Instructions.h"
 #include "llvm/Object/Archive.h"
 #include "llvm/Object/ObjectFile.h"
+#include "llvm/Object/SymbolSize.h"
 #include "llvm/Bitcode/BitcodeWriterPass.h"
 
 #include "llvm/IR/CallSite.h"
@@ -940,6 +941,65 @@ extern "C" size_t LLVMRustGetSectionName(LLVMSectionIteratorRef SI,
   return Ret.size();
 }
 
+typedef void (*LLVMRustSymbolSizeCallback)(void *, const char *, size_t,
+                                           const char *, size_t, uint64_t);
+
+// Calls `Callback` with the name, section name and size of every function and
+// data symbol defined in the object file contained in `Data`. Symbol sizes
+// are computed the same way `llvm-nm -S` does, so that this also works for
+// formats which don't record them, like Mach-O.
+extern "C" bool LLVMRustForEachSymbolSize(const char *Data, size_t Len,
+                                          LLVMRustSymbolSizeCallback Callback,
+                                          void *Ctx) {
+#if LLVM_VERSION_GE(4, 0)
+  MemoryBufferRef Buf(StringRef(Data, Len), "");
+  Expected<std::unique_ptr<ObjectFile>> ObjOrErr =
+      ObjectFile::createObjectFile(Buf);
+  if (!ObjOrErr) {
+    LLVMRustSetLastError(toString(ObjOrErr.takeError()).c_str());
+    return false;
+  }
+  ObjectFile &Obj = **ObjOrErr;
+
+  for (auto &SymAndSize : computeSymbolSizes(Obj)) {
+    const SymbolRef &Sym = SymAndSize.first;
+
+    Expected<SymbolRef::Type> TypeOrErr = Sym.getType();
+    if (!TypeOrErr) {
+      consumeError(TypeOrErr.takeError());
+      continue;
+    }
+    if (*TypeOrErr != SymbolRef::ST_Function &&
+        *TypeOrErr != SymbolRef::ST_Data)
+      continue;
+
+    Expected<section_iterator> SecOrErr = Sym.getSection();
+    if (!SecOrErr) {
+      consumeError(SecOrErr.takeError());
+      continue;
+    }
+    if (*SecOrErr == Obj.section_end())
+      continue;
+    StringRef SecName;
+    if ((*SecOrErr)->getName(SecName))
+      continue;
+
+    Expected<StringRef> NameOrErr = Sym.getName();
+    if (!NameOrErr) {
+      consumeError(NameOrErr.takeError());
+      continue;
+    }
+
+    Callback(Ctx, NameOrErr->data(), NameOrErr->size(), SecName.data(),
+             SecName.size(), SymAndSize.second);
+  }
+  return true;
+#else
+  LLVMRustSetLastError("symbol size reports require LLVM 4.0 or later");
+  return false;
+#endif
+}
+
 // LLVMArrayType function does not support 64-bit ElementCount
 extern "C" LLVMTypeRef LLVMRustArrayType(LLVMTypeRef ElementTy,
                                          uint64_t ElementCount) {
@@ -1019,6 +1079,7 @@ enum class LLVMRustDiagnosticKind {
   OptimizationRemarkAnalysisAliasing,
   OptimizationRemarkOther,
   OptimizationFailure,
+  PGOProfile,
 };
 
 static LLVMRustDiagnosticKind toRust(DiagnosticKind Kind) {
@@ -1041,6 +1102,8 @@ static LLVMRustDiagnosticKind toRust(DiagnosticKind Kind) {
     return LLVMRustDiagnosticKind::OptimizationRemarkAnalysisFPCommute;
   case DK_OptimizationRemarkAnalysisAliasing:
     return LLVMRustDiagnosticKind::OptimizationRemarkAnalysisAliasing;
+  case DK_PGOProfile:
+    return LLVMRustDiagnosticKind::PGOProfile;
   default:
     return (Kind >= DK_FirstRemark && Kind <= DK_LastRemark)
                ? LLVMRustDiagnosticKind::OptimizationRemarkOther
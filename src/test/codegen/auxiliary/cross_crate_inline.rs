@@ -0,0 +1,32 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -O -Z cross-crate-inline-threshold=10
+
+#![crate_type = "lib"]
+
+pub struct Point {
+    x: u32,
+    y: u32,
+}
+
+impl Point {
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+}
+
+pub fn leaf(a: u32) -> u32 {
+    a ^ 0x1234_5678
+}
+
+pub fn not_a_leaf(p: &Point) -> u32 {
+    leaf(p.x) + leaf(p.y)
+}
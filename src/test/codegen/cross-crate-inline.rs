@@ -0,0 +1,41 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:cross_crate_inline.rs
+// compile-flags: -O -Z cross-crate-inline-threshold=10
+
+#![crate_type = "lib"]
+
+extern crate cross_crate_inline;
+
+use cross_crate_inline::Point;
+
+// CHECK-LABEL: @use_accessor
+#[no_mangle]
+pub fn use_accessor(p: &Point) -> u32 {
+// CHECK-NOT: call
+// CHECK: ret i32
+    p.x()
+}
+
+// CHECK-LABEL: @use_leaf
+#[no_mangle]
+pub fn use_leaf(a: u32) -> u32 {
+// CHECK-NOT: call
+// CHECK: xor i32 %a, 305419896
+    cross_crate_inline::leaf(a)
+}
+
+// CHECK-LABEL: @use_not_a_leaf
+#[no_mangle]
+pub fn use_not_a_leaf(p: &Point) -> u32 {
+// CHECK: call {{.*}}not_a_leaf
+    cross_crate_inline::not_a_leaf(p)
+}
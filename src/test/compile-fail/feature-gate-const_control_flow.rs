@@ -0,0 +1,19 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(const_fn)]
+
+const fn sign(n: i32) -> i32 { if n < 0 { -1 } else { 1 } } //~ ERROR E0019
+
+const SIGN: i32 = if 1 < 0 { -1 } else { 1 }; //~ ERROR E0019
+
+static ABS: i32 = if -1 < 0 { 1 } else { -1 }; //~ ERROR E0019
+
+fn main() {}
@@ -0,0 +1,42 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![feature(static_assert)]
+
+const SIZE: usize = 4;
+
+trait HasAlign {
+    const ALIGN: usize;
+}
+
+struct Foo;
+
+impl HasAlign for Foo {
+    const ALIGN: usize = 2;
+}
+
+impl Foo {
+    const LEN: u32 = 3;
+    static_assert!(Self::LEN > 5, "LEN is too small"); //~ ERROR LEN is too small
+}
+
+trait Checked {
+    static_assert!(SIZE == 4); //~ ERROR `static_assert!` cannot be used in a trait
+}
+
+static_assert!(SIZE == 4);
+static_assert!(SIZE == 8); //~ ERROR static assertion failed: `SIZE == 8`
+static_assert!(<Foo as HasAlign>::ALIGN == 2);
+static_assert!(<Foo as HasAlign>::ALIGN >= SIZE); //~ ERROR static assertion failed
+
+fn main() {
+    static_assert!(SIZE < 2); //~ ERROR static assertion failed: `SIZE < 2`
+}
@@ -11,12 +11,12 @@
 #![feature(trace_macros)]
 
 fn main() {
-    trace_macros!(); //~ ERROR trace_macros! accepts only `true` or `false`
-    trace_macros!(1); //~ ERROR trace_macros! accepts only `true` or `false`
-    trace_macros!(ident); //~ ERROR trace_macros! accepts only `true` or `false`
-    trace_macros!(for); //~ ERROR trace_macros! accepts only `true` or `false`
-    trace_macros!(true,); //~ ERROR trace_macros! accepts only `true` or `false`
-    trace_macros!(false 1); //~ ERROR trace_macros! accepts only `true` or `false`
+    trace_macros!(); //~ ERROR trace_macros! accepts only `true`, `false` or `explain`
+    trace_macros!(1); //~ ERROR trace_macros! accepts only `true`, `false` or `explain`
+    trace_macros!(ident); //~ ERROR trace_macros! accepts only `true`, `false` or `explain`
+    trace_macros!(for); //~ ERROR trace_macros! accepts only `true`, `false` or `explain`
+    trace_macros!(true,); //~ ERROR trace_macros! accepts only `true`, `false` or `explain`
+    trace_macros!(false 1); //~ ERROR trace_macros! accepts only `true`, `false` or `explain`
 
 
     // should be fine:
@@ -0,0 +1,25 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[no_mangle]
+#[inline(never)]
+pub extern "C" fn startup_a() -> u32 { 1 }
+
+#[no_mangle]
+#[inline(never)]
+pub extern "C" fn startup_b() -> u32 { 2 }
+
+#[no_mangle]
+#[inline(never)]
+pub extern "C" fn startup_c() -> u32 { 3 }
+
+fn main() {
+    println!("{}", startup_a() + startup_b() + startup_c());
+}
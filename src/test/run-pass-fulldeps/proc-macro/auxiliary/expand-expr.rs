@@ -0,0 +1,32 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// no-prefer-dynamic
+
+#![feature(proc_macro)]
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::{TokenStream, TokenNode, Literal};
+
+/// Expands to the length of the string literal its input expands to.
+#[proc_macro]
+pub fn expanded_len(input: TokenStream) -> TokenStream {
+    let expanded = input.expand_expr().expect("failed to expand input");
+    let mut trees = expanded.into_iter();
+    let literal = match trees.next().map(|tree| tree.kind) {
+        Some(TokenNode::Literal(literal)) => literal.to_string(),
+        _ => panic!("expected the input to expand to a literal"),
+    };
+    assert!(trees.next().is_none());
+    assert!(literal.starts_with('"') && literal.ends_with('"'));
+    TokenNode::Literal(Literal::u32(literal.len() as u32 - 2)).into()
+}
@@ -0,0 +1,28 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:expand-expr.rs
+// ignore-stage1
+
+#![feature(proc_macro)]
+
+extern crate expand_expr;
+use expand_expr::expanded_len;
+
+macro_rules! greeting {
+    () => { "hello" }
+}
+
+fn main() {
+    assert_eq!(expanded_len!("abc"), 3);
+    assert_eq!(expanded_len!(concat!("ab", "cd", 1)), 5);
+    assert_eq!(expanded_len!(stringify!(a + b)), 5);
+    assert_eq!(expanded_len!(concat!(greeting!(), ", world")), 12);
+}
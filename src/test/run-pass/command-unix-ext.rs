@@ -0,0 +1,96 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-windows - this is a unix-specific test
+// ignore-cloudabi no processes
+// ignore-emscripten no processes
+
+#![feature(process_unix_ext, libc)]
+
+extern crate libc;
+
+use std::env;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::os::unix::io::FromRawFd;
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+
+fn nofile_limit() -> (u64, u64) {
+    unsafe {
+        let mut limit: libc::rlimit = std::mem::zeroed();
+        assert_eq!(libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit), 0);
+        (limit.rlim_cur as u64, limit.rlim_max as u64)
+    }
+}
+
+fn main() {
+    if let Some(arg) = env::args().skip(1).next() {
+        unsafe {
+            match &arg[..] {
+                "setsid" => {
+                    assert_eq!(libc::getsid(0), libc::getpid());
+                    assert_eq!(libc::getpgrp(), libc::getpid());
+                }
+                "pgroup" => {
+                    assert!(libc::getsid(0) != libc::getpid());
+                    assert_eq!(libc::getpgrp(), libc::getpid());
+                }
+                "rlimit" => assert_eq!(nofile_limit().0, 32),
+                "fd" => {
+                    let mut f = File::from_raw_fd(10);
+                    f.write_all(b"hello").unwrap();
+                }
+                #[cfg(target_os = "linux")]
+                "pdeathsig" => {
+                    let mut signal = 0;
+                    assert_eq!(libc::prctl(libc::PR_GET_PDEATHSIG, &mut signal), 0);
+                    assert_eq!(signal, libc::SIGKILL);
+                }
+                _ => panic!("unknown argument: {}", arg),
+            }
+        }
+        return
+    }
+
+    let me = env::current_exe().unwrap();
+
+    let status = Command::new(&me).arg("setsid").setsid(true).status().unwrap();
+    assert!(status.success());
+
+    let status = Command::new(&me).arg("pgroup").pgroup(0).status().unwrap();
+    assert!(status.success());
+
+    let (_, hard) = nofile_limit();
+    let status = Command::new(&me).arg("rlimit")
+                                  .rlimit(libc::RLIMIT_NOFILE as i32, 32, hard)
+                                  .status().unwrap();
+    assert!(status.success());
+
+    let mut fds = [0; 2];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+    let (mut reader, writer) = unsafe {
+        (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1]))
+    };
+    let status = Command::new(&me).arg("fd").inherit_fd(fds[1], 10).status().unwrap();
+    assert!(status.success());
+    drop(writer);
+    let mut s = String::new();
+    reader.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "hello");
+
+    #[cfg(target_os = "linux")]
+    {
+        let status = Command::new(&me).arg("pdeathsig")
+                                      .parent_death_signal(libc::SIGKILL)
+                                      .status().unwrap();
+        assert!(status.success());
+    }
+}
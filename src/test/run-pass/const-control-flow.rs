@@ -0,0 +1,54 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test branches, loops and local variables in constant and static
+// initializers.
+
+#![feature(const_control_flow)]
+
+const FACT: usize = {
+    let mut n = 5;
+    let mut acc = 1;
+    while n > 1 {
+        acc *= n;
+        n -= 1;
+    }
+    acc
+};
+
+static ONES: u32 = {
+    let mut x = 0xf0u32;
+    let mut count = 0;
+    loop {
+        if x == 0 {
+            break;
+        }
+        count += x & 1;
+        x >>= 1;
+    }
+    count
+};
+
+const SIGN: i32 = if FACT > 100 { 1 } else { -1 };
+
+const DIGIT: u8 = match FACT % 10 {
+    0 => b'0',
+    n => b'0' + n as u8,
+};
+
+fn main() {
+    let _: [u8; 120] = [0; FACT];
+    let _: [u8; 1] = [0; SIGN as usize];
+    let _: [u8; 48] = [0; DIGIT as usize];
+    assert_eq!(FACT, 120);
+    assert_eq!(ONES, 4);
+    assert_eq!(SIGN, 1);
+    assert_eq!(DIGIT, b'0');
+}
@@ -0,0 +1,58 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test branches, loops and local variables in `const fn`.
+
+#![feature(const_fn, const_control_flow)]
+
+const fn factorial(mut n: u32) -> u32 {
+    let mut acc = 1;
+    while n > 1 {
+        acc *= n;
+        n -= 1;
+    }
+    acc
+}
+
+const fn reverse_bits(byte: u8) -> u8 {
+    let mut out = 0;
+    let mut i = 0;
+    loop {
+        if i == 8 {
+            break;
+        }
+        out = (out << 1) | ((byte >> i) & 1);
+        i += 1;
+    }
+    out
+}
+
+const fn name_len(n: u8) -> usize {
+    match n {
+        0 => 4,
+        1 | 2 => 3,
+        _ => 4,
+    }
+}
+
+const FACT: u32 = factorial(5);
+const REV: u8 = reverse_bits(0b0000_0110);
+const LEN: usize = name_len(2);
+
+fn main() {
+    assert_eq!(FACT, 120);
+    assert_eq!(REV, 0b0110_0000);
+    assert_eq!(LEN, 3);
+
+    let array = [0u8; factorial(3) as usize];
+    assert_eq!(array.len(), 6);
+    let array = [0u8; LEN];
+    assert_eq!(array.len(), 3);
+}
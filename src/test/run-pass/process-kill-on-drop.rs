@@ -0,0 +1,54 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-cloudabi no processes
+// ignore-emscripten no processes
+
+#![feature(process_kill_on_drop)]
+
+use std::env;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    if args.len() != 1 {
+        match &args[1][..] {
+            "sleep" => thread::sleep(Duration::new(1_000, 0)),
+            _ => {}
+        }
+        return
+    }
+
+    // Once the child is killed its end of the pipe is closed, so reading
+    // from it finishes instead of blocking for as long as the child sleeps.
+    let mut child = Command::new(env::current_exe().unwrap())
+                            .arg("sleep")
+                            .stdout(Stdio::piped())
+                            .kill_on_drop(true)
+                            .spawn()
+                            .unwrap();
+    let mut stdout = child.stdout.take().unwrap();
+    drop(child);
+    let mut out = Vec::new();
+    stdout.read_to_end(&mut out).unwrap();
+    assert!(out.is_empty());
+
+    // Dropping a child that has already exited is fine.
+    let mut child = Command::new(env::current_exe().unwrap())
+                            .arg("return-quickly")
+                            .kill_on_drop(true)
+                            .spawn()
+                            .unwrap();
+    assert!(child.wait().unwrap().success());
+    drop(child);
+}
@@ -0,0 +1,39 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![feature(const_fn, static_assert)]
+
+const SIZE: usize = 4;
+
+const fn double(x: usize) -> usize { x * 2 }
+
+struct Foo;
+
+impl Foo {
+    const LEN: u32 = 3;
+    static_assert!(Self::LEN == 3);
+}
+
+static_assert!(SIZE == 4);
+static_assert!(SIZE * 2 == 8, "SIZE must be 4");
+static_assert!(double(SIZE) == 8);
+static_assert!(SIZE == 4);
+
+mod shadowed {
+    #[allow(non_camel_case_types, dead_code)]
+    struct bool;
+
+    static_assert!(super::SIZE == 4);
+}
+
+fn main() {
+    static_assert!(SIZE > 2);
+}
@@ -0,0 +1,55 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-cloudabi no processes
+// ignore-emscripten no processes
+
+#![feature(child_wait_timeout)]
+
+use std::env;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    if args.len() != 1 {
+        match &args[1][..] {
+            "sleep" => thread::sleep(Duration::new(1_000, 0)),
+            _ => {}
+        }
+        return
+    }
+
+    let mut me = Command::new(env::current_exe().unwrap())
+                         .arg("sleep")
+                         .spawn()
+                         .unwrap();
+    let start = Instant::now();
+    let maybe_status = me.wait_timeout(Duration::from_millis(100)).unwrap();
+    assert!(maybe_status.is_none());
+    assert!(start.elapsed() >= Duration::from_millis(100));
+
+    me.kill().unwrap();
+    let status = me.wait_timeout(Duration::from_secs(60)).unwrap().unwrap();
+    assert!(!status.success());
+    let status = me.wait_timeout(Duration::from_millis(0)).unwrap().unwrap();
+    assert!(!status.success());
+    assert!(!me.wait().unwrap().success());
+
+    let mut me = Command::new(env::current_exe().unwrap())
+                         .arg("return-quickly")
+                         .spawn()
+                         .unwrap();
+    let status = me.wait_timeout(Duration::from_secs(60)).unwrap().unwrap();
+    assert!(status.success());
+    let status = me.try_wait().unwrap().unwrap();
+    assert!(status.success());
+}
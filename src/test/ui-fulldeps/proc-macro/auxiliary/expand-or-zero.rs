@@ -0,0 +1,26 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// no-prefer-dynamic
+#![feature(proc_macro)]
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::{TokenStream, TokenNode, Literal};
+
+// Expands to the expanded input, or to `0` if it can't be expanded.
+#[proc_macro]
+pub fn expand_or_zero(input: TokenStream) -> TokenStream {
+    match input.expand_expr() {
+        Ok(expanded) => expanded,
+        Err(_) => TokenNode::Literal(Literal::u32(0)).into(),
+    }
+}
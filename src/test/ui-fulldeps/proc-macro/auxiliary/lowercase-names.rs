@@ -0,0 +1,47 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// no-prefer-dynamic
+#![feature(proc_macro)]
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::{TokenStream, TokenNode, Span, Diagnostic, Level};
+
+#[proc_macro]
+pub fn lowercase_names(input: TokenStream) -> TokenStream {
+    let mut seen: Vec<(String, Span)> = Vec::new();
+    for tree in input {
+        let span = tree.span;
+        let name = match tree.kind {
+            TokenNode::Term(term) => term.as_str().to_string(),
+            _ => continue,
+        };
+
+        if name.chars().any(|c| c.is_uppercase()) {
+            let text = span.source_text().unwrap();
+            span.error(format!("name `{}` is not lowercase", text))
+                .span_suggestion(span, "use a lowercase name", name.to_lowercase())
+                .emit();
+        }
+
+        if let Some(&(_, first)) = seen.iter().find(|&&(ref n, _)| *n == name) {
+            Diagnostic::spanned(vec![first, span], Level::Error,
+                                format!("name `{}` is listed twice", name))
+                .span_label(first, "first listed here")
+                .span_label(span, "listed again here")
+                .emit();
+        }
+        seen.push((name, span));
+    }
+
+    TokenStream::empty()
+}
@@ -0,0 +1,25 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:expand-or-zero.rs
+// ignore-stage1
+
+#![feature(proc_macro)]
+
+extern crate expand_or_zero;
+
+use expand_or_zero::expand_or_zero;
+
+fn main() {
+    let _ = expand_or_zero!(1 2); //~ ERROR expected the end of the expression
+
+    // The inner expansion borrows the context lent out to the outer one.
+    let _ = expand_or_zero!(expand_or_zero!(3 4) + 1); //~ ERROR expected the end
+}
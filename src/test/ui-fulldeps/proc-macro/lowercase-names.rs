@@ -0,0 +1,23 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:lowercase-names.rs
+// ignore-stage1
+
+#![feature(proc_macro)]
+
+extern crate lowercase_names;
+
+use lowercase_names::lowercase_names;
+
+fn main() {
+    lowercase_names!(alpha, Beta, gamma); //~ ERROR name `Beta` is not lowercase
+    lowercase_names!(alpha, beta, alpha); //~ ERROR name `alpha` is listed twice
+}
@@ -0,0 +1,20 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(const_control_flow)]
+
+const A: u32 = match Some(3) {
+    Some(x) => x, //~ ERROR E0693
+    None => 0,
+};
+
+static B: u32 = if let Some(x) = Some(3) { x } else { 0 }; //~ ERROR E0693
+
+fn main() {}
@@ -0,0 +1,22 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z const-eval-stack-limit=3
+
+#![feature(const_fn)]
+#![allow(unconditional_recursion)]
+
+const fn f(x: usize) -> usize {
+    f(x) //~ ERROR constant evaluation error
+}
+
+fn main() {
+    let _ = [0u8; f(0)];
+}
@@ -0,0 +1,25 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z const-eval-step-limit=8
+
+#![feature(const_fn)]
+
+const fn g(x: usize) -> usize {
+    x //~ ERROR constant evaluation error
+}
+
+const fn f(x: usize) -> usize {
+    g(x)
+}
+
+fn main() {
+    let _ = [0u8; f(0)];
+}
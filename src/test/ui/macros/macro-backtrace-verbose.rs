@@ -0,0 +1,24 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z macro-backtrace-verbose
+
+macro_rules! config {
+    ($name:ident = $value:expr;) => {};
+    ($name:ident : $ty:ty = $value:expr;) => {};
+}
+
+macro_rules! outer {
+    () => { config!(port = 80,); }; //~ ERROR no rules expected the token `,`
+}
+
+fn main() {
+    outer!();
+}